@@ -155,7 +155,12 @@ fn bench_search_borrowed_crl_small(c: &mut Bencher) {
     let crl_bytes = load_or_generate("./benches/small.crl.der", SMALL_CRL_CERT_COUNT);
     let crl = BorrowedCertRevocationList::from_der(&crl_bytes).unwrap();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| {
+        black_box(assert!(matches!(
+            crl.find_serial(crl.issuer(), FAKE_SERIAL),
+            Ok(None)
+        )))
+    });
 }
 
 /// Benchmark searching a small CRL file in owned representation for a serial that does not
@@ -167,7 +172,12 @@ fn bench_search_owned_crl_small(c: &mut Bencher) {
         .to_owned()
         .unwrap();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| {
+        black_box(assert!(matches!(
+            crl.find_serial(crl.issuer(), FAKE_SERIAL),
+            Ok(None)
+        )))
+    });
 }
 
 /// Benchmark searching a medium CRL file in borrowed representation for a serial that does not
@@ -176,7 +186,12 @@ fn bench_search_borrowed_crl_medium(c: &mut Bencher) {
     let crl_bytes = load_or_generate("./benches/medium.crl.der", MEDIUM_CRL_CERT_COUNT);
     let crl = BorrowedCertRevocationList::from_der(&crl_bytes).unwrap();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| {
+        black_box(assert!(matches!(
+            crl.find_serial(crl.issuer(), FAKE_SERIAL),
+            Ok(None)
+        )))
+    });
 }
 
 /// Benchmark searching a medium CRL file in owned representation for a serial that does not
@@ -188,7 +203,12 @@ fn bench_search_owned_crl_medium(c: &mut Bencher) {
         .to_owned()
         .unwrap();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| {
+        black_box(assert!(matches!(
+            crl.find_serial(crl.issuer(), FAKE_SERIAL),
+            Ok(None)
+        )))
+    });
 }
 
 /// Benchmark searching a large CRL file in borrowed representation for a serial that does not
@@ -197,7 +217,12 @@ fn bench_search_borrowed_crl_large(c: &mut Bencher) {
     let crl_bytes = load_or_generate("./benches/large.crl.der", LARGE_CRL_CERT_COUNT);
     let crl = BorrowedCertRevocationList::from_der(&crl_bytes).unwrap();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| {
+        black_box(assert!(matches!(
+            crl.find_serial(crl.issuer(), FAKE_SERIAL),
+            Ok(None)
+        )))
+    });
 }
 
 /// Benchmark searching a large CRL file in owned representation for a serial that does not
@@ -209,7 +234,12 @@ fn bench_search_owned_crl_large(c: &mut Bencher) {
         .to_owned()
         .unwrap();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| {
+        black_box(assert!(matches!(
+            crl.find_serial(crl.issuer(), FAKE_SERIAL),
+            Ok(None)
+        )))
+    });
 }
 
 benchmark_group!(
@@ -15,9 +15,14 @@
 use crate::der::Tag;
 use crate::der::{self, DerIterator, FromDer, CONSTRUCTED, CONTEXT_SPECIFIC};
 use crate::signed_data::SignedData;
+#[cfg(feature = "alloc")]
+use crate::subject_name::GeneralName;
 use crate::x509::{remember_extension, set_extension_once, DistributionPointName, Extension};
 use crate::Error;
 
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+
 /// An enumeration indicating whether a [`Cert`] is a leaf end-entity cert, or a linked
 /// list node from the CA `Cert` to a child `Cert` it issued.
 pub enum EndEntityOrCa<'a> {
@@ -28,6 +33,40 @@ pub enum EndEntityOrCa<'a> {
     Ca(&'a Cert<'a>),
 }
 
+/// An owned intermediate certificate.
+///
+/// This holds the DER of a certificate that has been checked to parse, behind shared
+/// ownership, so that intermediates can be cached and shared between threads cheaply. Use
+/// [`OwnedIntermediateCert::der`] to supply it to verification.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug)]
+pub struct OwnedIntermediateCert {
+    der: Arc<[u8]>,
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&[u8]> for OwnedIntermediateCert {
+    type Error = Error;
+
+    /// Parse the ASN.1 DER-encoded X.509 encoding of the certificate
+    /// `cert_der`, and take a copy of it.
+    fn try_from(cert_der: &[u8]) -> Result<Self, Self::Error> {
+        Cert::from_der(untrusted::Input::from(cert_der), EndEntityOrCa::EndEntity)?;
+        Ok(Self {
+            der: Arc::from(cert_der),
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedIntermediateCert {
+    /// The DER encoding of the certificate.
+    pub fn der(&self) -> &[u8] {
+        &self.der
+    }
+}
+
 /// A parsed X509 certificate.
 pub struct Cert<'a> {
     pub(crate) ee_or_ca: EndEntityOrCa<'a>,
@@ -49,6 +88,10 @@ pub struct Cert<'a> {
     pub(crate) name_constraints: Option<untrusted::Input<'a>>,
     pub(crate) subject_alt_name: Option<untrusted::Input<'a>>,
     pub(crate) crl_distribution_points: Option<untrusted::Input<'a>>,
+    pub(crate) authority_key_identifier: Option<untrusted::Input<'a>>,
+    pub(crate) subject_key_identifier: Option<untrusted::Input<'a>>,
+    pub(crate) authority_info_access: Option<untrusted::Input<'a>>,
+    pub(crate) certificate_policies: Option<untrusted::Input<'a>>,
 }
 
 impl<'a> Cert<'a> {
@@ -102,6 +145,10 @@ impl<'a> Cert<'a> {
                 name_constraints: None,
                 subject_alt_name: None,
                 crl_distribution_points: None,
+                authority_key_identifier: None,
+                subject_key_identifier: None,
+                authority_info_access: None,
+                certificate_policies: None,
             };
 
             if !tbs.at_end() {
@@ -149,12 +196,39 @@ impl<'a> Cert<'a> {
     }
 
     /// Returns an iterator over the certificate's cRLDistributionPoints extension values, if any.
-    #[allow(dead_code)] // TODO(@cpu): remove once used in CRL validation.
     pub(crate) fn crl_distribution_points(
         &self,
     ) -> Option<impl Iterator<Item = Result<CrlDistributionPoint<'a>, Error>>> {
         self.crl_distribution_points.map(DerIterator::new)
     }
+
+    /// Returns the keyIdentifier of the certificate's authorityKeyIdentifier extension, if any.
+    ///
+    /// The extension is only used as a hint for locating issuers and CRLs, so a malformed value
+    /// is treated as absent rather than as an error.
+    pub(crate) fn authority_key_id(&self) -> Option<untrusted::Input<'a>> {
+        key_identifier(self.authority_key_identifier?)
+            .ok()
+            .flatten()
+    }
+
+    /// Returns an iterator over the caIssuers URIs of the certificate's
+    /// authorityInfoAccess extension. Iteration stops at the first malformed entry.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn ca_issuers(&self) -> impl Iterator<Item = untrusted::Input<'a>> {
+        DerIterator::<AccessDescription>::new(
+            self.authority_info_access
+                .unwrap_or_else(|| untrusted::Input::from(&[])),
+        )
+        .map_while(Result::ok)
+        .filter_map(|access| match access {
+            AccessDescription {
+                method,
+                location: GeneralName::UniformResourceIdentifier(uri),
+            } if method == ID_AD_CA_ISSUERS => Some(uri),
+            _ => None,
+        })
+    }
 }
 
 // mozilla::pkix supports v1, v2, v3, and v4, including both the implicit
@@ -195,12 +269,27 @@ fn remember_cert_extension<'a>(
     cert: &mut Cert<'a>,
     extension: &Extension<'a>,
 ) -> Result<(), Error> {
-    // We don't do anything with certificate policies so we can safely ignore
-    // all policy-related stuff. We assume that the policy-related extensions
-    // are not marked critical.
+    // Certificate policies are only consulted when a trust anchor constrains them, and we don't
+    // support policy mapping at all. We assume that the policy-related extensions are not marked
+    // critical.
+
+    // id-pe-authorityInfoAccess 1.3.6.1.5.5.7.1.1 is the only extension we remember from
+    // outside of the id-ce arc.
+    if extension.id == ID_PE_AUTHORITY_INFO_ACCESS {
+        return remember_hint_extension(&mut cert.authority_info_access, extension, Tag::Sequence);
+    }
 
     remember_extension(extension, |id| {
         let out = match id {
+            // id-ce-subjectKeyIdentifier 2.5.29.14
+            14 => {
+                return remember_hint_extension(
+                    &mut cert.subject_key_identifier,
+                    extension,
+                    Tag::OctetString,
+                )
+            }
+
             // id-ce-keyUsage 2.5.29.15.
             15 => &mut cert.key_usage,
 
@@ -216,6 +305,21 @@ fn remember_cert_extension<'a>(
             // id-ce-cRLDistributionPoints 2.5.29.31
             31 => &mut cert.crl_distribution_points,
 
+            // id-ce-certificatePolicies 2.5.29.32
+            32 => {
+                extension.unsupported()?;
+                &mut cert.certificate_policies
+            }
+
+            // id-ce-authorityKeyIdentifier 2.5.29.35
+            35 => {
+                return remember_hint_extension(
+                    &mut cert.authority_key_identifier,
+                    extension,
+                    Tag::Sequence,
+                )
+            }
+
             // id-ce-extKeyUsage 2.5.29.37
             37 => &mut cert.eku,
 
@@ -235,6 +339,76 @@ fn remember_cert_extension<'a>(
     })
 }
 
+/// Remember the value of `extension`, one of the extensions only used as hints for locating
+/// issuers and CRLs, if it's a well-formed `tag`. A malformed or repeated value is ignored
+/// rather than treated as an error, since the certificate can be verified without it; like an
+/// unsupported extension, it's only an error when marked critical.
+fn remember_hint_extension<'a>(
+    destination: &mut Option<untrusted::Input<'a>>,
+    extension: &Extension<'a>,
+    tag: Tag,
+) -> Result<(), Error> {
+    extension.unsupported()?;
+    if destination.is_none() {
+        *destination = extension
+            .value
+            .read_all(Error::BadDer, |value| {
+                der::expect_tag_and_get_value(value, tag)
+            })
+            .ok();
+    }
+    Ok(())
+}
+
+/// Returns the keyIdentifier of `aki`, the contents of an AuthorityKeyIdentifier SEQUENCE, if
+/// it has one.
+pub(crate) fn key_identifier(aki: untrusted::Input) -> Result<Option<untrusted::Input>, Error> {
+    #[allow(clippy::identity_op)]
+    const KEY_IDENTIFIER_TAG: u8 = CONTEXT_SPECIFIC | 0;
+
+    aki.read_all(Error::BadDer, |aki| {
+        // RFC 5280 §4.2.1.1: keyIdentifier is the first, optional, field of the SEQUENCE.
+        if !aki.peek(KEY_IDENTIFIER_TAG) {
+            aki.skip_to_end();
+            return Ok(None);
+        }
+        let (_, key_id) = der::read_tag_and_get_value(aki)?;
+        aki.skip_to_end();
+        Ok(Some(key_id))
+    })
+}
+
+// id-pe-authorityInfoAccess 1.3.6.1.5.5.7.1.1 - RFC 5280 §4.2.2.1
+static ID_PE_AUTHORITY_INFO_ACCESS: untrusted::Input =
+    untrusted::Input::from(&oid!(1, 3, 6, 1, 5, 5, 7, 1, 1));
+
+// id-ad-caIssuers 1.3.6.1.5.5.7.48.2 - RFC 5280 §4.2.2.1
+#[cfg(feature = "alloc")]
+static ID_AD_CA_ISSUERS: untrusted::Input =
+    untrusted::Input::from(&oid!(1, 3, 6, 1, 5, 5, 7, 48, 2));
+
+/// An AccessDescription from an authorityInfoAccess extension, as described in RFC 5280
+/// section 4.2.2.1[^1].
+///
+/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.2.1>
+#[cfg(feature = "alloc")]
+struct AccessDescription<'a> {
+    method: untrusted::Input<'a>,
+    location: GeneralName<'a>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> FromDer<'a> for AccessDescription<'a> {
+    fn from_der(reader: &mut untrusted::Reader<'a>) -> Result<Self, Error> {
+        der::nested(reader, Tag::Sequence, Error::BadDer, |der| {
+            Ok(AccessDescription {
+                method: der::expect_tag_and_get_value(der, Tag::OID)?,
+                location: GeneralName::from_der(der)?,
+            })
+        })
+    }
+}
+
 /// A certificate revocation list (CRL) distribution point, describing a source of
 /// CRL information for a given certificate as described in RFC 5280 section 4.2.3.13[^1].
 ///
@@ -254,7 +428,6 @@ pub(crate) struct CrlDistributionPoint<'a> {
 
 impl<'a> CrlDistributionPoint<'a> {
     /// Return the distribution point names (if any).
-    #[allow(dead_code)] // TODO(@cpu): remove this once used in CRL validation.
     pub(crate) fn names(&self) -> Result<Option<DistributionPointName<'a>>, Error> {
         self.distribution_point
             .map(|input| DistributionPointName::from_der(&mut untrusted::Reader::new(input)))
@@ -631,4 +804,44 @@ mod tests {
             expected_names
         );
     }
+
+    #[test]
+    fn test_hint_extensions_are_lenient() {
+        use super::remember_hint_extension;
+        use crate::der::Tag;
+        use crate::x509::Extension;
+        use crate::Error;
+
+        let extension = |critical, value: &'static [u8]| Extension {
+            critical,
+            id: untrusted::Input::from(&[]),
+            value: untrusted::Input::from(value),
+        };
+        let first: &[u8] = &[0x30, 0x01, 0x01];
+        let second: &[u8] = &[0x30, 0x01, 0x02];
+
+        // A malformed value is treated as absent.
+        let mut destination = None;
+        assert_eq!(
+            remember_hint_extension(
+                &mut destination,
+                &extension(false, &[0x04, 0x00]),
+                Tag::Sequence
+            ),
+            Ok(())
+        );
+        assert!(destination.is_none());
+
+        // A repeated value is ignored.
+        remember_hint_extension(&mut destination, &extension(false, first), Tag::Sequence).unwrap();
+        remember_hint_extension(&mut destination, &extension(false, second), Tag::Sequence)
+            .unwrap();
+        assert_eq!(destination.unwrap().as_slice_less_safe(), &[0x01]);
+
+        // A critical hint is an error, as for any unsupported extension.
+        assert_eq!(
+            remember_hint_extension(&mut None, &extension(true, first), Tag::Sequence),
+            Err(Error::UnsupportedCriticalExtension)
+        );
+    }
 }
@@ -0,0 +1,271 @@
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHORS DISCLAIM ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHORS BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::der::{FromDer, Tag};
+use crate::{Error, Time, TrustAnchor};
+
+/// A root certificate from an NSS `certdata.txt` file, along with the trust NSS places in it.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug)]
+pub struct CertdataRoot {
+    label: String,
+    cert_der: Vec<u8>,
+    server_auth_trusted: bool,
+    email_protection_trusted: bool,
+    server_distrust_after: Option<Time>,
+    email_distrust_after: Option<Time>,
+}
+
+impl CertdataRoot {
+    /// The `CKA_LABEL` of the certificate.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// The DER encoding of the certificate.
+    pub fn cert_der(&self) -> &[u8] {
+        &self.cert_der
+    }
+
+    /// Interpret the certificate as a [`TrustAnchor`].
+    pub fn trust_anchor(&self) -> Result<TrustAnchor<'_>, Error> {
+        TrustAnchor::try_from_cert_der(&self.cert_der)
+    }
+
+    /// True if NSS trusts the certificate to issue TLS server certificates
+    /// (`CKA_TRUST_SERVER_AUTH` is `CKT_NSS_TRUSTED_DELEGATOR`).
+    pub fn server_auth_trusted(&self) -> bool {
+        self.server_auth_trusted
+    }
+
+    /// True if NSS trusts the certificate to issue S/MIME certificates
+    /// (`CKA_TRUST_EMAIL_PROTECTION` is `CKT_NSS_TRUSTED_DELEGATOR`).
+    pub fn email_protection_trusted(&self) -> bool {
+        self.email_protection_trusted
+    }
+
+    /// TLS server certificates issued by this root with a notBefore later than this time
+    /// should not be trusted (`CKA_NSS_SERVER_DISTRUST_AFTER`).
+    pub fn server_distrust_after(&self) -> Option<Time> {
+        self.server_distrust_after
+    }
+
+    /// S/MIME certificates issued by this root with a notBefore later than this time
+    /// should not be trusted (`CKA_NSS_EMAIL_DISTRUST_AFTER`).
+    pub fn email_distrust_after(&self) -> Option<Time> {
+        self.email_distrust_after
+    }
+}
+
+/// Parse the root certificates out of the contents of an NSS `certdata.txt` file.
+///
+/// Every `CKO_CERTIFICATE` object is returned, in the order it appears, with its trust taken
+/// from the `CKO_NSS_TRUST` object that names the same issuer and serial number. Certificates
+/// without such an object are returned as trusted for nothing. Returns
+/// [`Error::BadCertdata`] if the file can't be parsed.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn parse_certdata(certdata: &str) -> Result<Vec<CertdataRoot>, Error> {
+    let objects = parse_objects(certdata)?;
+
+    let mut roots = Vec::new();
+    for object in objects
+        .iter()
+        .filter(|o| o.class() == Some("CKO_CERTIFICATE"))
+    {
+        let (issuer, serial) = (
+            object.bytes("CKA_ISSUER")?,
+            object.bytes("CKA_SERIAL_NUMBER")?,
+        );
+        let trust = objects.iter().find(|o| {
+            o.class() == Some("CKO_NSS_TRUST")
+                && o.bytes("CKA_ISSUER") == Ok(issuer)
+                && o.bytes("CKA_SERIAL_NUMBER") == Ok(serial)
+        });
+        let trusted = |name| {
+            trust.map_or(false, |trust| {
+                trust.token(name) == Some("CKT_NSS_TRUSTED_DELEGATOR")
+            })
+        };
+
+        roots.push(CertdataRoot {
+            label: object.utf8("CKA_LABEL")?,
+            cert_der: object.bytes("CKA_VALUE")?.to_vec(),
+            server_auth_trusted: trusted("CKA_TRUST_SERVER_AUTH"),
+            email_protection_trusted: trusted("CKA_TRUST_EMAIL_PROTECTION"),
+            server_distrust_after: object.distrust_after("CKA_NSS_SERVER_DISTRUST_AFTER")?,
+            email_distrust_after: object.distrust_after("CKA_NSS_EMAIL_DISTRUST_AFTER")?,
+        });
+    }
+
+    Ok(roots)
+}
+
+struct Object<'a> {
+    attributes: Vec<Attribute<'a>>,
+}
+
+impl<'a> Object<'a> {
+    fn get(&self, name: &str) -> Option<&Attribute<'a>> {
+        self.attributes.iter().find(|a| a.name == name)
+    }
+
+    fn class(&self) -> Option<&'a str> {
+        self.token("CKA_CLASS")
+    }
+
+    fn token(&self, name: &str) -> Option<&'a str> {
+        match self.get(name)?.value {
+            Value::Token(token) => Some(token),
+            _ => None,
+        }
+    }
+
+    fn bytes(&self, name: &str) -> Result<&[u8], Error> {
+        match self.get(name).map(|a| &a.value) {
+            Some(Value::Bytes(bytes)) => Ok(bytes),
+            _ => Err(Error::BadCertdata),
+        }
+    }
+
+    fn utf8(&self, name: &str) -> Result<String, Error> {
+        match self.get(name).map(|a| &a.value) {
+            Some(Value::Utf8(bytes)) => {
+                String::from_utf8(bytes.clone()).map_err(|_| Error::BadCertdata)
+            }
+            _ => Err(Error::BadCertdata),
+        }
+    }
+
+    /// Distrust-after attributes are either `CK_BBOOL CK_FALSE` or the bare contents of a
+    /// UTCTime.
+    fn distrust_after(&self, name: &str) -> Result<Option<Time>, Error> {
+        let contents = match self.get(name).map(|a| &a.value) {
+            None | Some(Value::Token("CK_FALSE")) => return Ok(None),
+            Some(Value::Bytes(contents)) => contents,
+            Some(_) => return Err(Error::BadCertdata),
+        };
+
+        let len = u8::try_from(contents.len())
+            .ok()
+            .filter(|len| *len < 0x80)
+            .ok_or(Error::BadCertdata)?;
+        let mut der = Vec::with_capacity(contents.len() + 2);
+        der.push(Tag::UTCTime.into());
+        der.push(len);
+        der.extend_from_slice(contents);
+        untrusted::Input::from(&der)
+            .read_all(Error::BadCertdata, Time::from_der)
+            .map(Some)
+            .map_err(|_| Error::BadCertdata)
+    }
+}
+
+struct Attribute<'a> {
+    name: &'a str,
+    value: Value<'a>,
+}
+
+enum Value<'a> {
+    /// A `CK_*` typed value, such as `CK_OBJECT_CLASS CKO_CERTIFICATE` or `CK_BBOOL CK_TRUE`.
+    Token(&'a str),
+    /// A `MULTILINE_OCTAL` value.
+    Bytes(Vec<u8>),
+    /// A `UTF8` quoted string, with any octal escapes decoded.
+    Utf8(Vec<u8>),
+}
+
+fn parse_objects(certdata: &str) -> Result<Vec<Object<'_>>, Error> {
+    let mut lines = certdata
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    // Everything before BEGINDATA is file-level metadata.
+    if !lines.any(|line| line == "BEGINDATA") {
+        return Err(Error::BadCertdata);
+    }
+
+    let mut objects = Vec::<Object>::new();
+    while let Some(line) = lines.next() {
+        let mut fields = line.splitn(3, ' ');
+        let (name, kind, rest) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(name), Some(kind), rest) => (name, kind, rest.unwrap_or_default().trim()),
+            _ => return Err(Error::BadCertdata),
+        };
+
+        let value = match kind {
+            "MULTILINE_OCTAL" => {
+                let mut bytes = Vec::new();
+                loop {
+                    match lines.next() {
+                        Some("END") => break,
+                        Some(line) => unescape_octal(line, &mut bytes)?,
+                        None => return Err(Error::BadCertdata),
+                    }
+                }
+                Value::Bytes(bytes)
+            }
+            "UTF8" => {
+                let quoted = rest
+                    .strip_prefix('"')
+                    .and_then(|rest| rest.strip_suffix('"'))
+                    .ok_or(Error::BadCertdata)?;
+                let mut bytes = Vec::new();
+                unescape_octal(quoted, &mut bytes)?;
+                Value::Utf8(bytes)
+            }
+            _ => Value::Token(rest),
+        };
+
+        // Each object starts with its class.
+        if name == "CKA_CLASS" {
+            objects.push(Object {
+                attributes: Vec::new(),
+            });
+        }
+        objects
+            .last_mut()
+            .ok_or(Error::BadCertdata)?
+            .attributes
+            .push(Attribute { name, value });
+    }
+
+    Ok(objects)
+}
+
+/// Append `text` to `out`, decoding any `\ooo` octal escapes.
+fn unescape_octal(text: &str, out: &mut Vec<u8>) -> Result<(), Error> {
+    let mut bytes = text.bytes();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            out.push(b);
+            continue;
+        }
+
+        let mut value = 0u8;
+        for _ in 0..3 {
+            let digit = match bytes.next() {
+                Some(digit @ b'0'..=b'7') => digit - b'0',
+                _ => return Err(Error::BadCertdata),
+            };
+            value = value
+                .checked_mul(8)
+                .and_then(|value| value.checked_add(digit))
+                .ok_or(Error::BadCertdata)?;
+        }
+        out.push(value);
+    }
+    Ok(())
+}
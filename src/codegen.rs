@@ -0,0 +1,162 @@
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHORS DISCLAIM ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHORS BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Helpers for build scripts that embed a root set in a binary.
+
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::{Error, OwnedTrustAnchor};
+
+/// Render `anchors` as Rust source for a `static` named `name`, of type
+/// `&[<crate_path>::TrustAnchor<'static>]`, where `crate_path` is the path by which the
+/// generated source refers to this crate: usually `webpki`, or `::webpki`.
+///
+/// This is intended for build scripts: write the result to a file in `OUT_DIR` and `include!`
+/// it, so that the subjects and SPKIs are extracted at build time and no parsing happens at
+/// startup.
+///
+/// ```ignore
+/// include!(concat!(env!("OUT_DIR"), "/roots.rs"));
+/// ```
+///
+/// Returns [`Error::InvalidRustIdentifier`] if `name` isn't a Rust identifier, or `crate_path`
+/// isn't a Rust path.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn generate_trust_anchors_source(
+    crate_path: &str,
+    name: &str,
+    anchors: &[OwnedTrustAnchor],
+) -> Result<String, Error> {
+    if !is_identifier(name) || !is_path(crate_path) {
+        return Err(Error::InvalidRustIdentifier);
+    }
+
+    let mut source = String::new();
+    write_trust_anchors(&mut source, crate_path, name, anchors)
+        .expect("writing to a String can't fail");
+    Ok(source)
+}
+
+fn write_trust_anchors(
+    out: &mut String,
+    crate_path: &str,
+    name: &str,
+    anchors: &[OwnedTrustAnchor],
+) -> fmt::Result {
+    writeln!(
+        out,
+        "pub static {}: &[{}::TrustAnchor<'static>] = &[",
+        name, crate_path
+    )?;
+    for anchor in anchors {
+        let anchor = anchor.to_trust_anchor();
+        writeln!(out, "    {}::TrustAnchor {{", crate_path)?;
+        writeln!(out, "        subject: {},", ByteString(anchor.subject))?;
+        writeln!(out, "        spki: {},", ByteString(anchor.spki))?;
+        match anchor.name_constraints {
+            Some(nc) => writeln!(out, "        name_constraints: Some({}),", ByteString(nc))?,
+            None => writeln!(out, "        name_constraints: None,")?,
+        }
+        writeln!(out, "    }},")?;
+    }
+    writeln!(out, "];")
+}
+
+/// Load every certificate in the directory `dir` as an [`OwnedTrustAnchor`].
+///
+/// Files named `*.der` must contain a single DER-encoded certificate; files named `*.pem` or
+/// `*.crt` may contain any number of `CERTIFICATE` sections. Other files, and other kinds of PEM
+/// section, are ignored. Files are read in order of name, so the result is deterministic.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn load_trust_anchors_from_dir(
+    dir: &std::path::Path,
+) -> std::io::Result<alloc::vec::Vec<OwnedTrustAnchor>> {
+    use std::io::{Error as IoError, ErrorKind};
+
+    let invalid = |err: crate::Error| IoError::new(ErrorKind::InvalidData, err);
+
+    let mut paths = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<alloc::vec::Vec<_>, _>>()?;
+    paths.sort();
+
+    let mut anchors = alloc::vec::Vec::new();
+    for path in paths {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("der") => {
+                let der = std::fs::read(&path)?;
+                anchors.push(OwnedTrustAnchor::try_from_cert_der(&der).map_err(invalid)?);
+            }
+            Some("pem") | Some("crt") => {
+                let pem = std::fs::read(&path)?;
+                for section in crate::pem_sections(&pem) {
+                    let section = section.map_err(invalid)?;
+                    if section.kind() != Some(crate::PemSectionKind::Certificate) {
+                        continue;
+                    }
+                    let der = section.decode().map_err(invalid)?;
+                    anchors.push(OwnedTrustAnchor::try_from_cert_der(&der).map_err(invalid)?);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(anchors)
+}
+
+/// Return true if `name` is a Rust identifier that can name a `static`: not a keyword, and not
+/// a raw identifier.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+        && !KEYWORDS.contains(&name)
+}
+
+/// Return true if `path` is a Rust path made of identifiers, optionally starting with `::`,
+/// such as `webpki` or `::rustls::webpki`.
+fn is_path(path: &str) -> bool {
+    let (global, relative) = match path.strip_prefix("::") {
+        Some(relative) => (true, relative),
+        None => (false, path),
+    };
+    relative.split("::").enumerate().all(|(i, segment)| {
+        is_identifier(segment)
+            || (i == 0 && !global && matches!(segment, "crate" | "self" | "super"))
+    })
+}
+
+/// The strict and reserved keywords of the 2021 edition, which can't be used as identifiers.
+static KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Formats as a Rust byte string literal.
+struct ByteString<'a>(&'a [u8]);
+
+impl fmt::Display for ByteString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("b\"")?;
+        for b in self.0 {
+            write!(f, "\\x{:02x}", b)?;
+        }
+        f.write_str("\"")
+    }
+}
@@ -12,10 +12,15 @@
 // ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
 // OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
-use crate::cert::lenient_certificate_serial_number;
+use core::cmp::Ordering;
+
+use crate::cert::{key_identifier, lenient_certificate_serial_number, Cert, EndEntityOrCa};
 use crate::der::{self, DerIterator, FromDer, Tag, CONSTRUCTED, CONTEXT_SPECIFIC};
 use crate::signed_data::{self, SignedData};
+use crate::subject_name::GeneralName;
 use crate::x509::{remember_extension, set_extension_once, DistributionPointName, Extension};
+#[cfg(feature = "ring")]
+use crate::{signature_cache, SignatureCache};
 use crate::{Error, SignatureVerificationAlgorithm, Time};
 
 #[cfg(feature = "alloc")]
@@ -28,16 +33,80 @@ use private::Sealed;
 /// [`BorrowedCertRevocationList`].
 ///
 /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
-pub trait CertRevocationList: Sealed {
+pub trait CertRevocationList: Sealed + Send + Sync {
     /// Return the DER encoded issuer of the CRL.
     fn issuer(&self) -> &[u8];
 
     /// Return the DER encoded issuing distribution point of the CRL, if any.
     fn issuing_distribution_point(&self) -> Option<&[u8]>;
 
-    /// Try to find a revoked certificate in the CRL by DER encoded serial number. This
-    /// may yield an error if the CRL has malformed revoked certificates.
-    fn find_serial(&self, serial: &[u8]) -> Result<Option<BorrowedRevokedCert>, Error>;
+    /// Return the time the CRL was issued (its thisUpdate field).
+    fn this_update(&self) -> Time;
+
+    /// Return the time by which the next CRL will be issued (its nextUpdate field).
+    fn next_update(&self) -> Time;
+
+    /// Return the CRL's cRLNumber, if it has one, as a big-endian unsigned integer with no
+    /// leading zeros.
+    fn crl_number(&self) -> Option<&[u8]>;
+
+    /// Return the keyIdentifier of the CRL's authorityKeyIdentifier extension, if any.
+    fn authority_key_identifier(&self) -> Option<&[u8]>;
+
+    /// Return the BaseCRLNumber of the CRL's deltaCRLIndicator, if it is a delta CRL, as a
+    /// big-endian unsigned integer with no leading zeros.
+    fn base_crl_number(&self) -> Option<&[u8]>;
+
+    /// Return true if this CRL supersedes `other`: the two have the same issuer and authority
+    /// key identifier, are both complete CRLs or both delta CRLs, and this CRL has a higher
+    /// cRLNumber.
+    ///
+    /// A CRL without a cRLNumber neither supersedes, nor is superseded by, any other.
+    fn supersedes(&self, other: &dyn CertRevocationList) -> bool {
+        self.issuer() == other.issuer()
+            && self.authority_key_identifier() == other.authority_key_identifier()
+            && self.base_crl_number().is_some() == other.base_crl_number().is_some()
+            && matches!(
+                (self.crl_number(), other.crl_number()),
+                (Some(this), Some(other)) if compare_crl_numbers(this, other) == Ordering::Greater
+            )
+    }
+
+    /// Return true if this is a delta CRL that updates `base`, a complete CRL, as described
+    /// in RFC 5280 §5.2.4[^1]: the two have the same issuer, authority key identifier and
+    /// issuing distribution point, `base` is at least as new as the complete CRL this delta
+    /// was built from, and this delta is newer than `base`.
+    ///
+    /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5.2.4>
+    fn is_delta_for(&self, base: &dyn CertRevocationList) -> bool {
+        let (base_crl_number, delta_number, complete_number) =
+            match (self.base_crl_number(), self.crl_number(), base.crl_number()) {
+                (Some(base_crl_number), Some(delta_number), Some(complete_number)) => {
+                    (base_crl_number, delta_number, complete_number)
+                }
+                _ => return false,
+            };
+
+        base.base_crl_number().is_none()
+            && self.issuer() == base.issuer()
+            && self.authority_key_identifier() == base.authority_key_identifier()
+            && self.issuing_distribution_point() == base.issuing_distribution_point()
+            && compare_crl_numbers(complete_number, base_crl_number) != Ordering::Less
+            && compare_crl_numbers(delta_number, complete_number) == Ordering::Greater
+    }
+
+    /// Try to find a revoked certificate in the CRL by the DER encoded issuer and serial number
+    /// of the certificate. This may yield an error if the CRL has malformed revoked certificates.
+    ///
+    /// Entries are from the CRL's issuer, unless an indirect CRL attributes them to another
+    /// issuer with the certificateIssuer entry extension; see
+    /// [`BorrowedRevokedCert::certificate_issuer`]. The extension is an error in a CRL that
+    /// isn't indirect.
+    fn find_serial(
+        &self,
+        issuer: &[u8],
+        serial: &[u8],
+    ) -> Result<Option<BorrowedRevokedCert>, Error>;
 
     /// Verify the CRL signature using the issuer's subject public key information (SPKI)
     /// and a list of supported signature verification algorithms.
@@ -48,6 +117,11 @@ pub trait CertRevocationList: Sealed {
     ) -> Result<(), Error>;
 }
 
+/// Compare two cRLNumbers, each a big-endian unsigned integer with no leading zeros.
+pub(crate) fn compare_crl_numbers(a: &[u8], b: &[u8]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
 /// Owned representation of a RFC 5280[^1] profile Certificate Revocation List (CRL).
 ///
 /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
@@ -56,18 +130,44 @@ pub trait CertRevocationList: Sealed {
 #[derive(Debug, Clone)]
 pub struct OwnedCertRevocationList {
     /// A map of the revoked certificates contained in then CRL, keyed by the DER encoding
-    /// of the revoked cert's serial number.
-    revoked_certs: HashMap<Vec<u8>, OwnedRevokedCert>,
+    /// of the revoked cert's serial number. An indirect CRL can revoke certificates with the
+    /// same serial number from several issuers.
+    revoked_certs: HashMap<Vec<u8>, Vec<OwnedRevokedCert>>,
 
     issuer: Vec<u8>,
 
     issuing_distribution_point: Option<Vec<u8>>,
 
+    this_update: Time,
+
+    next_update: Time,
+
+    crl_number: Option<Vec<u8>>,
+
+    authority_key_identifier: Option<Vec<u8>>,
+
+    base_crl_number: Option<Vec<u8>>,
+
     signed_data: signed_data::OwnedSignedData,
 }
 
 #[cfg(feature = "alloc")]
-impl Sealed for OwnedCertRevocationList {}
+impl Sealed for OwnedCertRevocationList {
+    #[cfg(feature = "ring")]
+    fn verify_signature_with_cache(
+        &self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        issuer_spki: &[u8],
+        cache: &dyn SignatureCache,
+    ) -> Result<(), Error> {
+        signature_cache::verify_signed_data(
+            supported_sig_algs,
+            untrusted::Input::from(issuer_spki),
+            &self.signed_data.borrow(),
+            cache,
+        )
+    }
+}
 
 #[cfg(feature = "alloc")]
 impl CertRevocationList for OwnedCertRevocationList {
@@ -79,13 +179,46 @@ impl CertRevocationList for OwnedCertRevocationList {
         self.issuing_distribution_point.as_deref()
     }
 
-    fn find_serial(&self, serial: &[u8]) -> Result<Option<BorrowedRevokedCert>, Error> {
+    fn this_update(&self) -> Time {
+        self.this_update
+    }
+
+    fn next_update(&self) -> Time {
+        self.next_update
+    }
+
+    fn crl_number(&self) -> Option<&[u8]> {
+        self.crl_number.as_deref()
+    }
+
+    fn authority_key_identifier(&self) -> Option<&[u8]> {
+        self.authority_key_identifier.as_deref()
+    }
+
+    fn base_crl_number(&self) -> Option<&[u8]> {
+        self.base_crl_number.as_deref()
+    }
+
+    fn find_serial(
+        &self,
+        issuer: &[u8],
+        serial: &[u8],
+    ) -> Result<Option<BorrowedRevokedCert>, Error> {
         // note: this is infallible for the owned representation because we process all
         // revoked certificates at the time of construction to build the `revoked_certs` map,
         // returning any encountered errors at that time.
         Ok(self
             .revoked_certs
             .get(serial)
+            .into_iter()
+            .flatten()
+            .find(|owned_revoked_cert| {
+                owned_revoked_cert
+                    .certificate_issuer
+                    .as_deref()
+                    .unwrap_or(&self.issuer)
+                    == issuer
+            })
             .map(|owned_revoked_cert| owned_revoked_cert.borrow()))
     }
 
@@ -102,6 +235,93 @@ impl CertRevocationList for OwnedCertRevocationList {
     }
 }
 
+/// An [`OwnedCertRevocationList`] whose signature has already been verified with some
+/// issuer public keys, so that checking it against those keys again is free.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub(crate) struct PrevalidatedCrl {
+    pub(crate) crl: OwnedCertRevocationList,
+
+    /// The SubjectPublicKeyInfo contents that the CRL's signature was verified with.
+    pub(crate) verified_spkis: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "alloc")]
+impl Sealed for PrevalidatedCrl {
+    #[cfg(feature = "ring")]
+    fn verify_signature_with_cache(
+        &self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        issuer_spki: &[u8],
+        cache: &dyn SignatureCache,
+    ) -> Result<(), Error> {
+        if self.is_verified_by(issuer_spki) {
+            return Ok(());
+        }
+        self.crl
+            .verify_signature_with_cache(supported_sig_algs, issuer_spki, cache)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PrevalidatedCrl {
+    fn is_verified_by(&self, issuer_spki: &[u8]) -> bool {
+        self.verified_spkis
+            .iter()
+            .any(|spki| spki.as_slice() == issuer_spki)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl CertRevocationList for PrevalidatedCrl {
+    fn issuer(&self) -> &[u8] {
+        self.crl.issuer()
+    }
+
+    fn issuing_distribution_point(&self) -> Option<&[u8]> {
+        self.crl.issuing_distribution_point()
+    }
+
+    fn this_update(&self) -> Time {
+        self.crl.this_update()
+    }
+
+    fn next_update(&self) -> Time {
+        self.crl.next_update()
+    }
+
+    fn crl_number(&self) -> Option<&[u8]> {
+        self.crl.crl_number()
+    }
+
+    fn authority_key_identifier(&self) -> Option<&[u8]> {
+        self.crl.authority_key_identifier()
+    }
+
+    fn base_crl_number(&self) -> Option<&[u8]> {
+        self.crl.base_crl_number()
+    }
+
+    fn find_serial(
+        &self,
+        issuer: &[u8],
+        serial: &[u8],
+    ) -> Result<Option<BorrowedRevokedCert>, Error> {
+        self.crl.find_serial(issuer, serial)
+    }
+
+    fn verify_signature(
+        &self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        issuer_spki: &[u8],
+    ) -> Result<(), Error> {
+        if self.is_verified_by(issuer_spki) {
+            return Ok(());
+        }
+        self.crl.verify_signature(supported_sig_algs, issuer_spki)
+    }
+}
+
 /// Borrowed representation of a RFC 5280[^1] profile Certificate Revocation List (CRL).
 ///
 /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
@@ -117,6 +337,24 @@ pub struct BorrowedCertRevocationList<'a> {
     /// An optional CRL extension that identifies the CRL distribution point and scope for the CRL.
     issuing_distribution_point: Option<untrusted::Input<'a>>,
 
+    /// Whether the issuing distribution point sets the indirectCRL indicator.
+    indirect_crl: bool,
+
+    /// The time the CRL was issued.
+    this_update: Time,
+
+    /// The time by which the next CRL will be issued.
+    next_update: Time,
+
+    /// The CRL's cRLNumber, without leading zeros.
+    crl_number: Option<untrusted::Input<'a>>,
+
+    /// The keyIdentifier of the CRL's authorityKeyIdentifier extension.
+    authority_key_identifier: Option<untrusted::Input<'a>>,
+
+    /// The BaseCRLNumber of a delta CRL, without leading zeros.
+    base_crl_number: Option<untrusted::Input<'a>>,
+
     /// List of certificates revoked by the issuer in this CRL.
     revoked_certs: untrusted::Input<'a>,
 }
@@ -128,7 +366,6 @@ impl<'a> BorrowedCertRevocationList<'a> {
     ///   * CRL versions other than version 2.
     ///   * CRLs missing the next update field.
     ///   * CRLs missing certificate revocation list extensions.
-    ///   * Delta CRLs.
     ///   * CRLs larger than (2^32)-1 bytes in size.
     ///
     /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
@@ -144,12 +381,13 @@ impl<'a> BorrowedCertRevocationList<'a> {
     pub fn to_owned(&self) -> Result<OwnedCertRevocationList, Error> {
         // Parse and collect the CRL's revoked cert entries, ensuring there are no errors. With
         // the full set in-hand, create a lookup map by serial number for fast revocation checking.
-        let revoked_certs = self
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?
-            .iter()
-            .map(|revoked_cert| (revoked_cert.serial_number.to_vec(), revoked_cert.to_owned()))
-            .collect::<HashMap<_, _>>();
+        let mut revoked_certs = HashMap::<_, Vec<_>>::new();
+        for revoked_cert in self.into_iter().collect::<Result<Vec<_>, _>>()? {
+            revoked_certs
+                .entry(revoked_cert.serial_number.to_vec())
+                .or_default()
+                .push(revoked_cert.to_owned());
+        }
 
         Ok(OwnedCertRevocationList {
             signed_data: self.signed_data.to_owned(),
@@ -157,6 +395,17 @@ impl<'a> BorrowedCertRevocationList<'a> {
             issuing_distribution_point: self
                 .issuing_distribution_point
                 .map(|idp| idp.as_slice_less_safe().to_vec()),
+            this_update: self.this_update,
+            next_update: self.next_update,
+            crl_number: self
+                .crl_number
+                .map(|number| number.as_slice_less_safe().to_vec()),
+            authority_key_identifier: self
+                .authority_key_identifier
+                .map(|key_id| key_id.as_slice_less_safe().to_vec()),
+            base_crl_number: self
+                .base_crl_number
+                .map(|number| number.as_slice_less_safe().to_vec()),
             revoked_certs,
         })
     }
@@ -165,29 +414,19 @@ impl<'a> BorrowedCertRevocationList<'a> {
         remember_extension(extension, |id| {
             match id {
                 // id-ce-cRLNumber 2.5.29.20 - RFC 5280 §5.2.3
-                20 => {
-                    // RFC 5280 §5.2.3:
-                    //   CRL verifiers MUST be able to handle CRLNumber values
-                    //   up to 20 octets.  Conforming CRL issuers MUST NOT use CRLNumber
-                    //   values longer than 20 octets.
-                    //
-                    extension.value.read_all(Error::InvalidCrlNumber, |der| {
-                        let crl_number = der::nonnegative_integer(der)
-                            .map_err(|_| Error::InvalidCrlNumber)?
-                            .as_slice_less_safe();
-                        if crl_number.len() <= 20 {
-                            Ok(crl_number)
-                        } else {
-                            Err(Error::InvalidCrlNumber)
-                        }
-                    })?;
-                    // We enforce the cRLNumber is sensible, but don't retain the value for use.
-                    Ok(())
-                }
+                20 => set_extension_once(&mut self.crl_number, || {
+                    extension
+                        .value
+                        .read_all(Error::InvalidCrlNumber, crl_number)
+                }),
 
                 // id-ce-deltaCRLIndicator 2.5.29.27 - RFC 5280 §5.2.4
-                // We explicitly do not support delta CRLs.
-                27 => Err(Error::UnsupportedDeltaCrl),
+                // The BaseCRLNumber identifies the complete CRL the delta CRL updates.
+                27 => set_extension_once(&mut self.base_crl_number, || {
+                    extension
+                        .value
+                        .read_all(Error::InvalidCrlNumber, crl_number)
+                }),
 
                 // id-ce-issuingDistributionPoint 2.5.29.28 - RFC 5280 §5.2.4
                 // We recognize the extension and retain its value for use.
@@ -196,8 +435,18 @@ impl<'a> BorrowedCertRevocationList<'a> {
                 }
 
                 // id-ce-authorityKeyIdentifier 2.5.29.35 - RFC 5280 §5.2.1, §4.2.1.1
-                // We recognize the extension but don't retain its value for use.
-                35 => Ok(()),
+                // We recognize the extension and retain its keyIdentifier, if any, for use.
+                35 => {
+                    let aki = extension.value.read_all(Error::BadDer, |value| {
+                        der::expect_tag_and_get_value(value, Tag::Sequence)
+                    })?;
+                    match key_identifier(aki)? {
+                        Some(key_id) => {
+                            set_extension_once(&mut self.authority_key_identifier, || Ok(key_id))
+                        }
+                        None => Ok(()),
+                    }
+                }
 
                 // Unsupported extension
                 _ => extension.unsupported(),
@@ -206,7 +455,37 @@ impl<'a> BorrowedCertRevocationList<'a> {
     }
 }
 
-impl Sealed for BorrowedCertRevocationList<'_> {}
+/// Read a CRLNumber, or a BaseCRLNumber, which has the same syntax.
+fn crl_number<'a>(der: &mut untrusted::Reader<'a>) -> Result<untrusted::Input<'a>, Error> {
+    // RFC 5280 §5.2.3:
+    //   CRL verifiers MUST be able to handle CRLNumber values
+    //   up to 20 octets.  Conforming CRL issuers MUST NOT use CRLNumber
+    //   values longer than 20 octets.
+    //
+    let crl_number = der::nonnegative_integer(der).map_err(|_| Error::InvalidCrlNumber)?;
+    if crl_number.len() <= 20 {
+        Ok(crl_number)
+    } else {
+        Err(Error::InvalidCrlNumber)
+    }
+}
+
+impl Sealed for BorrowedCertRevocationList<'_> {
+    #[cfg(feature = "ring")]
+    fn verify_signature_with_cache(
+        &self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        issuer_spki: &[u8],
+        cache: &dyn SignatureCache,
+    ) -> Result<(), Error> {
+        signature_cache::verify_signed_data(
+            supported_sig_algs,
+            untrusted::Input::from(issuer_spki),
+            &self.signed_data,
+            cache,
+        )
+    }
+}
 
 impl CertRevocationList for BorrowedCertRevocationList<'_> {
     fn issuer(&self) -> &[u8] {
@@ -218,12 +497,40 @@ impl CertRevocationList for BorrowedCertRevocationList<'_> {
             .map(|der| der.as_slice_less_safe())
     }
 
-    fn find_serial(&self, serial: &[u8]) -> Result<Option<BorrowedRevokedCert>, Error> {
+    fn this_update(&self) -> Time {
+        self.this_update
+    }
+
+    fn next_update(&self) -> Time {
+        self.next_update
+    }
+
+    fn crl_number(&self) -> Option<&[u8]> {
+        self.crl_number.map(|number| number.as_slice_less_safe())
+    }
+
+    fn authority_key_identifier(&self) -> Option<&[u8]> {
+        self.authority_key_identifier
+            .map(|key_id| key_id.as_slice_less_safe())
+    }
+
+    fn base_crl_number(&self) -> Option<&[u8]> {
+        self.base_crl_number
+            .map(|number| number.as_slice_less_safe())
+    }
+
+    fn find_serial(
+        &self,
+        issuer: &[u8],
+        serial: &[u8],
+    ) -> Result<Option<BorrowedRevokedCert>, Error> {
         for revoked_cert_result in self {
             match revoked_cert_result {
                 Err(e) => return Err(e),
                 Ok(revoked_cert) => {
-                    if revoked_cert.serial_number.eq(serial) {
+                    if revoked_cert.serial_number.eq(serial)
+                        && revoked_cert.certificate_issuer.unwrap_or(self.issuer()) == issuer
+                    {
                         return Ok(Some(revoked_cert));
                     }
                 }
@@ -253,7 +560,6 @@ impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
     ///   * CRL versions other than version 2.
     ///   * CRLs missing the next update field.
     ///   * CRLs missing certificate revocation list extensions.
-    ///   * Delta CRLs.
     ///   * CRLs larger than (2^32)-1 bytes in size.
     ///
     /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
@@ -266,7 +572,7 @@ impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
             der::MAX_DER_SIZE,
         )?;
 
-        let crl = tbs_cert_list.read_all(Error::BadDer, |tbs_cert_list| {
+        let mut crl = tbs_cert_list.read_all(Error::BadDer, |tbs_cert_list| {
             // RFC 5280 §5.1.2.1:
             //   This optional field describes the version of the encoded CRL.  When
             //   extensions are used, as required by this profile, this field MUST be
@@ -298,18 +604,17 @@ impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
             //    encoded as UTCTime or GeneralizedTime.
             // We do not presently enforce the correct choice of UTCTime or GeneralizedTime based on
             // whether the date is post 2050.
-            Time::from_der(tbs_cert_list)?;
+            let this_update = Time::from_der(tbs_cert_list)?;
 
             // While OPTIONAL in the ASN.1 module, RFC 5280 §5.1.2.5 says:
             //   Conforming CRL issuers MUST include the nextUpdate field in all CRLs.
             // We do not presently enforce the correct choice of UTCTime or GeneralizedTime based on
             // whether the date is post 2050.
-            Time::from_der(tbs_cert_list)?;
+            let next_update = Time::from_der(tbs_cert_list)?;
 
             // RFC 5280 §5.1.2.6:
             //   When there are no revoked certificates, the revoked certificates list
             //   MUST be absent
-            // TODO(@cpu): Do we care to support empty CRLs if we don't support delta CRLs?
             let revoked_certs = if tbs_cert_list.peek(Tag::Sequence.into()) {
                 der::expect_tag_and_get_value_limited(
                     tbs_cert_list,
@@ -325,6 +630,12 @@ impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
                 issuer,
                 revoked_certs,
                 issuing_distribution_point: None,
+                indirect_crl: false,
+                this_update,
+                next_update,
+                crl_number: None,
+                authority_key_identifier: None,
+                base_crl_number: None,
             };
 
             // RFC 5280 §5.1.2.7:
@@ -363,7 +674,7 @@ impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
         // If an issuing distribution point extension is present, parse it up-front to validate
         // that it only uses well-formed and supported features.
         if let Some(der) = crl.issuing_distribution_point {
-            IssuingDistributionPoint::from_der(der)?;
+            crl.indirect_crl = IssuingDistributionPoint::from_der(der)?.indirect_crl;
         }
 
         Ok(crl)
@@ -372,10 +683,55 @@ impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
 
 impl<'a> IntoIterator for &'a BorrowedCertRevocationList<'a> {
     type Item = Result<BorrowedRevokedCert<'a>, Error>;
-    type IntoIter = DerIterator<'a, BorrowedRevokedCert<'a>>;
+    type IntoIter = RevokedCerts<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
-        DerIterator::new(self.revoked_certs)
+        RevokedCerts {
+            entries: DerIterator::new(self.revoked_certs),
+            indirect_crl: self.indirect_crl,
+            certificate_issuer: None,
+        }
+    }
+}
+
+/// An iterator over the revoked certificates of a [`BorrowedCertRevocationList`].
+///
+/// RFC 5280 §5.3.3[^1]: in an indirect CRL, an entry without the certificateIssuer extension
+/// is from the same issuer as the entry before it, or from the CRL's issuer if it is the first.
+/// The iterator carries issuers forward accordingly, so that the
+/// [`BorrowedRevokedCert::certificate_issuer`] of each entry is the issuer it is attributed to.
+/// The extension must not appear in a CRL that isn't indirect, and an entry with it is an
+/// error, [`Error::MalformedExtensions`].
+///
+/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5.3.3>
+#[derive(Debug)]
+pub struct RevokedCerts<'a> {
+    entries: DerIterator<'a, BorrowedRevokedCert<'a>>,
+
+    /// Whether the CRL's issuing distribution point sets the indirectCRL indicator.
+    indirect_crl: bool,
+
+    /// The certificateIssuer of the most recent entry that had one.
+    certificate_issuer: Option<&'a [u8]>,
+}
+
+impl<'a> Iterator for RevokedCerts<'a> {
+    type Item = Result<BorrowedRevokedCert<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut revoked_cert = match self.entries.next()? {
+            Ok(revoked_cert) => revoked_cert,
+            Err(err) => return Some(Err(err)),
+        };
+        match revoked_cert.certificate_issuer {
+            // RFC 5280 §5.3.3 only defines certificateIssuer for entries of indirect CRLs, and an
+            // issuer can only be attributed other issuers' certificates by declaring its CRL
+            // indirect.
+            Some(_) if !self.indirect_crl => return Some(Err(Error::MalformedExtensions)),
+            Some(certificate_issuer) => self.certificate_issuer = Some(certificate_issuer),
+            None => revoked_cert.certificate_issuer = self.certificate_issuer,
+        }
+        Some(Ok(revoked_cert))
     }
 }
 
@@ -404,6 +760,10 @@ pub struct OwnedRevokedCert {
     /// that the certificate otherwise became invalid. This date may be earlier than the revocation
     /// date which is the date at which the CA processed the revocation.
     pub invalidity_date: Option<Time>,
+
+    /// The DER encoded issuer of the revoked certificate, when an indirect CRL attributes the
+    /// entry to an issuer other than the CRL's. See [`BorrowedRevokedCert::certificate_issuer`].
+    pub certificate_issuer: Option<Vec<u8>>,
 }
 
 #[cfg(feature = "alloc")]
@@ -415,6 +775,7 @@ impl OwnedRevokedCert {
             revocation_date: self.revocation_date,
             reason_code: self.reason_code,
             invalidity_date: self.invalidity_date,
+            certificate_issuer: self.certificate_issuer.as_deref(),
         }
     }
 }
@@ -423,7 +784,7 @@ impl OwnedRevokedCert {
 /// certificate entry.
 ///
 /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct BorrowedRevokedCert<'a> {
     /// Serial number of the revoked certificate.
     pub serial_number: &'a [u8],
@@ -441,6 +802,13 @@ pub struct BorrowedRevokedCert<'a> {
     /// that the certificate otherwise became invalid. This date may be earlier than the revocation
     /// date which is the date at which the CA processed the revocation.
     pub invalidity_date: Option<Time>,
+
+    /// The DER encoded issuer of the revoked certificate, when an indirect CRL attributes the
+    /// entry to an issuer other than the CRL's. When absent, the entry is from the CRL's issuer.
+    ///
+    /// This is taken from the entry's certificateIssuer extension, or when iterating over a
+    /// [`BorrowedCertRevocationList`], carried forward from the last entry that had one.
+    pub certificate_issuer: Option<&'a [u8]>,
 }
 
 impl<'a> BorrowedRevokedCert<'a> {
@@ -453,6 +821,7 @@ impl<'a> BorrowedRevokedCert<'a> {
             revocation_date: self.revocation_date,
             reason_code: self.reason_code,
             invalidity_date: self.invalidity_date,
+            certificate_issuer: self.certificate_issuer.map(<[u8]>::to_vec),
         }
     }
 
@@ -476,9 +845,19 @@ impl<'a> BorrowedRevokedCert<'a> {
                 //   with an entry in an indirect CRL, that is, a CRL that has the
                 //   indirectCRL indicator set in its issuing distribution point
                 //   extension.
-                // We choose not to support indirect CRLs and so turn this into a more specific
-                // error rather than simply letting it fail as an unsupported critical extension.
-                29 => Err(Error::UnsupportedIndirectCrl),
+                // Issuers are compared with certificates' issuer fields, so we use the first
+                // directoryName. Issuers named any other way are unsupported.
+                29 => set_extension_once(&mut self.certificate_issuer, || {
+                    extension.value.read_all(Error::BadDer, |value| {
+                        let names = der::expect_tag_and_get_value(value, Tag::Sequence)?;
+                        for name in DerIterator::<GeneralName>::new(names) {
+                            if let GeneralName::DirectoryName(name) = name? {
+                                return Ok(directory_name(name)?.as_slice_less_safe());
+                            }
+                        }
+                        Err(Error::UnsupportedIndirectCrl)
+                    })
+                }),
 
                 // Unsupported extension
                 _ => extension.unsupported(),
@@ -510,6 +889,7 @@ impl<'a> FromDer<'a> for BorrowedRevokedCert<'a> {
                 revocation_date,
                 reason_code: None,
                 invalidity_date: None,
+                certificate_issuer: None,
             };
 
             // RFC 5280 §5.3:
@@ -566,7 +946,7 @@ pub enum RevocationReason {
     CessationOfOperation = 5,
     CertificateHold = 6,
     // 7 is not used.
-    /// RemoveFromCrl only appears in delta CRLs that are unsupported.
+    /// RemoveFromCrl only appears in delta CRLs, and releases a certificate that was on hold.
     RemoveFromCrl = 8,
     PrivilegeWithdrawn = 9,
     AaCompromise = 10,
@@ -624,7 +1004,6 @@ impl TryFrom<u8> for RevocationReason {
     }
 }
 
-#[allow(dead_code)] // TODO(@cpu): remove this once used in CRL validation.
 pub(crate) struct IssuingDistributionPoint<'a> {
     distribution_point: Option<untrusted::Input<'a>>,
     pub(crate) only_contains_user_certs: bool,
@@ -635,7 +1014,6 @@ pub(crate) struct IssuingDistributionPoint<'a> {
 }
 
 impl<'a> IssuingDistributionPoint<'a> {
-    #[allow(dead_code)] // TODO(@cpu): remove this once used in CRL validation.
     pub(crate) fn from_der(der: untrusted::Input<'a>) -> Result<IssuingDistributionPoint, Error> {
         const DISTRIBUTION_POINT_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED;
         const ONLY_CONTAINS_USER_CERTS_TAG: u8 = CONTEXT_SPECIFIC | 1;
@@ -710,21 +1088,11 @@ impl<'a> IssuingDistributionPoint<'a> {
             return Err(Error::MalformedExtensions);
         }
 
-        // We don't support indirect CRLs.
-        if result.indirect_crl {
-            return Err(Error::UnsupportedIndirectCrl);
-        }
-
-        // We don't support CRLs partitioned by revocation reason.
-        if result.only_some_reasons.is_some() {
-            return Err(Error::UnsupportedRevocationReasonsPartitioning);
-        }
-
-        // We require a distribution point, and it must be a full name.
+        // A distribution point is optional, but if present it must be a full name.
         use DistributionPointName::*;
         match result.names() {
-            Ok(Some(FullName(_))) => Ok(result),
-            Ok(Some(NameRelativeToCrlIssuer(_))) | Ok(None) => {
+            Ok(Some(FullName(_))) | Ok(None) => Ok(result),
+            Ok(Some(NameRelativeToCrlIssuer(_))) => {
                 Err(Error::UnsupportedCrlIssuingDistributionPoint)
             }
             Err(_) => Err(Error::MalformedExtensions),
@@ -732,16 +1100,181 @@ impl<'a> IssuingDistributionPoint<'a> {
     }
 
     /// Return the distribution point names (if any).
-    #[allow(dead_code)] // TODO(@cpu): remove this once used in CRL validation.
     pub(crate) fn names(&self) -> Result<Option<DistributionPointName<'a>>, Error> {
         self.distribution_point
             .map(|input| DistributionPointName::from_der(&mut untrusted::Reader::new(input)))
             .transpose()
     }
+
+    /// Return the revocation reasons a CRL from `crl_issuer` with this issuing distribution
+    /// point covers for `cert`, following RFC 5280 §6.3.3 (b) and (d)[^1]. The CRL is within
+    /// scope for `cert` if it covers any:
+    ///
+    ///   * onlyContainsUserCerts excludes certificates asserting cA in their basic constraints,
+    ///     and onlyContainsCACerts excludes those that don't.
+    ///   * One of the certificate's cRLDistributionPoints must match. A distribution point
+    ///     with a cRLIssuer matches indirect CRLs from one of the issuers it names, and one
+    ///     without matches CRLs from the certificate's issuer.
+    ///   * And if this issuing distribution point names a distribution point, one of its names
+    ///     must match one of that distribution point's names, or if it has none, one of its
+    ///     cRLIssuer names.
+    ///
+    /// The reasons covered through each matching distribution point are those in both its
+    /// reasons and our onlySomeReasons, where a missing field stands for every reason.
+    ///
+    /// A certificate without the cRLDistributionPoints extension is only in scope for CRLs
+    /// from its issuer whose issuing distribution point doesn't name a distribution point, for
+    /// the reasons in our onlySomeReasons.
+    ///
+    /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-6.3.3>
+    pub(crate) fn reasons_for(&self, crl_issuer: &[u8], cert: &Cert) -> ReasonFlags {
+        // Basic constraints are checked for every certificate in the path before revocation,
+        // so a certificate asserts cA exactly when it issued the next certificate in the path.
+        let is_ca = matches!(cert.ee_or_ca, EndEntityOrCa::Ca(_));
+        if (self.only_contains_user_certs && is_ca) || (self.only_contains_ca_certs && !is_ca) {
+            return ReasonFlags::NONE;
+        }
+
+        let only_some_reasons = ReasonFlags::from_optional(self.only_some_reasons.as_ref());
+        let has_name = matches!(self.names(), Ok(Some(_)));
+        let cert_dps = match cert.crl_distribution_points() {
+            Some(cert_dps) => cert_dps,
+            None if !has_name && crl_issuer == cert.issuer() => return only_some_reasons,
+            None => return ReasonFlags::NONE,
+        };
+
+        let mut reasons = ReasonFlags::NONE;
+        for cert_dp in cert_dps {
+            let cert_dp = match cert_dp {
+                Ok(cert_dp) => cert_dp,
+                Err(_) => return ReasonFlags::NONE,
+            };
+
+            let issuer_matches = match cert_dp.crl_issuer {
+                Some(dp_crl_issuer) => {
+                    self.indirect_crl
+                        && DerIterator::<GeneralName>::new(dp_crl_issuer).any(|name| {
+                            matches!(name, Ok(GeneralName::DirectoryName(name))
+                                if directory_name(name).map_or(false, |name| name.as_slice_less_safe() == crl_issuer))
+                        })
+                }
+                None => crl_issuer == cert.issuer(),
+            };
+            if !issuer_matches {
+                continue;
+            }
+
+            if has_name {
+                let mut dp_names = match (cert_dp.names(), cert_dp.crl_issuer) {
+                    (Ok(Some(DistributionPointName::FullName(names))), _) => names,
+                    (Ok(None), Some(dp_crl_issuer)) => DerIterator::new(dp_crl_issuer),
+                    _ => continue,
+                };
+                if !dp_names.any(|name| match name {
+                    Ok(name) => {
+                        full_names(self.names()).any(|idp_name| same_name(&idp_name, &name))
+                    }
+                    Err(_) => false,
+                }) {
+                    continue;
+                }
+            }
+
+            let dp_reasons = ReasonFlags::from_optional(cert_dp.reasons.as_ref());
+            reasons = reasons.union(dp_reasons.intersection(only_some_reasons));
+        }
+
+        reasons
+    }
+}
+
+/// A set of revocation reasons, as named by the ReasonFlags of a distribution point or an
+/// issuing distribution point, for tracking which reasons the CRLs consulted for a certificate
+/// cover. See RFC 5280 §4.2.1.13[^1].
+///
+/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.13>
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct ReasonFlags(u16);
+
+impl ReasonFlags {
+    /// No reasons.
+    pub(crate) const NONE: Self = Self(0);
+
+    /// Every reason, from keyCompromise (bit 1) to aACompromise (bit 8). Bit 0 is unused.
+    pub(crate) const ALL: Self = Self(0b1_1111_1110);
+
+    /// The reasons set in `flags`, or every reason if there are no flags.
+    fn from_optional(flags: Option<&der::BitStringFlags>) -> Self {
+        match flags {
+            Some(flags) => Self(
+                (1..=8)
+                    .filter(|&bit| flags.bit_set(bit))
+                    .fold(0, |reasons, bit| reasons | 1 << bit),
+            ),
+            None => Self::ALL,
+        }
+    }
+
+    pub(crate) fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub(crate) fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Return true if every reason in `other` is also in `self`.
+    pub(crate) fn contains(self, other: Self) -> bool {
+        self.intersection(other) == other
+    }
+}
+
+/// Iterate over the full names of `dp_name`, stopping at the first malformed name. A name
+/// relative to the CRL issuer has no full names.
+fn full_names<'a>(
+    dp_name: Result<Option<DistributionPointName<'a>>, Error>,
+) -> impl Iterator<Item = GeneralName<'a>> {
+    let names = match dp_name {
+        Ok(Some(DistributionPointName::FullName(names))) => Some(names),
+        _ => None,
+    };
+    names.into_iter().flatten().map_while(Result::ok)
+}
+
+/// Return the contents of the Name in the value of a directoryName GeneralName, for comparison
+/// with DER encoded issuer and subject fields.
+fn directory_name(name: untrusted::Input) -> Result<untrusted::Input, Error> {
+    name.read_all(Error::BadDer, |name| {
+        der::expect_tag_and_get_value(name, Tag::Sequence)
+    })
+}
+
+/// Return true if `a` and `b` are the same name. Names of unsupported types never match.
+fn same_name(a: &GeneralName, b: &GeneralName) -> bool {
+    use GeneralName::*;
+    match (a, b) {
+        (DnsName(a), DnsName(b))
+        | (DirectoryName(a), DirectoryName(b))
+        | (IpAddress(a), IpAddress(b))
+        | (UniformResourceIdentifier(a), UniformResourceIdentifier(b)) => a == b,
+        _ => false,
+    }
 }
 
 mod private {
-    pub trait Sealed {}
+    #[cfg(feature = "ring")]
+    use crate::{Error, SignatureCache, SignatureVerificationAlgorithm};
+
+    pub trait Sealed {
+        /// Like `CertRevocationList::verify_signature`, but consulting and updating `cache`.
+        #[cfg(feature = "ring")]
+        fn verify_signature_with_cache(
+            &self,
+            supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+            issuer_spki: &[u8],
+            cache: &dyn SignatureCache,
+        ) -> Result<(), Error>;
+    }
 }
 
 #[cfg(test)]
@@ -749,7 +1282,9 @@ mod tests {
     use alloc::vec::Vec;
 
     use crate::{
-        crl::IssuingDistributionPoint, subject_name::GeneralName, x509::DistributionPointName,
+        crl::{IssuingDistributionPoint, ReasonFlags},
+        subject_name::GeneralName,
+        x509::DistributionPointName,
         BorrowedCertRevocationList, CertRevocationList, Error, RevocationReason,
     };
 
@@ -908,10 +1443,18 @@ mod tests {
     #[test]
     fn test_issuing_distribution_point_indirect() {
         let crl = include_bytes!("../tests/crls/crl.idp.indirect_crl.der");
-        // We should encounter an error parsing a CRL with an IDP extension that indicates it's an
-        // indirect CRL.
-        let result = BorrowedCertRevocationList::from_der(&crl[..]);
-        assert!(matches!(result, Err(Error::UnsupportedIndirectCrl)));
+        let crl = BorrowedCertRevocationList::from_der(&crl[..]).unwrap();
+
+        // We should be able to parse the issuing distribution point extension.
+        let crl_issuing_dp = crl
+            .issuing_distribution_point()
+            .expect("missing crl distribution point DER");
+        let crl_issuing_dp =
+            IssuingDistributionPoint::from_der(untrusted::Input::from(crl_issuing_dp))
+                .expect("failed to parse issuing distribution point DER");
+
+        // We should find the expected bool state.
+        assert!(crl_issuing_dp.indirect_crl);
     }
 
     #[test]
@@ -926,13 +1469,22 @@ mod tests {
     #[test]
     fn test_issuing_distribution_only_some_reasons() {
         let crl = include_bytes!("../tests/crls/crl.idp.only_some_reasons.der");
-        // We should encounter an error parsing a CRL with an IDP extension that indicates it's
-        // partitioned by revocation reason.
-        let result = BorrowedCertRevocationList::from_der(&crl[..]);
-        assert!(matches!(
-            result,
-            Err(Error::UnsupportedRevocationReasonsPartitioning)
-        ));
+        let crl = BorrowedCertRevocationList::from_der(&crl[..]).unwrap();
+
+        // We should be able to parse the issuing distribution point extension.
+        let crl_issuing_dp = crl
+            .issuing_distribution_point()
+            .expect("missing crl distribution point DER");
+        let crl_issuing_dp =
+            IssuingDistributionPoint::from_der(untrusted::Input::from(crl_issuing_dp))
+                .expect("failed to parse issuing distribution point DER");
+
+        // We should find the CRL is partitioned by the expected revocation reasons.
+        let reasons = ReasonFlags::from_optional(crl_issuing_dp.only_some_reasons.as_ref());
+        let key_compromise = ReasonFlags(1 << 1);
+        let affiliation_changed = ReasonFlags(1 << 3);
+        assert_eq!(reasons, key_compromise.union(affiliation_changed));
+        assert!(!reasons.contains(ReasonFlags::ALL));
     }
 
     #[test]
@@ -995,12 +1547,15 @@ mod tests {
     #[test]
     fn test_issuing_distribution_no_name() {
         let crl = include_bytes!("../tests/crls/crl.idp.no_distribution_point_name.der");
-        // We should encounter an error parsing a CRL with an issuing distribution point extension
-        // that has no distribution point name.
-        let result = BorrowedCertRevocationList::from_der(&crl[..]);
-        assert!(matches!(
-            result,
-            Err(Error::UnsupportedCrlIssuingDistributionPoint)
+        // A CRL with an issuing distribution point extension that has no distribution point name
+        // should parse, and its scope is only limited by the other fields.
+        let crl = BorrowedCertRevocationList::from_der(&crl[..])
+            .expect("failed to parse CRL with IDP without a distribution point name");
+        let idp = IssuingDistributionPoint::from_der(untrusted::Input::from(
+            crl.issuing_distribution_point().unwrap(),
         ))
+        .unwrap();
+        assert!(idp.only_contains_user_certs);
+        assert!(matches!(idp.names(), Ok(None)));
     }
 }
@@ -17,7 +17,7 @@ use core::marker::PhantomData;
 use crate::Error;
 
 #[derive(Debug)]
-pub struct DerIterator<'a, T> {
+pub(crate) struct DerIterator<'a, T> {
     reader: untrusted::Reader<'a>,
     marker: PhantomData<T>,
 }
@@ -138,6 +138,11 @@ pub(crate) struct Value<'a> {
 }
 
 impl<'a> Value<'a> {
+    #[cfg(feature = "alloc")]
+    pub(crate) fn new(value: untrusted::Input<'a>) -> Self {
+        Self { value }
+    }
+
     pub(crate) fn value(&self) -> untrusted::Input<'a> {
         self.value
     }
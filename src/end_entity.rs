@@ -12,11 +12,16 @@
 // ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
 // OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+
 #[cfg(feature = "alloc")]
 use crate::subject_name::GeneralDnsNameRef;
+use crate::trust_anchor::Anchors;
 use crate::{
-    cert, signed_data, subject_name, verify_cert, CertRevocationList, Error, KeyUsage,
-    SignatureVerificationAlgorithm, SubjectNameRef, Time, TrustAnchor,
+    cert, signed_data, subject_name, verify_cert, Error, KeyUsage, RevocationOptions,
+    SignatureVerificationAlgorithm, SubjectNameRef, Time, TrustAnchor, TrustAnchors, VerifiedPath,
+    VerifyError, VerifyOptions,
 };
 
 /// An end-entity certificate.
@@ -49,7 +54,9 @@ use crate::{
 /// processed**. `EndEntityCert::from` is an inexpensive operation and is
 /// deterministic, so if these tasks are done in multiple threads, it is
 /// probably best to just call `EndEntityCert::from` multiple times (before each
-/// operation) for the same DER-encoded ASN.1 certificate bytes.
+/// operation) for the same DER-encoded ASN.1 certificate bytes. To keep a
+/// certificate beyond the lifetime of its DER, or share one between threads,
+/// use `OwnedEndEntityCert`.
 pub struct EndEntityCert<'a> {
     inner: cert::Cert<'a>,
 }
@@ -70,7 +77,7 @@ impl<'a> TryFrom<&'a [u8]> for EndEntityCert<'a> {
 }
 
 impl<'a> EndEntityCert<'a> {
-    pub(super) fn inner(&self) -> &cert::Cert {
+    pub(super) fn inner(&self) -> &cert::Cert<'a> {
         &self.inner
     }
 
@@ -80,31 +87,100 @@ impl<'a> EndEntityCert<'a> {
     /// * `supported_sig_algs` is the list of signature algorithms that are
     ///   trusted for use in certificate signatures; the end-entity certificate's
     ///   public key is not validated against this list.
-    /// * `trust_anchors` is the list of root CAs to trust
+    /// * `trust_anchors` is the set of root CAs to trust, either a list of
+    ///   [`TrustAnchor`](crate::TrustAnchor)s or an indexed `TrustAnchorStore`.
     /// * `intermediate_certs` is the sequence of intermediate certificates that
     ///   the server sent in the TLS handshake.
     /// * `time` is the time for which the validation is effective (usually the
     ///   current time).
     /// * `usage` is the intended usage of the certificate, indicating what kind
     ///   of usage we're verifying the certificate for.
-    /// * `crls` is the list of certificate revocation lists to check
-    ///   the certificate against.
-    pub fn verify_for_usage(
+    /// * `revocation` is the list of certificate revocation lists to check the
+    ///   certificates of the path against, and how to check them; if `None`,
+    ///   revocation status isn't checked.
+    pub fn verify_for_usage<A: TrustAnchors + ?Sized>(
         &self,
         supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
-        trust_anchors: &[TrustAnchor],
+        trust_anchors: &A,
         intermediate_certs: &[&[u8]],
         time: Time,
         usage: KeyUsage,
-        crls: &[&dyn CertRevocationList],
+        revocation: Option<RevocationOptions>,
     ) -> Result<(), Error> {
+        self.verify_for_usage_with_options(
+            supported_sig_algs,
+            trust_anchors,
+            intermediate_certs,
+            time,
+            usage,
+            revocation,
+            &VerifyOptions::default(),
+        )
+        .map(|_| ())
+        .map_err(Error::from)
+    }
+
+    /// Like [`EndEntityCert::verify_for_usage`], but with additional [`VerifyOptions`]
+    /// controlling how a path to a trust anchor is built.
+    ///
+    /// On success, the [`VerifiedPath`] has the revocation status of each certificate in the
+    /// path. On failure, the [`VerifyError`] has the details of the revoked certificate, if
+    /// verification failed because one was revoked.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_for_usage_with_options<'r, A: TrustAnchors + ?Sized>(
+        &self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        trust_anchors: &A,
+        intermediate_certs: &[&[u8]],
+        time: Time,
+        usage: KeyUsage,
+        revocation: Option<RevocationOptions<'r>>,
+        options: &VerifyOptions,
+    ) -> Result<VerifiedPath, VerifyError<'r>> {
         verify_cert::build_chain(
             &verify_cert::ChainOptions {
                 eku: usage,
                 supported_sig_algs,
-                trust_anchors,
+                trust_anchors: trust_anchors.anchors(),
+                intermediate_certs,
+                revocation,
+                options: *options,
+            },
+            &self.inner,
+            time,
+        )
+    }
+
+    /// Verifies that the end-entity certificate is valid for use against the specified Extended
+    /// Key Usage (EKU), over exactly the chain given by `intermediate_certs` and `trust_anchor`.
+    ///
+    /// Unlike [`EndEntityCert::verify_for_usage`], no path is searched for: the end-entity
+    /// certificate must be issued by `intermediate_certs[0]`, each intermediate by the one
+    /// after it, and the last intermediate (or the end-entity certificate, if there are none)
+    /// by `trust_anchor`. Every certificate must be used, so a chain that is misordered or
+    /// contains unrelated certificates is rejected even if a valid path could be built from it.
+    ///
+    /// This is intended for formats that carry an exactly ordered chain, like the `x5c` header
+    /// parameter of JWS. The remaining arguments, and the checks performed on each link, are
+    /// those of [`EndEntityCert::verify_for_usage`], and the result is like that of
+    /// [`EndEntityCert::verify_for_usage_with_options`].
+    pub fn verify_ordered_chain<'r>(
+        &self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        trust_anchor: &TrustAnchor,
+        intermediate_certs: &[&[u8]],
+        time: Time,
+        usage: KeyUsage,
+        revocation: Option<RevocationOptions<'r>>,
+    ) -> Result<VerifiedPath, VerifyError<'r>> {
+        verify_cert::verify_ordered_chain(
+            &verify_cert::ChainOptions {
+                eku: usage,
+                supported_sig_algs,
+                trust_anchors: Anchors::Slice(core::slice::from_ref(trust_anchor)),
                 intermediate_certs,
-                crls,
+                revocation,
+                options: VerifyOptions::default(),
             },
             &self.inner,
             time,
@@ -164,3 +240,131 @@ impl<'a> EndEntityCert<'a> {
         subject_name::list_cert_dns_names(self)
     }
 }
+
+/// An owned end-entity certificate.
+///
+/// This is the owned counterpart of [`EndEntityCert`], for certificates that must outlive the
+/// buffer they were received in, such as those kept in a cache across connections. The DER is
+/// stored once, behind shared ownership, so clones are cheap and the certificate can be shared
+/// between threads. Each operation re-parses the certificate, which is inexpensive.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug)]
+pub struct OwnedEndEntityCert {
+    der: Arc<[u8]>,
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&[u8]> for OwnedEndEntityCert {
+    type Error = Error;
+
+    /// Parse the ASN.1 DER-encoded X.509 encoding of the certificate
+    /// `cert_der`, and take a copy of it.
+    fn try_from(cert_der: &[u8]) -> Result<Self, Self::Error> {
+        EndEntityCert::try_from(cert_der)?;
+        Ok(Self {
+            der: Arc::from(cert_der),
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedEndEntityCert {
+    /// The DER encoding of the certificate.
+    pub fn der(&self) -> &[u8] {
+        &self.der
+    }
+
+    /// Borrow the certificate as an [`EndEntityCert`].
+    fn borrow(&self) -> Result<EndEntityCert<'_>, Error> {
+        EndEntityCert::try_from(&*self.der)
+    }
+
+    /// Like [`EndEntityCert::verify_for_usage`].
+    pub fn verify_for_usage<A: TrustAnchors + ?Sized>(
+        &self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        trust_anchors: &A,
+        intermediate_certs: &[&[u8]],
+        time: Time,
+        usage: KeyUsage,
+        revocation: Option<RevocationOptions>,
+    ) -> Result<(), Error> {
+        self.borrow()?.verify_for_usage(
+            supported_sig_algs,
+            trust_anchors,
+            intermediate_certs,
+            time,
+            usage,
+            revocation,
+        )
+    }
+
+    /// Like [`EndEntityCert::verify_for_usage_with_options`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_for_usage_with_options<'r, A: TrustAnchors + ?Sized>(
+        &self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        trust_anchors: &A,
+        intermediate_certs: &[&[u8]],
+        time: Time,
+        usage: KeyUsage,
+        revocation: Option<RevocationOptions<'r>>,
+        options: &VerifyOptions,
+    ) -> Result<VerifiedPath, VerifyError<'r>> {
+        self.borrow()?.verify_for_usage_with_options(
+            supported_sig_algs,
+            trust_anchors,
+            intermediate_certs,
+            time,
+            usage,
+            revocation,
+            options,
+        )
+    }
+
+    /// Like [`EndEntityCert::verify_ordered_chain`].
+    pub fn verify_ordered_chain<'r>(
+        &self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        trust_anchor: &TrustAnchor,
+        intermediate_certs: &[&[u8]],
+        time: Time,
+        usage: KeyUsage,
+        revocation: Option<RevocationOptions<'r>>,
+    ) -> Result<VerifiedPath, VerifyError<'r>> {
+        self.borrow()?.verify_ordered_chain(
+            supported_sig_algs,
+            trust_anchor,
+            intermediate_certs,
+            time,
+            usage,
+            revocation,
+        )
+    }
+
+    /// Like [`EndEntityCert::verify_is_valid_for_subject_name`].
+    pub fn verify_is_valid_for_subject_name(
+        &self,
+        subject_name: SubjectNameRef,
+    ) -> Result<(), Error> {
+        self.borrow()?
+            .verify_is_valid_for_subject_name(subject_name)
+    }
+
+    /// Like [`EndEntityCert::verify_signature`].
+    pub fn verify_signature(
+        &self,
+        signature_alg: &dyn SignatureVerificationAlgorithm,
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        self.borrow()?
+            .verify_signature(signature_alg, msg, signature)
+    }
+
+    /// Like [`EndEntityCert::dns_names`].
+    pub fn dns_names(&self) -> Result<impl Iterator<Item = GeneralDnsNameRef<'_>>, Error> {
+        subject_name::list_cert_dns_names(&self.borrow()?)
+    }
+}
@@ -18,12 +18,18 @@ use core::fmt;
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Error {
+    /// An NSS `certdata.txt` file could not be parsed.
+    BadCertdata,
+
     /// The encoding of some ASN.1 DER-encoded item is invalid.
     BadDer,
 
     /// The encoding of an ASN.1 DER-encoded time is invalid.
     BadDerTime,
 
+    /// A PEM section is malformed, or its contents are not valid base64.
+    BadPem,
+
     /// A CA certificate is being used as an end-entity certificate.
     CaUsedAsEndEntity,
 
@@ -39,8 +45,18 @@ pub enum Error {
     CertNotValidYet,
 
     /// The certificate, or one of its issuers, has been revoked.
+    ///
+    /// Which certificate, and the CRL entry revoking it, are given by
+    /// [`VerifyError::revoked`](crate::VerifyError::revoked).
     CertRevoked,
 
+    /// A CRL's nextUpdate time has passed, beyond the grace period, under
+    /// [`ExpirationPolicy::Enforce`](crate::ExpirationPolicy::Enforce).
+    CrlExpired,
+
+    /// A CRL's thisUpdate time is later than the time it is being used for.
+    CrlNotValidYet,
+
     /// An end-entity certificate is being used as a CA certificate.
     EndEntityUsedAsCa,
 
@@ -62,6 +78,9 @@ pub enum Error {
     /// - it was too long or short
     InvalidNetworkMaskConstraint,
 
+    /// A name given for generated Rust source is not a Rust identifier or path.
+    InvalidRustIdentifier,
+
     /// A serial number was invalid:
     ///  - it was misencoded
     ///  - it was negative
@@ -103,13 +122,32 @@ pub enum Error {
     /// being validated.
     RequiredEkuNotFound,
 
+    /// The certificate path does not satisfy the certificate policy controls
+    /// of its trust anchor.
+    RequiredPolicyNotFound,
+
     /// The algorithm in the TBSCertificate "signature" field of a certificate
     /// does not match the algorithm in the signature of the certificate.
     SignatureAlgorithmMismatch,
 
+    /// No certificate in the path, nor its trust anchor, has a public key matching one of the
+    /// applicable SubjectPublicKeyInfo pins.
+    SpkiPinMismatch,
+
+    /// A certificate required to be a self-signed trust anchor is not self-issued.
+    TrustAnchorNotSelfSigned,
+
     /// A valid issuer for the certificate could not be found.
     UnknownIssuer,
 
+    /// A raw public key is not one of the keys it was expected to be.
+    UnknownPublicKey,
+
+    /// The revocation status of a certificate could not be determined from the CRLs given,
+    /// and the [`UnknownStatusPolicy`](crate::UnknownStatusPolicy) in use denies such
+    /// certificates.
+    UnknownRevocationStatus,
+
     /// The certificate is not a v3 X.509 certificate.
     ///
     /// This error may be also reported if the certificate version field
@@ -132,15 +170,21 @@ pub enum Error {
     UnsupportedCrlVersion,
 
     /// The CRL is an unsupported "delta" CRL.
+    ///
+    /// Delta CRLs are supported, so this error is no longer returned.
     UnsupportedDeltaCrl,
 
-    /// The CRL contains unsupported "indirect" entries.
+    /// The CRL contains "indirect" entries whose certificate issuer isn't named by a
+    /// directoryName, which is unsupported.
     UnsupportedIndirectCrl,
 
     /// The revocation reason is not in the set of supported revocation reasons.
     UnsupportedRevocationReason,
 
     /// The CRL is partitioned by revocation reasons.
+    ///
+    /// CRLs partitioned by revocation reasons are supported, so this error is no longer
+    /// returned.
     UnsupportedRevocationReasonsPartitioning,
 
     /// The signature algorithm for a signature over a CRL is not in the set of supported
@@ -172,7 +216,7 @@ pub enum Error {
 
 impl Error {
     // Compare the Error with the new error by rank, returning the higher rank of the two as
-    // the most specific error.
+    // the most specific error. Of two equally specific errors, `self` is kept: the first found.
     pub(crate) fn most_specific(self, new: Error) -> Error {
         // Assign an error a numeric value ranking it by specificity.
         if self.rank() >= new.rank() {
@@ -191,13 +235,20 @@ impl Error {
             // Errors related to certificate validity
             Error::CertNotValidYet | Error::CertExpired => 29,
             Error::CertNotValidForName => 28,
-            Error::CertRevoked => 27,
+            Error::CertRevoked
+            | Error::CrlExpired
+            | Error::CrlNotValidYet
+            | Error::UnknownRevocationStatus => 27,
             Error::InvalidCrlSignatureForPublicKey | Error::InvalidSignatureForPublicKey => 26,
             Error::SignatureAlgorithmMismatch => 25,
-            Error::RequiredEkuNotFound => 24,
+            Error::RequiredEkuNotFound | Error::RequiredPolicyNotFound | Error::SpkiPinMismatch => {
+                24
+            }
             Error::NameConstraintViolation => 23,
             Error::PathLenConstraintViolated => 22,
-            Error::CaUsedAsEndEntity | Error::EndEntityUsedAsCa => 21,
+            Error::CaUsedAsEndEntity
+            | Error::EndEntityUsedAsCa
+            | Error::TrustAnchorNotSelfSigned => 21,
             Error::IssuerNotCrlSigner => 20,
 
             // Errors related to supported features used in an invalid way.
@@ -229,8 +280,14 @@ impl Error {
             Error::BadDerTime => 2,
             Error::BadDer => 1,
 
+            // Errors decoding the formats certificates are distributed in.
+            Error::BadCertdata | Error::BadPem => 1,
+
+            // Errors generating source for a root set.
+            Error::InvalidRustIdentifier => 1,
+
             // Default catch all error - should be renamed in the future.
-            Error::UnknownIssuer => 0,
+            Error::UnknownIssuer | Error::UnknownPublicKey => 0,
         }
     }
 }
@@ -0,0 +1,290 @@
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHORS DISCLAIM ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHORS BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::cert::{Cert, EndEntityOrCa};
+use crate::der;
+use crate::signed_data::SignedData;
+use crate::Error;
+
+/// A reusable set of intermediate CA certificates, indexed for issuer lookup.
+///
+/// This is intended for large, long-lived collections of known intermediates, such as the CCADB
+/// intermediate preload list, that allow verification to succeed when a peer fails to send a
+/// complete chain. Path building consults the store only after the intermediates supplied for a
+/// particular verification (see [`VerifyOptions::with_intermediate_store`]).
+///
+/// Certificates are parsed once, when they're added, rather than each time path building
+/// considers them.
+///
+/// Certificates in the store are only ever used as intermediates: a certificate's presence here
+/// never makes it a trust anchor.
+///
+/// [`VerifyOptions::with_intermediate_store`]: crate::VerifyOptions::with_intermediate_store
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, Default)]
+pub struct IntermediateStore {
+    certs: Vec<StoredCert>,
+
+    /// Indexes into `certs`, keyed by DER encoded subject.
+    by_subject: BTreeMap<Vec<u8>, Vec<usize>>,
+}
+
+impl IntermediateStore {
+    /// Construct an empty [`IntermediateStore`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the DER encoded certificate `cert_der` to the store.
+    ///
+    /// The certificate is parsed to build the index, and an error is returned if it can't be.
+    /// Adding a certificate that is already present has no effect.
+    pub fn add(&mut self, cert_der: &[u8]) -> Result<(), Error> {
+        let cert = Cert::from_der(untrusted::Input::from(cert_der), EndEntityOrCa::EndEntity)?;
+        let indexes = self
+            .by_subject
+            .entry(cert.subject.as_slice_less_safe().to_vec())
+            .or_default();
+
+        if indexes.iter().any(|&i| self.certs[i].der == cert_der) {
+            return Ok(());
+        }
+
+        indexes.push(self.certs.len());
+        self.certs.push(StoredCert {
+            fields: CertFields::new(cert_der, &cert),
+            der: cert_der.to_vec(),
+        });
+        Ok(())
+    }
+
+    /// Return the number of certificates in the store.
+    pub fn len(&self) -> usize {
+        self.certs.len()
+    }
+
+    /// Return true if the store contains no certificates.
+    pub fn is_empty(&self) -> bool {
+        self.certs.is_empty()
+    }
+
+    /// Return all the stored certificates.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Cert<'_>> {
+        self.certs
+            .iter()
+            .map(|cert| cert.cert(EndEntityOrCa::EndEntity))
+    }
+
+    /// Return the stored certificates whose subject is `subject`.
+    pub(crate) fn with_subject<'s>(&'s self, subject: &[u8]) -> impl Iterator<Item = Cert<'s>> {
+        self.by_subject
+            .get(subject)
+            .into_iter()
+            .flatten()
+            .map(move |&i| self.certs[i].cert(EndEntityOrCa::EndEntity))
+    }
+
+    /// Return the stored certificates whose subject matches the issuer of `cert`, as potential
+    /// issuers of it. Those whose subjectKeyIdentifier matches the authorityKeyIdentifier of
+    /// `cert` are returned first.
+    pub(crate) fn candidates<'s>(&'s self, cert: &'s Cert<'s>) -> Vec<Cert<'s>> {
+        let indexes = match self.by_subject.get(cert.issuer.as_slice_less_safe()) {
+            Some(indexes) => indexes,
+            None => return Vec::new(),
+        };
+
+        let authority_key_id = cert.authority_key_id();
+        let mut candidates = indexes
+            .iter()
+            .map(|&i| self.certs[i].cert(EndEntityOrCa::Ca(cert)))
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|candidate| {
+            authority_key_id.is_none() || candidate.subject_key_identifier != authority_key_id
+        });
+        candidates
+    }
+}
+
+#[derive(Clone, Debug)]
+struct StoredCert {
+    der: Vec<u8>,
+    fields: CertFields,
+}
+
+impl StoredCert {
+    fn cert<'a>(&'a self, ee_or_ca: EndEntityOrCa<'a>) -> Cert<'a> {
+        self.fields.cert(&self.der, ee_or_ca)
+    }
+}
+
+/// The fields of a parsed [`Cert`], as ranges of its DER encoding, from which it can be
+/// reconstructed without parsing it again.
+#[derive(Clone, Debug)]
+struct CertFields {
+    serial: Range<usize>,
+    signed_data: Range<usize>,
+    signature_algorithm: Range<usize>,
+    signature: Range<usize>,
+    issuer: Range<usize>,
+    validity: Range<usize>,
+    subject: Range<usize>,
+    spki: Range<usize>,
+
+    basic_constraints: Option<Range<usize>>,
+    key_usage: Option<Range<usize>>,
+    eku: Option<Range<usize>>,
+    name_constraints: Option<Range<usize>>,
+    subject_alt_name: Option<Range<usize>>,
+    crl_distribution_points: Option<Range<usize>>,
+    authority_key_identifier: Option<Range<usize>>,
+    subject_key_identifier: Option<Range<usize>>,
+    authority_info_access: Option<Range<usize>>,
+    certificate_policies: Option<Range<usize>>,
+}
+
+impl CertFields {
+    /// Record the fields of `cert`, which was parsed from `der`.
+    fn new(der: &[u8], cert: &Cert) -> Self {
+        let range = |input: untrusted::Input| {
+            #[allow(clippy::as_conversions)]
+            // Every field of `cert` is a subslice of `der`, so the difference between their
+            // addresses is the field's offset in `der`.
+            let start = input.as_slice_less_safe().as_ptr() as usize - der.as_ptr() as usize;
+            start..start + input.len()
+        };
+
+        Self {
+            serial: range(cert.serial),
+            signed_data: range(cert.signed_data.data),
+            signature_algorithm: range(cert.signed_data.algorithm),
+            signature: range(cert.signed_data.signature),
+            issuer: range(cert.issuer),
+            validity: range(cert.validity),
+            subject: range(cert.subject),
+            spki: range(cert.spki.value()),
+
+            basic_constraints: cert.basic_constraints.map(range),
+            key_usage: cert.key_usage.map(range),
+            eku: cert.eku.map(range),
+            name_constraints: cert.name_constraints.map(range),
+            subject_alt_name: cert.subject_alt_name.map(range),
+            crl_distribution_points: cert.crl_distribution_points.map(range),
+            authority_key_identifier: cert.authority_key_identifier.map(range),
+            subject_key_identifier: cert.subject_key_identifier.map(range),
+            authority_info_access: cert.authority_info_access.map(range),
+            certificate_policies: cert.certificate_policies.map(range),
+        }
+    }
+
+    /// Reconstruct the certificate from `der`, the DER encoding it was parsed from.
+    fn cert<'a>(&self, der: &'a [u8], ee_or_ca: EndEntityOrCa<'a>) -> Cert<'a> {
+        let input = |range: &Range<usize>| untrusted::Input::from(&der[range.clone()]);
+
+        Cert {
+            ee_or_ca,
+
+            serial: input(&self.serial),
+            signed_data: SignedData {
+                data: input(&self.signed_data),
+                algorithm: input(&self.signature_algorithm),
+                signature: input(&self.signature),
+            },
+            issuer: input(&self.issuer),
+            validity: input(&self.validity),
+            subject: input(&self.subject),
+            spki: der::Value::new(input(&self.spki)),
+
+            basic_constraints: self.basic_constraints.as_ref().map(input),
+            key_usage: self.key_usage.as_ref().map(input),
+            eku: self.eku.as_ref().map(input),
+            name_constraints: self.name_constraints.as_ref().map(input),
+            subject_alt_name: self.subject_alt_name.as_ref().map(input),
+            crl_distribution_points: self.crl_distribution_points.as_ref().map(input),
+            authority_key_identifier: self.authority_key_identifier.as_ref().map(input),
+            subject_key_identifier: self.subject_key_identifier.as_ref().map(input),
+            authority_info_access: self.authority_info_access.as_ref().map(input),
+            certificate_policies: self.certificate_policies.as_ref().map(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_by_subject() {
+        let ee = include_bytes!("../tests/netflix/ee.der");
+        let inter = include_bytes!("../tests/netflix/inter.der");
+        let ca = include_bytes!("../tests/netflix/ca.der");
+
+        let mut store = IntermediateStore::new();
+        assert!(store.is_empty());
+        store.add(inter).unwrap();
+        store.add(inter).unwrap();
+        assert_eq!(store.len(), 1);
+
+        // The v1 root can't be parsed as an intermediate.
+        assert_eq!(store.add(ca), Err(Error::UnsupportedCertVersion));
+
+        let ee = Cert::from_der(untrusted::Input::from(ee), EndEntityOrCa::EndEntity).unwrap();
+        let candidates = store.candidates(&ee);
+        assert_eq!(candidates.len(), 1);
+
+        let inter = Cert::from_der(untrusted::Input::from(inter), EndEntityOrCa::Ca(&ee)).unwrap();
+        assert_same_cert(&candidates[0], &inter);
+        assert!(store.candidates(&inter).is_empty());
+    }
+
+    #[test]
+    fn stored_certs_match_parsed() {
+        let der = include_bytes!("../tests/crl_distrib_point/with_crl_issuer.der");
+        let mut store = IntermediateStore::new();
+        store.add(der).unwrap();
+
+        let parsed = Cert::from_der(untrusted::Input::from(der), EndEntityOrCa::EndEntity).unwrap();
+        let stored = store.iter().collect::<Vec<_>>();
+        assert_eq!(stored.len(), 1);
+        assert_same_cert(&stored[0], &parsed);
+    }
+
+    fn assert_same_cert(stored: &Cert, parsed: &Cert) {
+        assert_eq!(stored.serial, parsed.serial);
+        assert_eq!(stored.signed_data.data, parsed.signed_data.data);
+        assert_eq!(stored.signed_data.algorithm, parsed.signed_data.algorithm);
+        assert_eq!(stored.signed_data.signature, parsed.signed_data.signature);
+        assert_eq!(stored.issuer, parsed.issuer);
+        assert_eq!(stored.validity, parsed.validity);
+        assert_eq!(stored.subject, parsed.subject);
+        assert_eq!(stored.spki.value(), parsed.spki.value());
+        assert_eq!(stored.basic_constraints, parsed.basic_constraints);
+        assert_eq!(stored.key_usage, parsed.key_usage);
+        assert_eq!(stored.eku, parsed.eku);
+        assert_eq!(stored.name_constraints, parsed.name_constraints);
+        assert_eq!(stored.subject_alt_name, parsed.subject_alt_name);
+        assert_eq!(
+            stored.crl_distribution_points,
+            parsed.crl_distribution_points
+        );
+        assert_eq!(
+            stored.authority_key_identifier,
+            parsed.authority_key_identifier
+        );
+        assert_eq!(stored.subject_key_identifier, parsed.subject_key_identifier);
+        assert_eq!(stored.authority_info_access, parsed.authority_info_access);
+        assert_eq!(stored.certificate_policies, parsed.certificate_policies);
+    }
+}
@@ -47,14 +47,28 @@ extern crate alloc;
 mod der;
 
 mod cert;
+#[cfg(feature = "alloc")]
+mod certdata;
+#[cfg(feature = "alloc")]
+mod codegen;
 mod end_entity;
 mod error;
+#[cfg(feature = "alloc")]
+mod intermediate_store;
+mod pem;
+mod raw_public_key;
 #[cfg(feature = "ring")]
 mod ring_algs;
+#[cfg(feature = "ring")]
+mod signature_cache;
 mod signed_data;
+#[cfg(all(feature = "alloc", feature = "ring"))]
+mod spki_pins;
 mod subject_name;
 mod time;
 mod trust_anchor;
+#[cfg(feature = "alloc")]
+mod verifier;
 
 mod crl;
 mod verify_cert;
@@ -62,26 +76,54 @@ mod x509;
 
 pub use {
     cert::{Cert, EndEntityOrCa},
-    crl::{BorrowedCertRevocationList, BorrowedRevokedCert, CertRevocationList, RevocationReason},
+    crl::{
+        BorrowedCertRevocationList, BorrowedRevokedCert, CertRevocationList, RevocationReason,
+        RevokedCerts,
+    },
     end_entity::EndEntityCert,
     error::Error,
+    pem::{pem_sections, PemSection, PemSectionKind, PemSections},
+    raw_public_key::RawPublicKeyEntity,
     signed_data::{alg_id, InvalidSignature, SignatureVerificationAlgorithm},
     subject_name::{
         AddrParseError, DnsNameRef, InvalidDnsNameError, InvalidSubjectNameError, IpAddrRef,
         SubjectNameRef,
     },
     time::Time,
-    trust_anchor::TrustAnchor,
-    verify_cert::KeyUsage,
+    trust_anchor::{TrustAnchor, TrustAnchorInfo, TrustAnchors},
+    verify_cert::{
+        ExpirationPolicy, KeyUsage, RevocationCheckDepth, RevocationOptions, RevocationStatus,
+        RevokedCertDetail, UnknownStatusPolicy, VerifiedPath, VerifyError, VerifyOptions,
+    },
 };
 
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 #[cfg(feature = "alloc")]
 pub use {
+    cert::OwnedIntermediateCert,
+    certdata::{parse_certdata, CertdataRoot},
+    codegen::generate_trust_anchors_source,
     crl::{OwnedCertRevocationList, OwnedRevokedCert},
+    end_entity::OwnedEndEntityCert,
+    intermediate_store::IntermediateStore,
     subject_name::{DnsName, IpAddr},
+    trust_anchor::{OwnedTrustAnchor, TrustAnchorStore},
+    verifier::{BatchVerifier, Verifier},
+    verify_cert::{IssuerQuery, IssuerSource},
 };
 
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use codegen::load_trust_anchors_from_dir;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+#[cfg(feature = "ring")]
+pub use signature_cache::{SignatureCache, SignatureCacheKey};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use signature_cache::BoundedSignatureCache;
+
 #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
 #[cfg(feature = "ring")]
 pub use ring_algs::{
@@ -95,3 +137,7 @@ pub use ring_algs::{
     RSA_PKCS1_3072_8192_SHA384, RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
     RSA_PSS_2048_8192_SHA384_LEGACY_KEY, RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
 };
+
+#[cfg_attr(docsrs, doc(cfg(all(feature = "ring", feature = "alloc"))))]
+#[cfg(all(feature = "ring", feature = "alloc"))]
+pub use spki_pins::{SpkiPins, SpkiSha256};
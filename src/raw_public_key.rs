@@ -0,0 +1,94 @@
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHORS DISCLAIM ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHORS BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use crate::der::{self, FromDer};
+use crate::signed_data::{self, SubjectPublicKeyInfo};
+use crate::{Error, SignatureVerificationAlgorithm};
+
+/// A peer authenticated by a raw public key, as in RFC 7250[^1], rather than by a certificate.
+///
+/// Raw public key processing in a TLS connection consists of two steps, both of which are
+/// necessary:
+///
+/// * `RawPublicKeyEntity.verify_is_pinned`: Verify that the peer's key is one of the keys it
+///   is expected to present.
+/// * `RawPublicKeyEntity.verify_signature`: Verify that the peer's signature in its
+///   `CertificateVerify` message is valid for its key.
+///
+/// Since there is no certificate, there is no name, validity period or issuer to check: trust
+/// in the key comes entirely from it having been pinned.
+///
+/// [^1]: <https://www.rfc-editor.org/rfc/rfc7250>
+pub struct RawPublicKeyEntity<'a> {
+    /// The complete DER encoding of the SubjectPublicKeyInfo.
+    der: &'a [u8],
+
+    /// The contents of the outer SEQUENCE of the SubjectPublicKeyInfo.
+    spki: untrusted::Input<'a>,
+}
+
+impl<'a> TryFrom<&'a [u8]> for RawPublicKeyEntity<'a> {
+    type Error = Error;
+
+    /// Parse the ASN.1 DER-encoded SubjectPublicKeyInfo `spki_der`.
+    fn try_from(spki_der: &'a [u8]) -> Result<Self, Self::Error> {
+        let spki = untrusted::Input::from(spki_der).read_all(Error::BadDer, |reader| {
+            der::expect_tag_and_get_value(reader, der::Tag::Sequence)
+        })?;
+        spki.read_all(Error::BadDer, SubjectPublicKeyInfo::from_der)?;
+        Ok(Self {
+            der: spki_der,
+            spki,
+        })
+    }
+}
+
+impl<'a> RawPublicKeyEntity<'a> {
+    /// Verifies that the key is one of `pinned_spkis`, each of which is a DER-encoded
+    /// SubjectPublicKeyInfo.
+    ///
+    /// Keys are compared by their encoding, so a pinned key that is encoded differently from the
+    /// presented one (for example, with explicit rather than named curve parameters) will not
+    /// match. Returns [`Error::UnknownPublicKey`] if no pinned key matches.
+    pub fn verify_is_pinned(&self, pinned_spkis: &[&[u8]]) -> Result<(), Error> {
+        if pinned_spkis.contains(&self.der) {
+            Ok(())
+        } else {
+            Err(Error::UnknownPublicKey)
+        }
+    }
+
+    /// Verifies the signature `signature` of message `msg` using the key.
+    ///
+    /// `signature_alg` is the algorithm to use to verify the signature. Returns
+    /// [`Error::InvalidSignatureForPublicKey`] if the signature is invalid, and
+    /// [`Error::UnsupportedSignatureAlgorithmForPublicKey`] if `signature_alg` can't be used
+    /// with the key.
+    pub fn verify_signature(
+        &self,
+        signature_alg: &dyn SignatureVerificationAlgorithm,
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        signed_data::verify_signature(
+            signature_alg,
+            self.spki,
+            untrusted::Input::from(msg),
+            untrusted::Input::from(signature),
+        )
+    }
+
+    /// The DER encoding of the SubjectPublicKeyInfo.
+    pub fn spki_der(&self) -> &'a [u8] {
+        self.der
+    }
+}
@@ -17,6 +17,7 @@ use ring::signature;
 
 /// A `SignatureVerificationAlgorithm` implemented using *ring*.
 struct RingAlgorithm {
+    cache_id: &'static str,
     public_key_alg_id: alg_id::AlgorithmIdentifier,
     signature_alg_id: alg_id::AlgorithmIdentifier,
     verification_alg: &'static dyn signature::VerificationAlgorithm,
@@ -31,6 +32,10 @@ impl SignatureVerificationAlgorithm for RingAlgorithm {
         self.signature_alg_id
     }
 
+    fn cache_id(&self) -> Option<&'static str> {
+        Some(self.cache_id)
+    }
+
     fn verify_signature(
         &self,
         public_key: &[u8],
@@ -45,6 +50,7 @@ impl SignatureVerificationAlgorithm for RingAlgorithm {
 
 /// ECDSA signatures using the P-256 curve and SHA-256.
 pub static ECDSA_P256_SHA256: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
+    cache_id: "webpki::ECDSA_P256_SHA256",
     public_key_alg_id: alg_id::ECDSA_P256,
     signature_alg_id: alg_id::ECDSA_SHA256,
     verification_alg: &signature::ECDSA_P256_SHA256_ASN1,
@@ -52,6 +58,7 @@ pub static ECDSA_P256_SHA256: &dyn SignatureVerificationAlgorithm = &RingAlgorit
 
 /// ECDSA signatures using the P-256 curve and SHA-384. Deprecated.
 pub static ECDSA_P256_SHA384: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
+    cache_id: "webpki::ECDSA_P256_SHA384",
     public_key_alg_id: alg_id::ECDSA_P256,
     signature_alg_id: alg_id::ECDSA_SHA384,
     verification_alg: &signature::ECDSA_P256_SHA384_ASN1,
@@ -59,6 +66,7 @@ pub static ECDSA_P256_SHA384: &dyn SignatureVerificationAlgorithm = &RingAlgorit
 
 /// ECDSA signatures using the P-384 curve and SHA-256. Deprecated.
 pub static ECDSA_P384_SHA256: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
+    cache_id: "webpki::ECDSA_P384_SHA256",
     public_key_alg_id: alg_id::ECDSA_P384,
     signature_alg_id: alg_id::ECDSA_SHA256,
     verification_alg: &signature::ECDSA_P384_SHA256_ASN1,
@@ -66,6 +74,7 @@ pub static ECDSA_P384_SHA256: &dyn SignatureVerificationAlgorithm = &RingAlgorit
 
 /// ECDSA signatures using the P-384 curve and SHA-384.
 pub static ECDSA_P384_SHA384: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
+    cache_id: "webpki::ECDSA_P384_SHA384",
     public_key_alg_id: alg_id::ECDSA_P384,
     signature_alg_id: alg_id::ECDSA_SHA384,
     verification_alg: &signature::ECDSA_P384_SHA384_ASN1,
@@ -76,6 +85,7 @@ pub static ECDSA_P384_SHA384: &dyn SignatureVerificationAlgorithm = &RingAlgorit
 /// Requires the `alloc` feature.
 #[cfg(feature = "alloc")]
 pub static RSA_PKCS1_2048_8192_SHA256: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
+    cache_id: "webpki::RSA_PKCS1_2048_8192_SHA256",
     public_key_alg_id: alg_id::RSA_ENCRYPTION,
     signature_alg_id: alg_id::RSA_PKCS1_SHA256,
     verification_alg: &signature::RSA_PKCS1_2048_8192_SHA256,
@@ -86,6 +96,7 @@ pub static RSA_PKCS1_2048_8192_SHA256: &dyn SignatureVerificationAlgorithm = &Ri
 /// Requires the `alloc` feature.
 #[cfg(feature = "alloc")]
 pub static RSA_PKCS1_2048_8192_SHA384: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
+    cache_id: "webpki::RSA_PKCS1_2048_8192_SHA384",
     public_key_alg_id: alg_id::RSA_ENCRYPTION,
     signature_alg_id: alg_id::RSA_PKCS1_SHA384,
     verification_alg: &signature::RSA_PKCS1_2048_8192_SHA384,
@@ -96,6 +107,7 @@ pub static RSA_PKCS1_2048_8192_SHA384: &dyn SignatureVerificationAlgorithm = &Ri
 /// Requires the `alloc` feature.
 #[cfg(feature = "alloc")]
 pub static RSA_PKCS1_2048_8192_SHA512: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
+    cache_id: "webpki::RSA_PKCS1_2048_8192_SHA512",
     public_key_alg_id: alg_id::RSA_ENCRYPTION,
     signature_alg_id: alg_id::RSA_PKCS1_SHA512,
     verification_alg: &signature::RSA_PKCS1_2048_8192_SHA512,
@@ -106,6 +118,7 @@ pub static RSA_PKCS1_2048_8192_SHA512: &dyn SignatureVerificationAlgorithm = &Ri
 /// Requires the `alloc` feature.
 #[cfg(feature = "alloc")]
 pub static RSA_PKCS1_3072_8192_SHA384: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
+    cache_id: "webpki::RSA_PKCS1_3072_8192_SHA384",
     public_key_alg_id: alg_id::RSA_ENCRYPTION,
     signature_alg_id: alg_id::RSA_PKCS1_SHA384,
     verification_alg: &signature::RSA_PKCS1_3072_8192_SHA384,
@@ -120,6 +133,7 @@ pub static RSA_PKCS1_3072_8192_SHA384: &dyn SignatureVerificationAlgorithm = &Ri
 #[cfg(feature = "alloc")]
 pub static RSA_PSS_2048_8192_SHA256_LEGACY_KEY: &dyn SignatureVerificationAlgorithm =
     &RingAlgorithm {
+        cache_id: "webpki::RSA_PSS_2048_8192_SHA256_LEGACY_KEY",
         public_key_alg_id: alg_id::RSA_ENCRYPTION,
         signature_alg_id: alg_id::RSA_PSS_SHA256,
         verification_alg: &signature::RSA_PSS_2048_8192_SHA256,
@@ -134,6 +148,7 @@ pub static RSA_PSS_2048_8192_SHA256_LEGACY_KEY: &dyn SignatureVerificationAlgori
 #[cfg(feature = "alloc")]
 pub static RSA_PSS_2048_8192_SHA384_LEGACY_KEY: &dyn SignatureVerificationAlgorithm =
     &RingAlgorithm {
+        cache_id: "webpki::RSA_PSS_2048_8192_SHA384_LEGACY_KEY",
         public_key_alg_id: alg_id::RSA_ENCRYPTION,
         signature_alg_id: alg_id::RSA_PSS_SHA384,
         verification_alg: &signature::RSA_PSS_2048_8192_SHA384,
@@ -148,6 +163,7 @@ pub static RSA_PSS_2048_8192_SHA384_LEGACY_KEY: &dyn SignatureVerificationAlgori
 #[cfg(feature = "alloc")]
 pub static RSA_PSS_2048_8192_SHA512_LEGACY_KEY: &dyn SignatureVerificationAlgorithm =
     &RingAlgorithm {
+        cache_id: "webpki::RSA_PSS_2048_8192_SHA512_LEGACY_KEY",
         public_key_alg_id: alg_id::RSA_ENCRYPTION,
         signature_alg_id: alg_id::RSA_PSS_SHA512,
         verification_alg: &signature::RSA_PSS_2048_8192_SHA512,
@@ -155,6 +171,7 @@ pub static RSA_PSS_2048_8192_SHA512_LEGACY_KEY: &dyn SignatureVerificationAlgori
 
 /// ED25519 signatures according to RFC 8410
 pub static ED25519: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
+    cache_id: "webpki::ED25519",
     public_key_alg_id: alg_id::ED25519,
     signature_alg_id: alg_id::ED25519,
     verification_alg: &signature::ED25519,
@@ -162,10 +179,8 @@ pub static ED25519: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
 
 #[cfg(test)]
 mod tests {
-    use base64::{engine::general_purpose, Engine as _};
-
-    use crate::{der, signed_data, Error};
-    use alloc::{string::String, vec::Vec};
+    use crate::{der, pem, signed_data, Error};
+    use alloc::vec::Vec;
 
     macro_rules! test_file_bytes {
         ( $file_name:expr ) => {
@@ -510,11 +525,11 @@ mod tests {
     }
 
     fn parse_test_signed_data(file_contents: &[u8]) -> TestSignedData {
-        let mut lines = core::str::from_utf8(file_contents).unwrap().lines();
-        let spki = read_pem_section(&mut lines, "PUBLIC KEY");
-        let algorithm = read_pem_section(&mut lines, "ALGORITHM");
-        let data = read_pem_section(&mut lines, "DATA");
-        let signature = read_pem_section(&mut lines, "SIGNATURE");
+        let mut sections = pem::pem_sections(file_contents);
+        let spki = read_pem_section(&mut sections, b"PUBLIC KEY");
+        let algorithm = read_pem_section(&mut sections, b"ALGORITHM");
+        let data = read_pem_section(&mut sections, b"DATA");
+        let signature = read_pem_section(&mut sections, b"SIGNATURE");
 
         TestSignedData {
             spki,
@@ -524,30 +539,14 @@ mod tests {
         }
     }
 
-    use alloc::str::Lines;
-
-    fn read_pem_section(lines: &mut Lines, section_name: &str) -> Vec<u8> {
-        // Skip comments and header
-        let begin_section = format!("-----BEGIN {}-----", section_name);
-        loop {
-            let line = lines.next().unwrap();
-            if line == begin_section {
-                break;
-            }
-        }
-
-        let mut base64 = String::new();
-
-        let end_section = format!("-----END {}-----", section_name);
-        loop {
-            let line = lines.next().unwrap();
-            if line == end_section {
-                break;
-            }
-            base64.push_str(line);
-        }
+    fn read_pem_section(sections: &mut pem::PemSections, label: &[u8]) -> Vec<u8> {
+        let section = sections.next().unwrap().unwrap();
+        assert_eq!(section.label(), label);
 
-        general_purpose::STANDARD.decode(&base64).unwrap()
+        let mut decoded = vec![0; section.decoded_len()];
+        let len = section.decode_into(&mut decoded).unwrap().len();
+        decoded.truncate(len);
+        decoded
     }
 
     static SUPPORTED_ALGORITHMS_IN_TESTS: &[&dyn signed_data::SignatureVerificationAlgorithm] = &[
@@ -0,0 +1,257 @@
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHORS DISCLAIM ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHORS BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+#[cfg(feature = "std")]
+use std::collections::{HashSet, VecDeque};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+use ring::digest;
+
+use crate::signed_data::{self, SignedData};
+use crate::{Error, SignatureVerificationAlgorithm};
+
+/// Identifies a signature verification: the [`SignatureVerificationAlgorithm`] that performed
+/// it, the issuer's public key, and the signed data, its signature algorithm and its signature.
+///
+/// This is a SHA-256 hash of those inputs, so a key reveals nothing about them and has a
+/// fixed size, suitable for fixed-size tables. The algorithm is identified by its
+/// [`SignatureVerificationAlgorithm::cache_id`] and `AlgorithmIdentifier`s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignatureCacheKey([u8; 32]);
+
+impl SignatureCacheKey {
+    /// Returns `None` if `algorithm` has no [`SignatureVerificationAlgorithm::cache_id`], so
+    /// its verifications can't be cached.
+    pub(crate) fn new(
+        algorithm: &dyn SignatureVerificationAlgorithm,
+        spki_value: untrusted::Input,
+        signed_data: &SignedData,
+    ) -> Option<Self> {
+        // Algorithms accepting the same identifiers can differ in the keys they accept, such as
+        // `RSA_PKCS1_2048_8192_SHA384` and `RSA_PKCS1_3072_8192_SHA384`, so a success is only
+        // reused by an algorithm with the same name.
+        let cache_id = algorithm.cache_id()?;
+
+        let mut ctx = digest::Context::new(&digest::SHA256);
+        for input in [
+            cache_id.as_bytes(),
+            algorithm.public_key_alg_id().as_slice(),
+            algorithm.signature_alg_id().as_slice(),
+            spki_value.as_slice_less_safe(),
+            signed_data.algorithm.as_slice_less_safe(),
+            signed_data.data.as_slice_less_safe(),
+            signed_data.signature.as_slice_less_safe(),
+        ] {
+            // Length prefixes keep the boundaries between the inputs unambiguous.
+            ctx.update(&input.len().to_be_bytes());
+            ctx.update(input);
+        }
+
+        let mut key = [0; 32];
+        key.copy_from_slice(ctx.finish().as_ref());
+        Some(Self(key))
+    }
+
+    /// The bytes of the key.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+/// A cache of successful signature verifications.
+///
+/// Verifying the same intermediate and root signatures for every connection is wasteful,
+/// especially for large RSA keys. When a cache is supplied, with
+/// [`VerifyOptions::with_signature_cache`] or [`Verifier::with_signature_cache`], certificate
+/// and CRL signatures are looked up in it before being verified, and those that verify are
+/// recorded in it.
+///
+/// Only successful verifications are recorded, and a key covers everything that determines
+/// the result, including the algorithm that verified the signature, so a cache can only save
+/// work: it never changes the outcome of a verification. In particular, a cache may be shared
+/// between verifications supporting different signature algorithms.
+///
+/// Implementations decide how many entries to retain, and which to evict. [`BoundedSignatureCache`]
+/// is provided for `std` users; `no_std` users can implement this trait over a fixed-size table.
+///
+/// [`VerifyOptions::with_signature_cache`]: crate::VerifyOptions::with_signature_cache
+/// [`Verifier::with_signature_cache`]: crate::Verifier::with_signature_cache
+pub trait SignatureCache: Send + Sync {
+    /// Return true if a verification identified by `key` was recorded by `insert`, and
+    /// hasn't since been evicted.
+    fn contains(&self, key: &SignatureCacheKey) -> bool;
+
+    /// Record that the verification identified by `key` succeeded.
+    fn insert(&self, key: SignatureCacheKey);
+}
+
+/// A [`SignatureCache`] that retains up to a fixed number of entries, evicting the oldest
+/// first.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub struct BoundedSignatureCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+struct Entries {
+    keys: HashSet<SignatureCacheKey>,
+
+    /// The keys in `keys`, oldest first.
+    order: VecDeque<SignatureCacheKey>,
+}
+
+#[cfg(feature = "std")]
+impl BoundedSignatureCache {
+    /// Construct an empty [`BoundedSignatureCache`] retaining up to `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Return the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.entries().keys.len()
+    }
+
+    /// Return true if the cache contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, Entries> {
+        // The entries are consistent between each operation, so a panic while the lock was
+        // held leaves nothing to recover.
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(feature = "std")]
+impl SignatureCache for BoundedSignatureCache {
+    fn contains(&self, key: &SignatureCacheKey) -> bool {
+        self.entries().keys.contains(key)
+    }
+
+    fn insert(&self, key: SignatureCacheKey) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries();
+        if !entries.keys.insert(key) {
+            return;
+        }
+        entries.order.push_back(key);
+        while entries.order.len() > self.capacity {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.keys.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Like [`signed_data::verify_signed_data`], but skipping the signature verification if `cache`
+/// records a previous success by one of `supported_algorithms`, and recording a success in
+/// `cache`. Algorithms without a [`SignatureVerificationAlgorithm::cache_id`] always verify.
+pub(crate) fn verify_signed_data(
+    supported_algorithms: &[&dyn SignatureVerificationAlgorithm],
+    spki_value: untrusted::Input,
+    signed_data: &SignedData,
+    cache: &dyn SignatureCache,
+) -> Result<(), Error> {
+    let cached = supported_algorithms.iter().any(|alg| {
+        alg.signature_alg_id()
+            .matches_algorithm_id_value(signed_data.algorithm)
+            && SignatureCacheKey::new(*alg, spki_value, signed_data)
+                .map_or(false, |key| cache.contains(&key))
+    });
+    if cached {
+        return Ok(());
+    }
+
+    let algorithm =
+        signed_data::verifying_algorithm(supported_algorithms, spki_value, signed_data)?;
+    if let Some(key) = SignatureCacheKey::new(algorithm, spki_value, signed_data) {
+        cache.insert(key);
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_cache_evicts_oldest() {
+        let key = |n| SignatureCacheKey([n; 32]);
+
+        let cache = BoundedSignatureCache::new(2);
+        assert!(cache.is_empty());
+        cache.insert(key(1));
+        cache.insert(key(2));
+        cache.insert(key(1));
+        assert_eq!(cache.len(), 2);
+
+        cache.insert(key(3));
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains(&key(1)));
+        assert!(cache.contains(&key(2)));
+        assert!(cache.contains(&key(3)));
+
+        let cache = BoundedSignatureCache::new(0);
+        cache.insert(key(1));
+        assert!(!cache.contains(&key(1)));
+    }
+
+    #[test]
+    fn key_covers_every_input() {
+        let input = untrusted::Input::from;
+        let signed_data = |data, algorithm, signature| SignedData {
+            data: input(data),
+            algorithm: input(algorithm),
+            signature: input(signature),
+        };
+
+        let key = |alg, spki, data, algorithm, signature| {
+            SignatureCacheKey::new(alg, input(spki), &signed_data(data, algorithm, signature))
+                .unwrap()
+        };
+
+        let alg = crate::RSA_PKCS1_2048_8192_SHA384;
+        let base = key(alg, b"spki", b"data", b"alg", b"sig");
+        assert_eq!(base, key(alg, b"spki", b"data", b"alg", b"sig"));
+        for other in [
+            key(
+                crate::RSA_PKCS1_3072_8192_SHA384,
+                b"spki",
+                b"data",
+                b"alg",
+                b"sig",
+            ),
+            key(alg, b"other", b"data", b"alg", b"sig"),
+            key(alg, b"spki", b"other", b"alg", b"sig"),
+            key(alg, b"spki", b"data", b"other", b"sig"),
+            key(alg, b"spki", b"data", b"alg", b"other"),
+            key(alg, b"spkid", b"ata", b"alg", b"sig"),
+        ] {
+            assert_ne!(base, other);
+        }
+    }
+}
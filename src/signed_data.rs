@@ -153,6 +153,16 @@ pub(crate) fn verify_signed_data(
     spki_value: untrusted::Input,
     signed_data: &SignedData,
 ) -> Result<(), Error> {
+    verifying_algorithm(supported_algorithms, spki_value, signed_data).map(|_| ())
+}
+
+/// Like [`verify_signed_data`], but returning the algorithm of `supported_algorithms` that
+/// verified the signature.
+pub(crate) fn verifying_algorithm<'a>(
+    supported_algorithms: &[&'a dyn SignatureVerificationAlgorithm],
+    spki_value: untrusted::Input,
+    signed_data: &SignedData,
+) -> Result<&'a dyn SignatureVerificationAlgorithm, Error> {
     // We need to verify the signature in `signed_data` using the public key
     // in `public_key`. In order to know which *ring* signature verification
     // algorithm to use, we need to know the public key algorithm (ECDSA,
@@ -188,7 +198,7 @@ pub(crate) fn verify_signed_data(
                 continue;
             }
             result => {
-                return result;
+                return result.map(|()| *supported_alg);
             }
         }
     }
@@ -223,7 +233,7 @@ pub(crate) fn verify_signature(
         .map_err(|_| Error::InvalidSignatureForPublicKey)
 }
 
-struct SubjectPublicKeyInfo<'a> {
+pub(crate) struct SubjectPublicKeyInfo<'a> {
     algorithm_id_value: untrusted::Input<'a>,
     key_value: untrusted::Input<'a>,
 }
@@ -260,6 +270,17 @@ pub trait SignatureVerificationAlgorithm: Send + Sync {
     /// for this `SignatureVerificationAlgorithm` to be considered.
     fn signature_alg_id(&self) -> alg_id::AlgorithmIdentifier;
 
+    /// Return a name identifying this algorithm to a [`SignatureCache`](crate::SignatureCache),
+    /// or `None` if signatures it verifies shouldn't be cached.
+    ///
+    /// A cached success is only reused by an algorithm with the same name and
+    /// `AlgorithmIdentifier`s, so algorithms sharing a cache must only share a name if they
+    /// accept exactly the same signatures: for example, not if one accepts smaller keys than
+    /// the other. The default is `None`.
+    fn cache_id(&self) -> Option<&'static str> {
+        None
+    }
+
     /// Verify a signature.
     ///
     /// `public_key` is the `subjectPublicKey` value from a `SubjectPublicKeyInfo` encoding
@@ -325,6 +346,11 @@ pub mod alg_id {
         pub(crate) fn matches_algorithm_id_value(&self, encoded: untrusted::Input) -> bool {
             encoded == self.asn1_id_value
         }
+
+        #[cfg_attr(not(feature = "ring"), allow(dead_code))]
+        pub(crate) fn as_slice(&self) -> &'static [u8] {
+            self.asn1_id_value.as_slice_less_safe()
+        }
     }
 
     // See src/data/README.md.
@@ -0,0 +1,185 @@
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHORS DISCLAIM ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHORS BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use ring::digest;
+
+use crate::{DnsNameRef, Error};
+
+/// The SHA-256 hash of a DER-encoded SubjectPublicKeyInfo, as used by the `pin-sha256`
+/// directive of RFC 7469[^1].
+///
+/// [^1]: <https://www.rfc-editor.org/rfc/rfc7469#section-2.4>
+pub type SpkiSha256 = [u8; 32];
+
+/// A set of SubjectPublicKeyInfo pins, enforced during path building.
+///
+/// Pins are either global, applying to every verification, or scoped to a hostname. When a
+/// verification has any applicable pins, a path is only accepted if at least one of its
+/// certificates, or its trust anchor, has a public key matching one of them. Paths that don't
+/// are rejected with [`Error::SpkiPinMismatch`] and path building goes on to try others.
+///
+/// When no pins apply, pinning places no constraint on the path.
+///
+/// See [`VerifyOptions::with_spki_pins`].
+///
+/// [`VerifyOptions::with_spki_pins`]: crate::VerifyOptions::with_spki_pins
+#[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "ring"))))]
+#[derive(Clone, Debug, Default)]
+pub struct SpkiPins {
+    global: Vec<SpkiSha256>,
+
+    /// Keyed by lowercased hostname.
+    by_host: BTreeMap<String, Vec<SpkiSha256>>,
+}
+
+impl SpkiPins {
+    /// Construct an empty [`SpkiPins`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pin `spki_sha256` for every verification.
+    pub fn add_global(&mut self, spki_sha256: SpkiSha256) {
+        if !self.global.contains(&spki_sha256) {
+            self.global.push(spki_sha256);
+        }
+    }
+
+    /// Pin `spki_sha256` for verifications of `host`.
+    ///
+    /// Only verifications for exactly `host` are affected; subdomains are not.
+    pub fn add_for_host(&mut self, host: DnsNameRef, spki_sha256: SpkiSha256) {
+        let pins = self.by_host.entry(lowercase(host)).or_default();
+        if !pins.contains(&spki_sha256) {
+            pins.push(spki_sha256);
+        }
+    }
+
+    /// Check the public keys `spkis`, each the contents of the outer SEQUENCE of a
+    /// SubjectPublicKeyInfo, against the pins applicable to `host`.
+    ///
+    /// Returns the first pin that matched, `None` if there are no applicable pins, or
+    /// [`Error::SpkiPinMismatch`] if no key matched any of them.
+    pub(crate) fn check<'s>(
+        &self,
+        host: Option<DnsNameRef>,
+        spkis: impl Iterator<Item = &'s [u8]>,
+    ) -> Result<Option<SpkiSha256>, Error> {
+        let host_pins = host
+            .and_then(|host| self.by_host.get(&lowercase(host)))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if self.global.is_empty() && host_pins.is_empty() {
+            return Ok(None);
+        }
+
+        for spki in spkis {
+            let hash = spki_sha256(spki);
+            if self.global.contains(&hash) || host_pins.contains(&hash) {
+                return Ok(Some(hash));
+            }
+        }
+
+        Err(Error::SpkiPinMismatch)
+    }
+}
+
+fn lowercase(host: DnsNameRef) -> String {
+    <&str>::from(host).to_ascii_lowercase()
+}
+
+/// Hash the DER encoding of the SubjectPublicKeyInfo whose SEQUENCE contents are `spki`.
+fn spki_sha256(spki: &[u8]) -> SpkiSha256 {
+    let mut ctx = digest::Context::new(&digest::SHA256);
+
+    // Re-encode the SEQUENCE header stripped during parsing.
+    let len = spki.len().to_be_bytes();
+    let significant = &len[len.iter().position(|b| *b != 0).unwrap_or(len.len() - 1)..];
+    match significant {
+        [short] if *short < 0x80 => ctx.update(&[0x30, *short]),
+        _ => {
+            // `significant` is at most `size_of::<usize>()` bytes long.
+            let count = significant.len().to_le_bytes()[0];
+            ctx.update(&[0x30, 0x80 | count]);
+            ctx.update(significant);
+        }
+    }
+    ctx.update(spki);
+
+    let mut hash = [0; 32];
+    hash.copy_from_slice(ctx.finish().as_ref());
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spki_sha256_reencodes_header() {
+        for len in [0, 0x7f, 0x80, 0xff, 0x100, 0x1_0000] {
+            let spki = vec![0xa5; len];
+            let mut der = vec![0x30];
+            der.extend_from_slice(&der_length(len));
+            der.extend_from_slice(&spki);
+
+            assert_eq!(
+                spki_sha256(&spki),
+                digest::digest(&digest::SHA256, &der).as_ref(),
+                "length {}",
+                len
+            );
+        }
+    }
+
+    fn der_length(len: usize) -> Vec<u8> {
+        match len {
+            0..=0x7f => vec![u8::try_from(len).unwrap()],
+            0x80..=0xff => vec![0x81, u8::try_from(len).unwrap()],
+            0x100..=0xffff => [&[0x82][..], &u16::try_from(len).unwrap().to_be_bytes()].concat(),
+            _ => [&[0x83][..], &u32::try_from(len).unwrap().to_be_bytes()[1..]].concat(),
+        }
+    }
+
+    #[test]
+    fn host_scoped_pins() {
+        let host = DnsNameRef::try_from_ascii_str("Example.com").unwrap();
+        let other = DnsNameRef::try_from_ascii_str("other.example.com").unwrap();
+        let key = b"key".as_slice();
+
+        let mut pins = SpkiPins::new();
+        assert_eq!(pins.check(Some(host), [key].into_iter()), Ok(None));
+
+        pins.add_for_host(host, spki_sha256(key));
+        let lower = DnsNameRef::try_from_ascii_str("example.com").unwrap();
+        assert_eq!(
+            pins.check(Some(lower), [key].into_iter()),
+            Ok(Some(spki_sha256(key)))
+        );
+        assert_eq!(
+            pins.check(Some(lower), [b"other".as_slice()].into_iter()),
+            Err(Error::SpkiPinMismatch)
+        );
+        assert_eq!(pins.check(Some(other), [key].into_iter()), Ok(None));
+        assert_eq!(pins.check(None, [key].into_iter()), Ok(None));
+
+        pins.add_global(spki_sha256(b"global"));
+        assert_eq!(
+            pins.check(None, [key].into_iter()),
+            Err(Error::SpkiPinMismatch)
+        );
+    }
+}
@@ -33,7 +33,6 @@ pub use ip_address::IpAddr;
 mod verify;
 #[cfg(feature = "alloc")]
 pub(super) use verify::list_cert_dns_names;
-#[allow(unused_imports)] // TODO(@cpu): remove once used by cert module.
 pub(crate) use verify::GeneralName;
 pub(super) use verify::{
     check_name_constraints, verify_cert_subject_name, SubjectCommonNameContents,
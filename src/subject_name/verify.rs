@@ -353,7 +353,7 @@ impl<'a> Iterator for NameIterator<'a> {
 
 #[cfg(feature = "alloc")]
 pub(crate) fn list_cert_dns_names<'names>(
-    cert: &'names crate::EndEntityCert<'names>,
+    cert: &crate::EndEntityCert<'names>,
 ) -> Result<impl Iterator<Item = GeneralDnsNameRef<'names>>, Error> {
     let cert = &cert.inner();
     let mut names = Vec::new();
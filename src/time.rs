@@ -36,6 +36,11 @@ impl Time {
     pub fn from_seconds_since_unix_epoch(secs: u64) -> Self {
         Self(secs)
     }
+
+    /// Return the time `secs` seconds later, saturating at the latest representable time.
+    pub(crate) fn saturating_add_secs(self, secs: u64) -> Self {
+        Self(self.0.saturating_add(secs))
+    }
 }
 
 impl<'a> FromDer<'a> for Time {
@@ -1,5 +1,15 @@
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use crate::cert::{lenient_certificate_serial_number, Cert, EndEntityOrCa};
-use crate::{der, Error};
+use crate::der::{FromDer, CONSTRUCTED, CONTEXT_SPECIFIC};
+use crate::verify_cert::{check_basic_constraints, UsedAsCa};
+use crate::x509::Extension;
+use crate::{der, signed_data, Error, SignatureVerificationAlgorithm};
+
+pub(crate) use private::Anchors;
 
 /// A trust anchor (a.k.a. root CA).
 ///
@@ -27,8 +37,57 @@ impl<'a> TrustAnchor<'a> {
     /// Interprets the given DER-encoded certificate as a `TrustAnchor`. The
     /// certificate is not validated. In particular, there is no check that the
     /// certificate is self-signed or even that the certificate has the cA basic
-    /// constraint.
+    /// constraint; use [`TrustAnchor::try_from_self_signed_cert_der`] for that.
     pub fn try_from_cert_der(cert_der: &'a [u8]) -> Result<Self, Error> {
+        Self::from_cert_der(cert_der).map(|(anchor, _)| anchor)
+    }
+
+    /// Interprets the given DER-encoded certificate as a `TrustAnchor`, like
+    /// [`TrustAnchor::try_from_cert_der`], but only if it is a plausible root.
+    ///
+    /// The certificate must be self-issued, have the cA basic constraint, and
+    /// carry a signature that verifies with its own public key using one of
+    /// `supported_sig_algs`. This catches misconfigured root bundles when they
+    /// are loaded, rather than leaving them to cause confusing path building
+    /// failures later. v1 certificates are rejected, since they can't have
+    /// the cA basic constraint.
+    pub fn try_from_self_signed_cert_der(
+        cert_der: &'a [u8],
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+    ) -> Result<Self, Error> {
+        let cert = Cert::from_der(untrusted::Input::from(cert_der), EndEntityOrCa::EndEntity)?;
+        if cert.issuer != cert.subject {
+            return Err(Error::TrustAnchorNotSelfSigned);
+        }
+
+        untrusted::read_all_optional(cert.basic_constraints, Error::BadDer, |value| {
+            check_basic_constraints(value, UsedAsCa::Yes, 0)
+        })?;
+        signed_data::verify_signed_data(supported_sig_algs, cert.spki.value(), &cert.signed_data)?;
+
+        Ok(Self::from(cert))
+    }
+
+    /// Constructs a `TrustAnchor` from a DER-encoded subject Name and
+    /// SubjectPublicKeyInfo, for trust anchors that are distributed without
+    /// a certificate.
+    pub fn try_from_subject_spki_der(subject: &'a [u8], spki: &'a [u8]) -> Result<Self, Error> {
+        let sequence_value = |der: &'a [u8]| {
+            untrusted::Input::from(der).read_all(Error::BadDer, |der| {
+                der::expect_tag_and_get_value(der, der::Tag::Sequence)
+            })
+        };
+
+        Ok(Self {
+            subject: sequence_value(subject)?.as_slice_less_safe(),
+            spki: sequence_value(spki)?.as_slice_less_safe(),
+            name_constraints: None,
+        })
+    }
+
+    /// Like [`TrustAnchor::try_from_cert_der`], but also returns the value of the certificate's
+    /// `validity` field and subjectKeyIdentifier extension, if any.
+    fn from_cert_der(cert_der: &'a [u8]) -> Result<(Self, CertDetails<'a>), Error> {
         let cert_der = untrusted::Input::from(cert_der);
 
         // XXX: `EndEntityOrCA::EndEntity` is used instead of `EndEntityOrCA::CA`
@@ -41,7 +100,15 @@ impl<'a> TrustAnchor<'a> {
         // parser doesn't allow extensions, so there's no need to worry about
         // embedded name constraints in a v1 certificate.
         match Cert::from_der(cert_der, EndEntityOrCa::EndEntity) {
-            Ok(cert) => Ok(Self::from(cert)),
+            Ok(cert) => {
+                let details = CertDetails {
+                    validity: cert.validity.as_slice_less_safe(),
+                    subject_key_id: cert
+                        .subject_key_identifier
+                        .map(|key_id| key_id.as_slice_less_safe()),
+                };
+                Ok((Self::from(cert), details))
+            }
             Err(Error::UnsupportedCertVersion) => {
                 Self::from_v1_der(cert_der).or(Err(Error::BadDer))
             }
@@ -50,7 +117,7 @@ impl<'a> TrustAnchor<'a> {
     }
 
     /// Parses a v1 certificate directly into a TrustAnchor.
-    fn from_v1_der(cert_der: untrusted::Input<'a>) -> Result<Self, Error> {
+    fn from_v1_der(cert_der: untrusted::Input<'a>) -> Result<(Self, CertDetails<'a>), Error> {
         // X.509 Certificate: https://tools.ietf.org/html/rfc5280#section-4.1.
         cert_der.read_all(Error::BadDer, |cert_der| {
             der::nested(cert_der, der::Tag::Sequence, Error::BadDer, |cert_der| {
@@ -60,15 +127,20 @@ impl<'a> TrustAnchor<'a> {
 
                     skip(tbs, der::Tag::Sequence)?; // signature.
                     skip(tbs, der::Tag::Sequence)?; // issuer.
-                    skip(tbs, der::Tag::Sequence)?; // validity.
+                    let validity = der::expect_tag_and_get_value(tbs, der::Tag::Sequence)?;
                     let subject = der::expect_tag_and_get_value(tbs, der::Tag::Sequence)?;
                     let spki = der::expect_tag_and_get_value(tbs, der::Tag::Sequence)?;
 
-                    Ok(TrustAnchor {
+                    let anchor = TrustAnchor {
                         subject: subject.as_slice_less_safe(),
                         spki: spki.as_slice_less_safe(),
                         name_constraints: None,
-                    })
+                    };
+                    let details = CertDetails {
+                        validity: validity.as_slice_less_safe(),
+                        subject_key_id: None,
+                    };
+                    Ok((anchor, details))
                 });
 
                 // read and discard signatureAlgorithm + signature
@@ -91,6 +163,459 @@ impl<'a> From<Cert<'a>> for TrustAnchor<'a> {
     }
 }
 
+/// An RFC 5914[^1] TrustAnchorInfo: a trust anchor, with the certPath controls constraining
+/// the paths ending at it.
+///
+/// Add it to a [`TrustAnchorStore`] with [`TrustAnchorStore::add_trust_anchor_info`] to have
+/// the controls enforced.
+///
+/// [^1]: <https://www.rfc-editor.org/rfc/rfc5914>
+#[derive(Debug)]
+pub struct TrustAnchorInfo<'a> {
+    anchor: TrustAnchor<'a>,
+    cert_path_controls: &'a [u8],
+}
+
+impl<'a> TrustAnchorInfo<'a> {
+    /// Parse the given DER-encoded TrustAnchorInfo.
+    ///
+    /// The TrustAnchorInfo must contain certPath controls, as they carry the name of the trust
+    /// anchor. Name constraints in the controls become the `name_constraints` of the
+    /// [`TrustAnchor`].
+    pub fn from_der(tai_der: &'a [u8]) -> Result<Self, Error> {
+        // RFC 5914 section 2: the module uses implicit tagging.
+        #[allow(clippy::identity_op)]
+        const EXTENSIONS_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED | 1;
+        const TITLE_LANG_TAG_TAG: u8 = CONTEXT_SPECIFIC | 2;
+
+        untrusted::Input::from(tai_der).read_all(Error::BadDer, |tai_der| {
+            der::nested(tai_der, der::Tag::Sequence, Error::BadDer, |tai| {
+                // version TrustAnchorInfoVersion DEFAULT v1(1), and v1 is the only version. DER
+                // forbids encoding a DEFAULT value, so the version must be absent.
+                if tai.peek(der::Tag::Integer.into()) {
+                    return Err(Error::BadDer);
+                }
+
+                let spki = der::expect_tag_and_get_value(tai, der::Tag::Sequence)?;
+                let _key_id = der::expect_tag_and_get_value(tai, der::Tag::OctetString)?;
+                optional_value(tai, der::Tag::UTF8String.into())?; // taTitle
+                let cert_path = der::expect_tag_and_get_value(tai, der::Tag::Sequence)?;
+
+                if let Some(extensions) = optional_value(tai, EXTENSIONS_TAG)? {
+                    // None of the TrustAnchorInfo extensions are supported.
+                    extensions.read_all(Error::BadDer, |extensions| {
+                        der::nested_of_mut(
+                            extensions,
+                            der::Tag::Sequence,
+                            der::Tag::Sequence,
+                            Error::BadDer,
+                            |extension| Extension::from_der(extension)?.unsupported(),
+                        )
+                    })?;
+                }
+                optional_value(tai, TITLE_LANG_TAG_TAG)?;
+
+                let controls = CertPathControls::from_der(cert_path)?;
+                let anchor = TrustAnchor {
+                    subject: controls.ta_name.as_slice_less_safe(),
+                    spki: spki.as_slice_less_safe(),
+                    name_constraints: controls.name_constraints.map(|nc| nc.as_slice_less_safe()),
+                };
+                Ok(Self {
+                    anchor,
+                    cert_path_controls: cert_path.as_slice_less_safe(),
+                })
+            })
+        })
+    }
+
+    /// The trust anchor.
+    pub fn trust_anchor(&self) -> &TrustAnchor<'a> {
+        &self.anchor
+    }
+
+    /// The value of the DER-encoded CertPathControls of the trust anchor.
+    pub fn cert_path_controls(&self) -> &'a [u8] {
+        self.cert_path_controls
+    }
+}
+
+/// The parts of a trust anchor's certificate that a [`TrustAnchor`] doesn't keep.
+struct CertDetails<'a> {
+    /// The value of the certificate's `validity` field.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    validity: &'a [u8],
+
+    /// The value of the certificate's subjectKeyIdentifier extension, if any.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    subject_key_id: Option<&'a [u8]>,
+}
+
+/// An owned representation of a [`TrustAnchor`], for trust anchors loaded at runtime whose
+/// source DER can't be kept alive.
+///
+/// Use [`OwnedTrustAnchor::to_trust_anchor`] to borrow a [`TrustAnchor`] for verification.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedTrustAnchor {
+    subject: Vec<u8>,
+    spki: Vec<u8>,
+    name_constraints: Option<Vec<u8>>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedTrustAnchor {
+    /// Construct an [`OwnedTrustAnchor`] from its components, with the same meaning as the
+    /// fields of [`TrustAnchor`].
+    pub fn from_subject_spki_name_constraints(
+        subject: impl Into<Vec<u8>>,
+        spki: impl Into<Vec<u8>>,
+        name_constraints: Option<impl Into<Vec<u8>>>,
+    ) -> Self {
+        Self {
+            subject: subject.into(),
+            spki: spki.into(),
+            name_constraints: name_constraints.map(Into::into),
+        }
+    }
+
+    /// Interprets the given DER-encoded certificate as an [`OwnedTrustAnchor`], as
+    /// [`TrustAnchor::try_from_cert_der`] does.
+    pub fn try_from_cert_der(cert_der: &[u8]) -> Result<Self, Error> {
+        TrustAnchor::try_from_cert_der(cert_der).map(|anchor| Self::from(&anchor))
+    }
+
+    /// Interprets the given DER-encoded certificate as an [`OwnedTrustAnchor`], as
+    /// [`TrustAnchor::try_from_self_signed_cert_der`] does.
+    pub fn try_from_self_signed_cert_der(
+        cert_der: &[u8],
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+    ) -> Result<Self, Error> {
+        TrustAnchor::try_from_self_signed_cert_der(cert_der, supported_sig_algs)
+            .map(|anchor| Self::from(&anchor))
+    }
+
+    /// Borrow this trust anchor as a [`TrustAnchor`].
+    pub fn to_trust_anchor(&self) -> TrustAnchor<'_> {
+        TrustAnchor {
+            subject: &self.subject,
+            spki: &self.spki,
+            name_constraints: self.name_constraints.as_deref(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&TrustAnchor<'_>> for OwnedTrustAnchor {
+    fn from(anchor: &TrustAnchor<'_>) -> Self {
+        Self {
+            subject: anchor.subject.to_vec(),
+            spki: anchor.spki.to_vec(),
+            name_constraints: anchor.name_constraints.map(<[u8]>::to_vec),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedTrustAnchor> for TrustAnchor<'a> {
+    fn from(anchor: &'a OwnedTrustAnchor) -> Self {
+        anchor.to_trust_anchor()
+    }
+}
+
+/// A collection of [`TrustAnchor`]s that path building may use.
+///
+/// Implemented for slices, arrays and `Vec`s of [`TrustAnchor`], which are searched linearly, and
+/// for [`TrustAnchorStore`], which is indexed.
+pub trait TrustAnchors: private::Sealed {}
+
+impl TrustAnchors for [TrustAnchor<'_>] {}
+
+impl private::Sealed for [TrustAnchor<'_>] {
+    fn anchors(&self) -> Anchors<'_> {
+        Anchors::Slice(self)
+    }
+}
+
+impl<const N: usize> TrustAnchors for [TrustAnchor<'_>; N] {}
+
+impl<const N: usize> private::Sealed for [TrustAnchor<'_>; N] {
+    fn anchors(&self) -> Anchors<'_> {
+        Anchors::Slice(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TrustAnchors for Vec<TrustAnchor<'_>> {}
+
+#[cfg(feature = "alloc")]
+impl private::Sealed for Vec<TrustAnchor<'_>> {
+    fn anchors(&self) -> Anchors<'_> {
+        Anchors::Slice(self)
+    }
+}
+
+/// A set of [`TrustAnchor`]s indexed by subject, for use when there are too many anchors for a
+/// linear search to be cheap.
+///
+/// Anchors added from certificates with [`TrustAnchorStore::add_cert_der`] are also indexed by
+/// their subjectKeyIdentifier, so that when several anchors share a subject the one matching
+/// the authorityKeyIdentifier of the certificate being verified is tried first.
+///
+/// The store can also hold constraints on the paths ending at each anchor that a
+/// [`TrustAnchor`] has no room for, such as the validity period of anchors added from
+/// certificates, and the certPath controls of anchors added with
+/// [`TrustAnchorStore::add_trust_anchor_info`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Default)]
+pub struct TrustAnchorStore<'a> {
+    anchors: Vec<StoredAnchor<'a>>,
+
+    /// Indexes into `anchors`, keyed by DER encoded subject.
+    by_subject: BTreeMap<&'a [u8], Vec<usize>>,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub(crate) struct StoredAnchor<'a> {
+    pub(crate) anchor: TrustAnchor<'a>,
+    pub(crate) metadata: AnchorMetadata<'a>,
+    subject_key_id: Option<&'a [u8]>,
+}
+
+/// What is known about a trust anchor beyond the fields of [`TrustAnchor`], and constrains the
+/// paths ending at it.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct AnchorMetadata<'a> {
+    /// The value of a DER-encoded RFC 5914 CertPathControls. The policy flags and path length
+    /// constraint are enforced, and the policy set when an explicit policy is required; name
+    /// constraints are only taken from the anchor's `name_constraints`.
+    pub(crate) cert_path_controls: Option<&'a [u8]>,
+
+    /// The value of the `validity` field of the anchor's certificate, enforced when verifying
+    /// with [`VerifyOptions::with_trust_anchor_validity`](crate::VerifyOptions::with_trust_anchor_validity).
+    pub(crate) validity: Option<&'a [u8]>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> TrustAnchorStore<'a> {
+    /// Construct an empty [`TrustAnchorStore`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `anchor` to the store.
+    pub fn add(&mut self, anchor: TrustAnchor<'a>) {
+        self.insert(anchor, AnchorMetadata::default(), None);
+    }
+
+    /// Interpret the DER-encoded certificate `cert_der` as a trust anchor, as
+    /// [`TrustAnchor::try_from_cert_der`] does, and add it to the store.
+    ///
+    /// The certificate's validity period is kept, to be enforced when verifying with
+    /// [`VerifyOptions::with_trust_anchor_validity`](crate::VerifyOptions::with_trust_anchor_validity).
+    pub fn add_cert_der(&mut self, cert_der: &'a [u8]) -> Result<(), Error> {
+        let (anchor, details) = TrustAnchor::from_cert_der(cert_der)?;
+        let metadata = AnchorMetadata {
+            validity: Some(details.validity),
+            ..AnchorMetadata::default()
+        };
+        self.insert(anchor, metadata, details.subject_key_id);
+        Ok(())
+    }
+
+    /// Add the trust anchor of `info` to the store.
+    ///
+    /// The policy flags, path length constraint and name constraints of its certPath controls
+    /// are enforced on paths ending at the anchor, as is their policy set when the policy flags
+    /// require an explicit policy.
+    pub fn add_trust_anchor_info(&mut self, info: TrustAnchorInfo<'a>) {
+        let metadata = AnchorMetadata {
+            cert_path_controls: Some(info.cert_path_controls),
+            ..AnchorMetadata::default()
+        };
+        self.insert(info.anchor, metadata, None);
+    }
+
+    /// Parse the DER-encoded RFC 5914 TrustAnchorInfo `tai_der` with
+    /// [`TrustAnchorInfo::from_der`], and add it to the store as
+    /// [`TrustAnchorStore::add_trust_anchor_info`] does.
+    pub fn add_trust_anchor_info_der(&mut self, tai_der: &'a [u8]) -> Result<(), Error> {
+        self.add_trust_anchor_info(TrustAnchorInfo::from_der(tai_der)?);
+        Ok(())
+    }
+
+    /// Return the number of trust anchors in the store.
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    /// Return true if the store contains no trust anchors.
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    fn insert(
+        &mut self,
+        anchor: TrustAnchor<'a>,
+        metadata: AnchorMetadata<'a>,
+        subject_key_id: Option<&'a [u8]>,
+    ) {
+        self.by_subject
+            .entry(anchor.subject)
+            .or_default()
+            .push(self.anchors.len());
+        self.anchors.push(StoredAnchor {
+            anchor,
+            metadata,
+            subject_key_id,
+        });
+    }
+
+    /// Return the stored anchors whose subject is `subject`.
+    pub(crate) fn with_subject<'s>(
+        &'s self,
+        subject: &[u8],
+    ) -> impl Iterator<Item = &'s TrustAnchor<'a>> + 's {
+        self.by_subject
+            .get(subject)
+            .into_iter()
+            .flatten()
+            .map(move |&i| &self.anchors[i].anchor)
+    }
+
+    /// Return the trust anchors whose subject matches the issuer of `cert`. Those whose
+    /// subjectKeyIdentifier matches the authorityKeyIdentifier of `cert` are returned first.
+    pub(crate) fn candidates(&self, cert: &Cert) -> Vec<&StoredAnchor<'a>> {
+        let indexes = match self.by_subject.get(cert.issuer.as_slice_less_safe()) {
+            Some(indexes) => indexes,
+            None => return Vec::new(),
+        };
+
+        let authority_key_id = cert.authority_key_id().map(|id| id.as_slice_less_safe());
+        let mut candidates = indexes
+            .iter()
+            .map(|&i| &self.anchors[i])
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|candidate| {
+            authority_key_id.is_none() || candidate.subject_key_id != authority_key_id
+        });
+        candidates
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> FromIterator<TrustAnchor<'a>> for TrustAnchorStore<'a> {
+    fn from_iter<T: IntoIterator<Item = TrustAnchor<'a>>>(iter: T) -> Self {
+        let mut store = Self::new();
+        for anchor in iter {
+            store.add(anchor);
+        }
+        store
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TrustAnchors for TrustAnchorStore<'_> {}
+
+#[cfg(feature = "alloc")]
+impl private::Sealed for TrustAnchorStore<'_> {
+    fn anchors(&self) -> Anchors<'_> {
+        Anchors::Store(self)
+    }
+}
+
+mod private {
+    use super::TrustAnchor;
+    #[cfg(feature = "alloc")]
+    use super::TrustAnchorStore;
+
+    pub trait Sealed {
+        fn anchors(&self) -> Anchors<'_>;
+    }
+
+    #[derive(Clone, Copy)]
+    pub enum Anchors<'a> {
+        Slice(&'a [TrustAnchor<'a>]),
+        #[cfg(feature = "alloc")]
+        Store(&'a TrustAnchorStore<'a>),
+    }
+}
+
+/// The RFC 5914 CertPathControls of a trust anchor.
+pub(crate) struct CertPathControls<'a> {
+    pub(crate) ta_name: untrusted::Input<'a>,
+    pub(crate) policy_set: Option<untrusted::Input<'a>>,
+    pub(crate) require_explicit_policy: bool,
+    pub(crate) inhibit_any_policy: bool,
+    pub(crate) name_constraints: Option<untrusted::Input<'a>>,
+    pub(crate) path_len_constraint: Option<usize>,
+}
+
+impl<'a> CertPathControls<'a> {
+    /// Parse the value of a CertPathControls SEQUENCE.
+    pub(crate) fn from_der(value: untrusted::Input<'a>) -> Result<Self, Error> {
+        #[allow(clippy::identity_op)]
+        const CERTIFICATE_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED | 0;
+        const POLICY_SET_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED | 1;
+        const POLICY_FLAGS_TAG: u8 = CONTEXT_SPECIFIC | 2;
+        const NAME_CONSTRAINTS_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED | 3;
+        const PATH_LEN_CONSTRAINT_TAG: u8 = CONTEXT_SPECIFIC | 4;
+
+        value.read_all(Error::BadDer, |controls| {
+            let ta_name = der::expect_tag_and_get_value(controls, der::Tag::Sequence)?;
+            optional_value(controls, CERTIFICATE_TAG)?;
+            let policy_set = optional_value(controls, POLICY_SET_TAG)?;
+
+            // CertPolicyFlags ::= BIT STRING {
+            //   inhibitPolicyMapping (0), requireExplicitPolicy (1), inhibitAnyPolicy (2) }
+            //
+            // Policy mapping is never performed, so inhibitPolicyMapping needs no handling.
+            let (require_explicit_policy, inhibit_any_policy) =
+                match optional_value(controls, POLICY_FLAGS_TAG)? {
+                    // An empty BIT STRING has only the unused bits octet.
+                    Some(flags) if flags.len() > 1 => {
+                        let flags = der::bit_string_flags(flags)?;
+                        (flags.bit_set(1), flags.bit_set(2))
+                    }
+                    Some(flags) if flags.as_slice_less_safe() != [0] => return Err(Error::BadDer),
+                    _ => (false, false),
+                };
+
+            let name_constraints = optional_value(controls, NAME_CONSTRAINTS_TAG)?;
+            let path_len_constraint = match optional_value(controls, PATH_LEN_CONSTRAINT_TAG)? {
+                Some(len) => match *len.as_slice_less_safe() {
+                    [len] if len & 0x80 == 0 => Some(usize::from(len)),
+                    _ => return Err(Error::BadDer),
+                },
+                None => None,
+            };
+
+            Ok(Self {
+                ta_name,
+                policy_set,
+                require_explicit_policy,
+                inhibit_any_policy,
+                name_constraints,
+                path_len_constraint,
+            })
+        })
+    }
+}
+
+/// Read the value of the next element if it has the given tag.
+fn optional_value<'a>(
+    input: &mut untrusted::Reader<'a>,
+    tag: u8,
+) -> Result<Option<untrusted::Input<'a>>, Error> {
+    if !input.peek(tag) {
+        return Ok(None);
+    }
+    der::read_tag_and_get_value(input).map(|(_, value)| Some(value))
+}
+
 fn skip(input: &mut untrusted::Reader, tag: der::Tag) -> Result<(), Error> {
     der::expect_tag_and_get_value(input, tag).map(|_| ())
 }
@@ -0,0 +1,384 @@
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHORS DISCLAIM ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHORS BE LIABLE FOR
+// ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+// WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+// ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+// OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::crl::PrevalidatedCrl;
+use crate::signed_data;
+use crate::trust_anchor::Anchors;
+use crate::verify_cert::{self, ChainOptions};
+use core::time::Duration;
+
+use crate::{
+    CertRevocationList, EndEntityCert, Error, ExpirationPolicy, IntermediateStore, KeyUsage,
+    OwnedCertRevocationList, RevocationCheckDepth, RevocationOptions, SignatureCache,
+    SignatureCacheKey, SignatureVerificationAlgorithm, Time, TrustAnchorStore, UnknownStatusPolicy,
+    VerifiedPath, VerifyError, VerifyOptions,
+};
+
+/// A reusable certificate verifier.
+///
+/// A `Verifier` holds everything about verification that doesn't change from one peer to the
+/// next: the supported signature algorithms, the trust anchors, any known intermediates, the
+/// CRLs and the path building policy. The work of indexing these, and of verifying the CRL
+/// signatures, is done once when the `Verifier` is built rather than on every verification.
+///
+/// A `Verifier` is `Send` and `Sync`, so one instance can serve verifications from many
+/// threads concurrently.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct Verifier<'a> {
+    supported_sig_algs: &'a [&'a dyn SignatureVerificationAlgorithm],
+    trust_anchors: TrustAnchorStore<'a>,
+    intermediates: IntermediateStore,
+    crls: Vec<PrevalidatedCrl>,
+    signature_cache: Option<&'a dyn SignatureCache>,
+    revocation_depth: RevocationCheckDepth,
+    unknown_status_policy: UnknownStatusPolicy,
+    expiration_policy: ExpirationPolicy,
+    grace_period: Duration,
+    partial_chain: bool,
+    trust_anchor_validity: bool,
+}
+
+impl<'a> Verifier<'a> {
+    /// Construct a [`Verifier`] trusting `trust_anchors`, and verifying certificate signatures
+    /// with `supported_sig_algs`.
+    pub fn new(
+        supported_sig_algs: &'a [&'a dyn SignatureVerificationAlgorithm],
+        trust_anchors: TrustAnchorStore<'a>,
+    ) -> Self {
+        Self {
+            supported_sig_algs,
+            trust_anchors,
+            intermediates: IntermediateStore::new(),
+            crls: Vec::new(),
+            signature_cache: None,
+            revocation_depth: RevocationCheckDepth::Chain,
+            unknown_status_policy: UnknownStatusPolicy::Allow,
+            expiration_policy: ExpirationPolicy::Enforce,
+            grace_period: Duration::ZERO,
+            partial_chain: false,
+            trust_anchor_validity: false,
+        }
+    }
+
+    /// Consider the certificates in `store` as intermediates, after those supplied for each
+    /// verification. See [`VerifyOptions::with_intermediate_store`].
+    ///
+    /// The signatures of CRLs already added are verified again with the public keys of the
+    /// stored intermediates, so the result doesn't depend on whether CRLs are added before or
+    /// after the store: an error is returned for a CRL that [`Verifier::add_crl`] would reject.
+    pub fn with_intermediate_store(mut self, store: IntermediateStore) -> Result<Self, Error> {
+        self.intermediates = store;
+        for prevalidated in core::mem::take(&mut self.crls) {
+            self.add_crl(prevalidated.crl)?;
+        }
+        Ok(self)
+    }
+
+    /// Accept an end-entity certificate that is itself a trust anchor. See
+    /// [`VerifyOptions::with_partial_chain`].
+    pub fn with_partial_chain(mut self) -> Self {
+        self.partial_chain = true;
+        self
+    }
+
+    /// Reject paths ending at a trust anchor that isn't valid at the verification time. See
+    /// [`VerifyOptions::with_trust_anchor_validity`].
+    pub fn with_trust_anchor_validity(mut self) -> Self {
+        self.trust_anchor_validity = true;
+        self
+    }
+
+    /// Look up certificate and CRL signatures in `cache` before verifying them. See
+    /// [`VerifyOptions::with_signature_cache`].
+    pub fn with_signature_cache(mut self, cache: &'a dyn SignatureCache) -> Self {
+        self.signature_cache = Some(cache);
+        self
+    }
+
+    /// Set which certificates of each path have their revocation status checked. See
+    /// [`RevocationOptions::with_depth`].
+    pub fn with_revocation_check_depth(mut self, depth: RevocationCheckDepth) -> Self {
+        self.revocation_depth = depth;
+        self
+    }
+
+    /// Set how certificates whose revocation status can't be determined are treated. See
+    /// [`RevocationOptions::with_status_policy`].
+    pub fn with_unknown_status_policy(mut self, policy: UnknownStatusPolicy) -> Self {
+        self.unknown_status_policy = policy;
+        self
+    }
+
+    /// Set how CRLs that aren't current are treated. See
+    /// [`RevocationOptions::with_expiration_policy`].
+    pub fn with_expiration_policy(mut self, policy: ExpirationPolicy) -> Self {
+        self.expiration_policy = policy;
+        self
+    }
+
+    /// Continue to consider CRLs current for `grace_period` after their nextUpdate time. See
+    /// [`RevocationOptions::with_grace_period`].
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Check the certificates of every verification against `crl`.
+    ///
+    /// The CRL's signature is verified now, with the public key of each trust anchor and stored
+    /// intermediate whose subject is the CRL's issuer, and isn't verified again when the CRL is
+    /// consulted for a path through one of those. An error is returned if there are such
+    /// issuers, but the signature can't be verified with any of them. A CRL with no known
+    /// issuer is still accepted, and its signature is verified when a path through its issuer
+    /// is checked against it, as it would be by [`EndEntityCert::verify_for_usage`].
+    pub fn add_crl(&mut self, crl: OwnedCertRevocationList) -> Result<(), Error> {
+        let mut verified_spkis = Vec::new();
+        let mut err = None;
+
+        let anchor_spkis = self
+            .trust_anchors
+            .with_subject(crl.issuer())
+            .map(|anchor| anchor.spki);
+        let intermediate_spkis = self
+            .intermediates
+            .with_subject(crl.issuer())
+            .map(|cert| cert.spki.value().as_slice_less_safe());
+        for spki in anchor_spkis.chain(intermediate_spkis) {
+            match crl.verify_signature(self.supported_sig_algs, spki) {
+                Ok(()) => verified_spkis.push(spki.to_vec()),
+                Err(e) => err = Some(verify_cert::crl_signature_err(e)),
+            }
+        }
+
+        match err {
+            Some(err) if verified_spkis.is_empty() => Err(err),
+            _ => {
+                self.crls.push(PrevalidatedCrl {
+                    crl,
+                    verified_spkis,
+                });
+                Ok(())
+            }
+        }
+    }
+
+    /// Verify that `end_entity` is valid for `usage` at `time`, as
+    /// [`EndEntityCert::verify_for_usage_with_options`] does.
+    ///
+    /// `intermediate_certs` are those supplied by the peer, and are considered before any known
+    /// to the `Verifier`.
+    pub fn verify(
+        &self,
+        end_entity: &EndEntityCert,
+        intermediate_certs: &[&[u8]],
+        time: Time,
+        usage: KeyUsage,
+    ) -> Result<VerifiedPath, VerifyError<'_>> {
+        self.verify_with_signature_cache(
+            end_entity,
+            intermediate_certs,
+            time,
+            usage,
+            self.signature_cache,
+        )
+    }
+
+    /// Prepare to verify many end-entity certificates whose paths run through the stored
+    /// intermediates. See [`BatchVerifier`].
+    pub fn batch(&self) -> BatchVerifier<'_> {
+        let mut verified = BTreeSet::new();
+        for cert in self.intermediates.iter() {
+            let issuer = cert.issuer.as_slice_less_safe();
+            let anchor_spkis = self
+                .trust_anchors
+                .with_subject(issuer)
+                .map(|anchor| untrusted::Input::from(anchor.spki));
+            let intermediate_spkis = self
+                .intermediates
+                .with_subject(issuer)
+                .map(|issuer| issuer.spki.value());
+            for spki in anchor_spkis.chain(intermediate_spkis) {
+                let key = signed_data::verifying_algorithm(
+                    self.supported_sig_algs,
+                    spki,
+                    &cert.signed_data,
+                )
+                .ok()
+                .and_then(|alg| SignatureCacheKey::new(alg, spki, &cert.signed_data));
+                if let Some(key) = key {
+                    verified.insert(key);
+                }
+            }
+        }
+
+        BatchVerifier {
+            verifier: self,
+            signatures: BatchSignatures {
+                verified,
+                fallback: self.signature_cache,
+            },
+        }
+    }
+
+    fn verify_with_signature_cache(
+        &self,
+        end_entity: &EndEntityCert,
+        intermediate_certs: &[&[u8]],
+        time: Time,
+        usage: KeyUsage,
+        signature_cache: Option<&dyn SignatureCache>,
+    ) -> Result<VerifiedPath, VerifyError<'_>> {
+        let mut options = VerifyOptions::new().with_intermediate_store(&self.intermediates);
+        if self.partial_chain {
+            options = options.with_partial_chain();
+        }
+        if self.trust_anchor_validity {
+            options = options.with_trust_anchor_validity();
+        }
+        if let Some(cache) = signature_cache {
+            options = options.with_signature_cache(cache);
+        }
+
+        let revocation = RevocationOptions::from_prevalidated(&self.crls)
+            .with_depth(self.revocation_depth)
+            .with_status_policy(self.unknown_status_policy)
+            .with_expiration_policy(self.expiration_policy)
+            .with_grace_period(self.grace_period);
+
+        verify_cert::build_chain(
+            &ChainOptions {
+                eku: usage,
+                supported_sig_algs: self.supported_sig_algs,
+                trust_anchors: Anchors::Store(&self.trust_anchors),
+                intermediate_certs,
+                revocation: Some(revocation),
+                options,
+            },
+            end_entity.inner(),
+            time,
+        )
+    }
+}
+
+/// Verifies many end-entity certificates against a [`Verifier`], sharing the work of validating
+/// the CA portion of their paths.
+///
+/// Constructing a `BatchVerifier`, with [`Verifier::batch`], verifies the signature of each
+/// intermediate in the verifier's [`IntermediateStore`] with the public keys of its potential
+/// issuers up-front, so paths through them only need the end-entity certificate's signature
+/// to be verified. This suits verifying large numbers of certificates, such as those logged
+/// by Certificate Transparency logs, that share a small set of intermediates.
+///
+/// A `BatchVerifier` is `Send` and `Sync`, so the end-entity certificates can be divided between
+/// threads, with each verifying its share with [`BatchVerifier::verify`], or between the
+/// available cores with [`BatchVerifier::verify_all_parallel`].
+pub struct BatchVerifier<'a> {
+    verifier: &'a Verifier<'a>,
+    signatures: BatchSignatures<'a>,
+}
+
+impl<'a> BatchVerifier<'a> {
+    /// Verify that `end_entity` is valid for `usage` at `time`, as [`Verifier::verify`] does.
+    ///
+    /// The signatures of `intermediate_certs` weren't verified when the batch was constructed,
+    /// so they are verified as usual for paths through them.
+    pub fn verify(
+        &self,
+        end_entity: &EndEntityCert,
+        intermediate_certs: &[&[u8]],
+        time: Time,
+        usage: KeyUsage,
+    ) -> Result<VerifiedPath, VerifyError<'a>> {
+        self.verifier.verify_with_signature_cache(
+            end_entity,
+            intermediate_certs,
+            time,
+            usage,
+            Some(&self.signatures),
+        )
+    }
+
+    /// Verify each of the DER-encoded end-entity certificates `end_entities`, with the
+    /// intermediates supplied alongside it, as [`BatchVerifier::verify`] does, returning a
+    /// result for each in the same order.
+    pub fn verify_all<'c>(
+        &self,
+        end_entities: impl IntoIterator<Item = (&'c [u8], &'c [&'c [u8]])>,
+        time: Time,
+        usage: KeyUsage,
+    ) -> Vec<Result<VerifiedPath, VerifyError<'a>>> {
+        end_entities
+            .into_iter()
+            .map(|(der, intermediate_certs)| {
+                self.verify(
+                    &EndEntityCert::try_from(der)?,
+                    intermediate_certs,
+                    time,
+                    usage,
+                )
+            })
+            .collect()
+    }
+
+    /// Like [`BatchVerifier::verify_all`], but dividing `end_entities` between a thread for
+    /// each of the available cores.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn verify_all_parallel(
+        &self,
+        end_entities: &[(&[u8], &[&[u8]])],
+        time: Time,
+        usage: KeyUsage,
+    ) -> Vec<Result<VerifiedPath, VerifyError<'a>>> {
+        let threads = std::thread::available_parallelism().map_or(1, usize::from);
+        // Round up, so there are at most `threads` chunks; `chunks` panics given zero.
+        let chunk_len = ((end_entities.len() + threads - 1) / threads).max(1);
+
+        std::thread::scope(|scope| {
+            let handles = end_entities
+                .chunks(chunk_len)
+                .map(|chunk| {
+                    scope.spawn(move || self.verify_all(chunk.iter().copied(), time, usage))
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|handle| match handle.join() {
+                    Ok(results) => results,
+                    Err(panic) => std::panic::resume_unwind(panic),
+                })
+                .collect()
+        })
+    }
+}
+
+/// The signatures verified when a [`BatchVerifier`] was constructed, in front of the
+/// verifier's own signature cache, if any.
+struct BatchSignatures<'a> {
+    verified: BTreeSet<SignatureCacheKey>,
+    fallback: Option<&'a dyn SignatureCache>,
+}
+
+impl SignatureCache for BatchSignatures<'_> {
+    fn contains(&self, key: &SignatureCacheKey) -> bool {
+        self.verified.contains(key) || self.fallback.map_or(false, |cache| cache.contains(key))
+    }
+
+    fn insert(&self, key: SignatureCacheKey) {
+        if let Some(cache) = self.fallback {
+            cache.insert(key);
+        }
+    }
+}
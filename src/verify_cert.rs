@@ -12,41 +12,208 @@
 // ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
 // OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+use core::time::Duration;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::IntermediateStore;
+#[cfg(feature = "ring")]
+use crate::{signature_cache, SignatureCache};
+#[cfg(all(feature = "alloc", feature = "ring"))]
+use crate::{DnsNameRef, SpkiPins, SpkiSha256};
+
 use crate::cert::{Cert, EndEntityOrCa};
+#[cfg(feature = "alloc")]
+use crate::crl::PrevalidatedCrl;
+use crate::crl::{compare_crl_numbers, IssuingDistributionPoint, ReasonFlags};
 use crate::der::{self, FromDer};
+use crate::trust_anchor::{AnchorMetadata, Anchors, CertPathControls};
 use crate::{
-    signed_data, subject_name, time, CertRevocationList, Error, SignatureVerificationAlgorithm,
-    TrustAnchor,
+    signed_data, subject_name, time, BorrowedRevokedCert, CertRevocationList, Error,
+    RevocationReason, SignatureVerificationAlgorithm, TrustAnchor,
 };
 
-pub(crate) struct ChainOptions<'a> {
+/// The revocation options have their own lifetime, that of the CRLs, which the details of a
+/// revoked certificate returned in a [`VerifyError`] borrow from.
+pub(crate) struct ChainOptions<'a, 'r> {
     pub(crate) eku: KeyUsage,
     pub(crate) supported_sig_algs: &'a [&'a dyn SignatureVerificationAlgorithm],
-    pub(crate) trust_anchors: &'a [TrustAnchor<'a>],
+    pub(crate) trust_anchors: Anchors<'a>,
     pub(crate) intermediate_certs: &'a [&'a [u8]],
-    pub(crate) crls: &'a [&'a dyn CertRevocationList],
+    pub(crate) revocation: Option<RevocationOptions<'r>>,
+    pub(crate) options: VerifyOptions<'a>,
 }
 
-pub(crate) fn build_chain(opts: &ChainOptions, cert: &Cert, time: time::Time) -> Result<(), Error> {
-    build_chain_inner(opts, cert, time, 0)
+pub(crate) fn build_chain<'r>(
+    opts: &ChainOptions<'_, 'r>,
+    cert: &Cert,
+    time: time::Time,
+) -> Result<VerifiedPath, VerifyError<'r>> {
+    let mut revoked = None;
+    build_chain_inner(opts, cert, time, 0, &mut Budget::new(), &mut revoked)
+        .map_err(|error| VerifyError::new(error, revoked))
 }
 
-fn build_chain_inner(
+/// `revoked` records the first revoked certificate found; see [`record_revoked`].
+fn build_chain_inner<'r>(
+    opts: &ChainOptions<'_, 'r>,
+    cert: &Cert,
+    time: time::Time,
+    sub_ca_count: usize,
+    budget: &mut Budget,
+    revoked: &mut Option<RevokedCertDetail<'r>>,
+) -> Result<VerifiedPath, Error> {
+    let (used_as_ca, subject_common_name_contents) = check_cert(opts, cert, time, sub_ca_count)?;
+
+    let err = match opts.options.partial_chain && used_as_ca == UsedAsCa::No {
+        true => match try_end_entity_as_anchor(opts, cert) {
+            Ok(path) => return Ok(path),
+            Err(err) => err,
+        },
+        false => Error::UnknownIssuer,
+    };
+
+    let err = match try_anchors(
+        opts,
+        cert,
+        time,
+        used_as_ca,
+        sub_ca_count,
+        subject_common_name_contents,
+        revoked,
+    ) {
+        Ok(path) => return Ok(path),
+        Err(new_err) => err.most_specific(new_err),
+    };
+
+    let next_sub_ca_count = next_sub_ca_count(used_as_ca, sub_ca_count);
+
+    let try_issuer = |potential_issuer: &Cert,
+                      budget: &mut Budget,
+                      revoked: &mut Option<RevokedCertDetail<'r>>| {
+        check_issuer(cert, potential_issuer, subject_common_name_contents)?;
+        build_chain_inner(
+            opts,
+            potential_issuer,
+            time,
+            next_sub_ca_count,
+            budget,
+            revoked,
+        )
+    };
+    let try_issuer_der =
+        |cert_der: &[u8], budget: &mut Budget, revoked: &mut Option<RevokedCertDetail<'r>>| {
+            let potential_issuer =
+                Cert::from_der(untrusted::Input::from(cert_der), EndEntityOrCa::Ca(cert))?;
+            try_issuer(&potential_issuer, budget, revoked)
+        };
+
+    let err = match loop_while_non_fatal_error(err, opts.intermediate_certs, |cert_der| {
+        try_issuer_der(cert_der, budget, revoked)
+    }) {
+        Ok(path) => return Ok(path),
+        Err(err) => err,
+    };
+
+    #[cfg(feature = "alloc")]
+    let err = match opts.options.intermediate_store {
+        Some(store) => match loop_while_non_fatal_error(err, store.candidates(cert), |issuer| {
+            try_issuer(&issuer, budget, revoked)
+        }) {
+            Ok(path) => return Ok(path),
+            Err(err) => err,
+        },
+        None => err,
+    };
+
+    // Only ask the issuer source for help once the supplied intermediates are exhausted, and
+    // only while the budget allows: a misbehaving source must not be able to make path
+    // building arbitrarily expensive.
+    #[cfg(feature = "alloc")]
+    if let Some(source) = opts.options.issuer_source {
+        if budget.consume_issuer_source_query() {
+            let candidates = source.issuers(&IssuerQuery { cert });
+            return loop_while_non_fatal_error(err, candidates.iter(), |cert_der| {
+                try_issuer_der(cert_der, budget, revoked)
+            });
+        }
+    }
+
+    Err(err)
+}
+
+/// Verify `cert` against exactly the path `issuers`, ordered from the issuer of `cert` to the
+/// certificate issued by one of the trust anchors, without searching for alternatives.
+pub(crate) fn verify_ordered_chain<'r>(
+    opts: &ChainOptions<'_, 'r>,
+    cert: &Cert,
+    time: time::Time,
+) -> Result<VerifiedPath, VerifyError<'r>> {
+    let mut revoked = None;
+    verify_ordered_chain_inner(opts, cert, opts.intermediate_certs, time, 0, &mut revoked)
+        .map_err(|error| VerifyError::new(error, revoked))
+}
+
+fn verify_ordered_chain_inner<'r>(
+    opts: &ChainOptions<'_, 'r>,
+    cert: &Cert,
+    issuers: &[&[u8]],
+    time: time::Time,
+    sub_ca_count: usize,
+    revoked: &mut Option<RevokedCertDetail<'r>>,
+) -> Result<VerifiedPath, Error> {
+    let (used_as_ca, subject_common_name_contents) = check_cert(opts, cert, time, sub_ca_count)?;
+
+    let (issuer_der, issuers) = match issuers.split_first() {
+        Some(next) => next,
+        None => {
+            return try_anchors(
+                opts,
+                cert,
+                time,
+                used_as_ca,
+                sub_ca_count,
+                subject_common_name_contents,
+                revoked,
+            )
+        }
+    };
+
+    let issuer = Cert::from_der(untrusted::Input::from(issuer_der), EndEntityOrCa::Ca(cert))?;
+    check_issuer(cert, &issuer, subject_common_name_contents)?;
+
+    verify_ordered_chain_inner(
+        opts,
+        &issuer,
+        issuers,
+        time,
+        next_sub_ca_count(used_as_ca, sub_ca_count),
+        revoked,
+    )
+}
+
+/// The maximum number of intermediate certificates in a path.
+const MAX_SUB_CA_COUNT: usize = 6;
+
+/// Check the properties of `cert` that don't depend on its issuer, returning how it's used and
+/// how its subject commonName should be treated for name constraints.
+fn check_cert(
     opts: &ChainOptions,
     cert: &Cert,
     time: time::Time,
     sub_ca_count: usize,
-) -> Result<(), Error> {
+) -> Result<(UsedAsCa, subject_name::SubjectCommonNameContents), Error> {
     let used_as_ca = used_as_ca(&cert.ee_or_ca);
 
     check_issuer_independent_properties(cert, time, used_as_ca, sub_ca_count, opts.eku.inner)?;
 
-    // TODO: HPKP checks.
-
     match used_as_ca {
         UsedAsCa::Yes => {
-            const MAX_SUB_CA_COUNT: usize = 6;
-
             if sub_ca_count >= MAX_SUB_CA_COUNT {
                 // TODO(XXX): Candidate for a more specific error - Error::PathTooDeep?
                 return Err(Error::UnknownIssuer);
@@ -71,95 +238,780 @@ fn build_chain_inner(
         subject_name::SubjectCommonNameContents::Ignore
     };
 
-    let result = loop_while_non_fatal_error(
-        Error::UnknownIssuer,
-        opts.trust_anchors,
-        |trust_anchor: &TrustAnchor| {
-            let trust_anchor_subject = untrusted::Input::from(trust_anchor.subject);
-            if cert.issuer != trust_anchor_subject {
-                return Err(Error::UnknownIssuer);
+    Ok((used_as_ca, subject_common_name_contents))
+}
+
+/// Try to complete the path ending in `cert` with each of the trust anchors that could have
+/// issued it.
+fn try_anchors<'r>(
+    opts: &ChainOptions<'_, 'r>,
+    cert: &Cert,
+    time: time::Time,
+    used_as_ca: UsedAsCa,
+    sub_ca_count: usize,
+    subject_common_name_contents: subject_name::SubjectCommonNameContents,
+    revoked: &mut Option<RevokedCertDetail<'r>>,
+) -> Result<VerifiedPath, Error> {
+    let mut try_anchor = |trust_anchor: &TrustAnchor, metadata: &AnchorMetadata| {
+        let trust_anchor_subject = untrusted::Input::from(trust_anchor.subject);
+        if cert.issuer != trust_anchor_subject {
+            return Err(Error::UnknownIssuer);
+        }
+
+        if opts.options.trust_anchor_validity {
+            if let Some(validity) = metadata.validity {
+                untrusted::Input::from(validity)
+                    .read_all(Error::BadDer, |value| check_validity(value, time))?;
             }
+        }
 
-            let name_constraints = trust_anchor.name_constraints.map(untrusted::Input::from);
+        let name_constraints = trust_anchor.name_constraints.map(untrusted::Input::from);
 
-            untrusted::read_all_optional(name_constraints, Error::BadDer, |value| {
-                subject_name::check_name_constraints(value, cert, subject_common_name_contents)
-            })?;
+        untrusted::read_all_optional(name_constraints, Error::BadDer, |value| {
+            subject_name::check_name_constraints(value, cert, subject_common_name_contents)
+        })?;
 
-            // TODO: check_distrust(trust_anchor_subject, trust_anchor_spki)?;
+        if let Some(controls) = metadata.cert_path_controls {
+            let controls = CertPathControls::from_der(untrusted::Input::from(controls))?;
+            check_cert_path_controls(&controls, cert, used_as_ca, sub_ca_count)?;
+        }
 
-            check_signatures(opts.supported_sig_algs, cert, trust_anchor, opts.crls)?;
+        // Pins are checked per candidate path, so that a path without a pinned key can be
+        // abandoned in favour of one with.
+        #[cfg(all(feature = "alloc", feature = "ring"))]
+        let spki_pin = match opts.options.spki_pins {
+            Some((pins, host)) => pins.check(
+                host,
+                path(cert)
+                    .map(|cert| cert.spki.value().as_slice_less_safe())
+                    .chain(core::iter::once(trust_anchor.spki)),
+            )?,
+            None => None,
+        };
 
-            Ok(())
-        },
-    );
+        // TODO: check_distrust(trust_anchor_subject, trust_anchor_spki)?;
 
-    let err = match result {
-        Ok(()) => return Ok(()),
-        Err(err) => err,
+        let revocation_status = check_signatures(opts, cert, trust_anchor, time, revoked)?;
+
+        Ok(VerifiedPath {
+            #[cfg(all(feature = "alloc", feature = "ring"))]
+            spki_pin,
+            revocation_status,
+        })
+    };
+
+    match opts.trust_anchors {
+        Anchors::Slice(anchors) => {
+            loop_while_non_fatal_error(Error::UnknownIssuer, anchors, |anchor| {
+                try_anchor(anchor, &AnchorMetadata::default())
+            })
+        }
+        #[cfg(feature = "alloc")]
+        Anchors::Store(store) => {
+            loop_while_non_fatal_error(Error::UnknownIssuer, store.candidates(cert), |stored| {
+                try_anchor(&stored.anchor, &stored.metadata)
+            })
+        }
+    }
+}
+
+/// Accept the end-entity certificate `cert` if it is itself one of the trust anchors.
+///
+/// Failure is never fatal: a path to an issuing trust anchor may still be built as usual.
+///
+/// Only an anchor with exactly the subject and SPKI of `cert` is accepted. The anchor's name
+/// constraints and certPath controls constrain the certificates it issues, so they don't apply
+/// here; and since there is no issuer, the revocation status of `cert` is not checked.
+fn try_end_entity_as_anchor(opts: &ChainOptions, cert: &Cert) -> Result<VerifiedPath, Error> {
+    let subject = cert.subject.as_slice_less_safe();
+    let spki = cert.spki.value().as_slice_less_safe();
+    let is_cert = |anchor: &TrustAnchor| anchor.subject == subject && anchor.spki == spki;
+
+    let found = match opts.trust_anchors {
+        Anchors::Slice(anchors) => anchors.iter().any(is_cert),
+        #[cfg(feature = "alloc")]
+        Anchors::Store(store) => store.with_subject(subject).any(is_cert),
+    };
+    if !found {
+        return Err(Error::UnknownIssuer);
+    }
+
+    #[cfg(all(feature = "alloc", feature = "ring"))]
+    let spki_pin = match opts.options.spki_pins {
+        Some((pins, host)) => pins.check(host, core::iter::once(spki))?,
+        None => None,
     };
 
-    loop_while_non_fatal_error(err, opts.intermediate_certs, |cert_der| {
-        let potential_issuer =
-            Cert::from_der(untrusted::Input::from(cert_der), EndEntityOrCa::Ca(cert))?;
+    Ok(VerifiedPath {
+        #[cfg(all(feature = "alloc", feature = "ring"))]
+        spki_pin,
+        revocation_status: RevocationStatuses::default(),
+    })
+}
+
+/// Check that `potential_issuer` could have issued `cert`, as far as names are concerned, and
+/// that it isn't already in the path ending at `cert`.
+fn check_issuer(
+    cert: &Cert,
+    potential_issuer: &Cert,
+    subject_common_name_contents: subject_name::SubjectCommonNameContents,
+) -> Result<(), Error> {
+    if potential_issuer.subject != cert.issuer {
+        return Err(Error::UnknownIssuer);
+    }
 
-        if potential_issuer.subject != cert.issuer {
+    // Prevent loops; see RFC 4158 section 5.2.
+    let mut prev = cert;
+    loop {
+        if potential_issuer.spki.value() == prev.spki.value()
+            && potential_issuer.subject == prev.subject
+        {
             return Err(Error::UnknownIssuer);
         }
+        match &prev.ee_or_ca {
+            EndEntityOrCa::EndEntity => {
+                break;
+            }
+            EndEntityOrCa::Ca(child_cert) => {
+                prev = child_cert;
+            }
+        }
+    }
 
-        // Prevent loops; see RFC 4158 section 5.2.
-        let mut prev = cert;
-        loop {
-            if potential_issuer.spki.value() == prev.spki.value()
-                && potential_issuer.subject == prev.subject
-            {
-                return Err(Error::UnknownIssuer);
+    untrusted::read_all_optional(potential_issuer.name_constraints, Error::BadDer, |value| {
+        subject_name::check_name_constraints(value, cert, subject_common_name_contents)
+    })
+}
+
+fn next_sub_ca_count(used_as_ca: UsedAsCa, sub_ca_count: usize) -> usize {
+    match used_as_ca {
+        UsedAsCa::No => sub_ca_count,
+        UsedAsCa::Yes => sub_ca_count + 1,
+    }
+}
+
+// Bounds the amount of work a single call to `build_chain` may perform.
+struct Budget {
+    #[cfg(feature = "alloc")]
+    issuer_source_queries: usize,
+}
+
+impl Budget {
+    fn new() -> Self {
+        Self {
+            // Enough to fetch every issuer of a maximum length path, with a little room for
+            // candidates that turn out to be dead ends.
+            #[cfg(feature = "alloc")]
+            issuer_source_queries: 8,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn consume_issuer_source_query(&mut self) -> bool {
+        match self.issuer_source_queries.checked_sub(1) {
+            Some(remaining) => {
+                self.issuer_source_queries = remaining;
+                true
             }
-            match &prev.ee_or_ca {
-                EndEntityOrCa::EndEntity => {
-                    break;
+            None => false,
+        }
+    }
+}
+
+/// A source of candidate issuer certificates, consulted during path building when none of the
+/// intermediate certificates supplied for verification lead to a trust anchor.
+///
+/// This is the extension point for "AIA chasing" (fetching the certificates named by the
+/// caIssuers URIs of a certificate's authorityInfoAccess extension) or for looking up
+/// certificates in a local store. Transport is left entirely to the implementation.
+///
+/// Candidates returned by the source are treated exactly like intermediate certificates
+/// supplied by the peer; they are never trusted implicitly. The number of times the source is
+/// consulted during a single verification is bounded, and the usual path length and loop
+/// detection checks apply to the paths it contributes to.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait IssuerSource {
+    /// Return DER-encoded certificates that may have issued the certificate described by
+    /// `query`. Returning an empty list is always acceptable.
+    fn issuers(&self, query: &IssuerQuery) -> Vec<Vec<u8>>;
+}
+
+/// Describes the certificate whose issuer is sought from an [`IssuerSource`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct IssuerQuery<'a> {
+    cert: &'a Cert<'a>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> IssuerQuery<'a> {
+    /// The DER encoded issuer distinguished name of the certificate.
+    pub fn issuer(&self) -> &'a [u8] {
+        self.cert.issuer.as_slice_less_safe()
+    }
+
+    /// The keyIdentifier of the certificate's authorityKeyIdentifier extension, if any.
+    pub fn authority_key_id(&self) -> Option<&'a [u8]> {
+        self.cert
+            .authority_key_id()
+            .map(|key_id| key_id.as_slice_less_safe())
+    }
+
+    /// The caIssuers URIs of the certificate's authorityInfoAccess extension, if any.
+    pub fn ca_issuers(&self) -> impl Iterator<Item = &'a [u8]> {
+        self.cert.ca_issuers().map(|uri| uri.as_slice_less_safe())
+    }
+}
+
+/// Optional inputs to path building beyond those taken by
+/// [`EndEntityCert::verify_for_usage`](crate::EndEntityCert::verify_for_usage).
+///
+/// The default value changes nothing about how verification is performed.
+#[derive(Clone, Copy, Default)]
+pub struct VerifyOptions<'a> {
+    #[cfg(feature = "alloc")]
+    issuer_source: Option<&'a dyn IssuerSource>,
+    #[cfg(feature = "alloc")]
+    intermediate_store: Option<&'a IntermediateStore>,
+    #[cfg(all(feature = "alloc", feature = "ring"))]
+    spki_pins: Option<(&'a SpkiPins, Option<DnsNameRef<'a>>)>,
+    #[cfg(feature = "ring")]
+    signature_cache: Option<&'a dyn SignatureCache>,
+    partial_chain: bool,
+    trust_anchor_validity: bool,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a> VerifyOptions<'a> {
+    /// Construct [`VerifyOptions`] that add nothing to the default verification behaviour.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consult `source` for candidate issuers when the supplied intermediates don't lead to a
+    /// trust anchor.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn with_issuer_source(mut self, source: &'a dyn IssuerSource) -> Self {
+        self.issuer_source = Some(source);
+        self
+    }
+
+    /// Consider the certificates in `store` as intermediates, after those supplied for the
+    /// verification itself.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn with_intermediate_store(mut self, store: &'a IntermediateStore) -> Self {
+        self.intermediate_store = Some(store);
+        self
+    }
+
+    /// Accept an end-entity certificate that is itself a trust anchor, without any issuer.
+    ///
+    /// This is the equivalent of OpenSSL's `X509_V_FLAG_PARTIAL_CHAIN` for directly trusted
+    /// certificates, like self-signed certificates pinned for a particular service. The
+    /// end-entity certificate is only accepted if a trust anchor has exactly its subject and
+    /// SubjectPublicKeyInfo, and it must still pass the checks that don't depend on an issuer,
+    /// such as its validity period and extended key usage. Its revocation status is not checked.
+    ///
+    /// If no trust anchor matches, a path to an issuing trust anchor is built as usual.
+    pub fn with_partial_chain(mut self) -> Self {
+        self.partial_chain = true;
+        self
+    }
+
+    /// Reject paths ending at a trust anchor whose own validity period doesn't include the
+    /// verification time, as described in RFC 5937 section 3.
+    ///
+    /// Only anchors added to a [`TrustAnchorStore`](crate::TrustAnchorStore) from a certificate
+    /// with [`TrustAnchorStore::add_cert_der`](crate::TrustAnchorStore::add_cert_der) have a
+    /// validity period; other anchors are considered valid at all times.
+    pub fn with_trust_anchor_validity(mut self) -> Self {
+        self.trust_anchor_validity = true;
+        self
+    }
+
+    /// Only accept paths containing a public key pinned by `pins`, for the pins that are global
+    /// or scoped to `host`.
+    ///
+    /// The key that matched is reported by [`VerifiedPath::spki_pin`].
+    #[cfg(all(feature = "alloc", feature = "ring"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "ring"))))]
+    pub fn with_spki_pins(mut self, pins: &'a SpkiPins, host: Option<DnsNameRef<'a>>) -> Self {
+        self.spki_pins = Some((pins, host));
+        self
+    }
+
+    /// Look up certificate and CRL signatures in `cache` before verifying them, and record
+    /// those that verify. See [`SignatureCache`].
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    pub fn with_signature_cache(mut self, cache: &'a dyn SignatureCache) -> Self {
+        self.signature_cache = Some(cache);
+        self
+    }
+}
+
+/// Options for checking the revocation status of the certificates in a path, with the CRLs
+/// they're given.
+///
+/// By default, the revocation status of every certificate in the path is checked, a certificate
+/// whose status can't be determined from the CRLs is accepted, and using a CRL that isn't
+/// current is an error.
+#[derive(Clone, Copy)]
+pub struct RevocationOptions<'a> {
+    crls: &'a [&'a dyn CertRevocationList],
+    /// The CRLs of a [`Verifier`](crate::Verifier), checked after `crls`.
+    #[cfg(feature = "alloc")]
+    prevalidated_crls: &'a [PrevalidatedCrl],
+    depth: RevocationCheckDepth,
+    status_policy: UnknownStatusPolicy,
+    expiration_policy: ExpirationPolicy,
+    grace_period: Duration,
+}
+
+impl<'a> RevocationOptions<'a> {
+    /// Construct [`RevocationOptions`] checking certificates against `crls`, with the default
+    /// policies.
+    pub fn new(crls: &'a [&'a dyn CertRevocationList]) -> Self {
+        Self {
+            crls,
+            #[cfg(feature = "alloc")]
+            prevalidated_crls: &[],
+            depth: RevocationCheckDepth::Chain,
+            status_policy: UnknownStatusPolicy::Allow,
+            expiration_policy: ExpirationPolicy::Enforce,
+            grace_period: Duration::ZERO,
+        }
+    }
+
+    /// Construct [`RevocationOptions`] checking certificates against the CRLs of a
+    /// [`Verifier`](crate::Verifier), with the default policies.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn from_prevalidated(crls: &'a [PrevalidatedCrl]) -> Self {
+        Self {
+            prevalidated_crls: crls,
+            ..Self::new(&[])
+        }
+    }
+
+    /// Set which certificates of the path have their revocation status checked.
+    pub fn with_depth(mut self, depth: RevocationCheckDepth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Set how certificates whose revocation status can't be determined are treated.
+    pub fn with_status_policy(mut self, policy: UnknownStatusPolicy) -> Self {
+        self.status_policy = policy;
+        self
+    }
+
+    /// Set how CRLs that aren't current are treated.
+    ///
+    /// A CRL is current at a time no earlier than its thisUpdate, and no later than its
+    /// nextUpdate plus the grace period; see [`RevocationOptions::with_grace_period`]. A
+    /// complete CRL updated by a delta CRL is as current as the delta CRL, so an expired
+    /// complete CRL is still used with a delta CRL that is current.
+    pub fn with_expiration_policy(mut self, policy: ExpirationPolicy) -> Self {
+        self.expiration_policy = policy;
+        self
+    }
+
+    /// Continue to consider CRLs current for `grace_period` after their nextUpdate time, to
+    /// tolerate a CRL issuer that is late publishing its next CRL. The default is zero.
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Check that `crl` is current at `time`, returning [`Error::CrlNotValidYet`] or
+    /// [`Error::CrlExpired`] if it isn't.
+    fn check_current(&self, crl: &dyn CertRevocationList, time: time::Time) -> Result<(), Error> {
+        if time < crl.this_update() {
+            return Err(Error::CrlNotValidYet);
+        }
+
+        let expiry = crl
+            .next_update()
+            .saturating_add_secs(self.grace_period.as_secs());
+        if time > expiry {
+            return Err(Error::CrlExpired);
+        }
+
+        Ok(())
+    }
+
+    /// Whether `crl` isn't current at `time`, and is to be skipped under
+    /// [`ExpirationPolicy::Ignore`].
+    fn ignores(&self, crl: &dyn CertRevocationList, time: time::Time) -> bool {
+        self.expiration_policy == ExpirationPolicy::Ignore && self.check_current(crl, time).is_err()
+    }
+
+    /// Return all of the CRLs to check certificates against.
+    fn crls(&self) -> impl Iterator<Item = &'a dyn CertRevocationList> {
+        let crls = self.crls.iter().copied();
+        #[cfg(feature = "alloc")]
+        let crls = crls.chain(
+            self.prevalidated_crls
+                .iter()
+                .map(|crl| -> &dyn CertRevocationList { crl }),
+        );
+        crls
+    }
+}
+
+/// Which certificates of a path have their revocation status checked. See
+/// [`RevocationOptions::with_depth`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevocationCheckDepth {
+    /// Only check the end-entity certificate.
+    EndEntity,
+
+    /// Check every certificate in the path, other than the trust anchor.
+    Chain,
+}
+
+/// How a certificate whose revocation status can't be determined is treated: one for which
+/// there is no CRL in scope, or whose CRLs don't cover every revocation reason. See
+/// [`RevocationOptions::with_status_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnknownStatusPolicy {
+    /// Treat the certificate as not revoked.
+    Allow,
+
+    /// Reject the certificate, with [`Error::UnknownRevocationStatus`].
+    Deny,
+}
+
+/// How a CRL that isn't current is treated. See [`RevocationOptions::with_expiration_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExpirationPolicy {
+    /// Skip the CRL, as if it hadn't been supplied. The revocation status of a certificate
+    /// covered only by CRLs that aren't current is unknown, and is treated according to the
+    /// [`UnknownStatusPolicy`].
+    Ignore,
+
+    /// Using the CRL is an error: [`Error::CrlExpired`] after its nextUpdate time plus the
+    /// grace period, or [`Error::CrlNotValidYet`] before its thisUpdate time. This is the
+    /// default.
+    Enforce,
+}
+
+/// Details of the path found by
+/// [`EndEntityCert::verify_for_usage_with_options`](crate::EndEntityCert::verify_for_usage_with_options).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerifiedPath {
+    #[cfg(all(feature = "alloc", feature = "ring"))]
+    spki_pin: Option<SpkiSha256>,
+    revocation_status: RevocationStatuses,
+}
+
+impl VerifiedPath {
+    /// The revocation status of each certificate in the path, other than the trust anchor,
+    /// starting with the end-entity certificate.
+    ///
+    /// This is empty if the end-entity certificate was itself a trust anchor, since there is no
+    /// issuer to check its revocation status with.
+    pub fn revocation_status(&self) -> &[RevocationStatus] {
+        &self.revocation_status.statuses[..self.revocation_status.len]
+    }
+
+    /// The pin that a public key in the path matched, if pins were configured with
+    /// [`VerifyOptions::with_spki_pins`] and any applied.
+    #[cfg(all(feature = "alloc", feature = "ring"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "ring"))))]
+    pub fn spki_pin(&self) -> Option<&SpkiSha256> {
+        self.spki_pin.as_ref()
+    }
+}
+
+/// The revocation status of a certificate in a [`VerifiedPath`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RevocationStatus {
+    /// The certificate's revocation status wasn't checked: no [`RevocationOptions`] were given,
+    /// or the certificate is beyond the [`RevocationCheckDepth`].
+    #[default]
+    NotChecked,
+
+    /// The CRLs cover every revocation reason, and none revokes the certificate.
+    NotRevoked,
+
+    /// The certificate's revocation status couldn't be determined from the CRLs, and was
+    /// accepted under [`UnknownStatusPolicy::Allow`].
+    Unknown,
+}
+
+/// The revocation status of each certificate of a path, indexed by depth.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct RevocationStatuses {
+    statuses: [RevocationStatus; MAX_SUB_CA_COUNT + 1],
+    len: usize,
+}
+
+/// Details of a failed verification, returned by
+/// [`EndEntityCert::verify_for_usage_with_options`](crate::EndEntityCert::verify_for_usage_with_options).
+///
+/// When verification failed with [`Error::CertRevoked`], this also has the details of the
+/// revoked certificate, borrowed from the CRL revoking it.
+#[derive(Clone, Debug)]
+pub struct VerifyError<'a> {
+    error: Error,
+    revoked: Option<RevokedCertDetail<'a>>,
+}
+
+impl<'a> VerifyError<'a> {
+    fn new(error: Error, revoked: Option<RevokedCertDetail<'a>>) -> Self {
+        // A revoked certificate may have been found in a candidate path that was abandoned in
+        // favour of one failing with a more specific error.
+        let revoked = match error {
+            Error::CertRevoked => revoked,
+            _ => None,
+        };
+        Self { error, revoked }
+    }
+
+    /// The reason verification failed.
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    /// The revoked certificate that caused verification to fail, if it failed with
+    /// [`Error::CertRevoked`].
+    ///
+    /// If more than one candidate path had a revoked certificate, this is the one in the path
+    /// whose error was returned.
+    pub fn revoked(&self) -> Option<&RevokedCertDetail<'a>> {
+        self.revoked.as_ref()
+    }
+}
+
+impl From<Error> for VerifyError<'_> {
+    fn from(error: Error) -> Self {
+        Self::new(error, None)
+    }
+}
+
+impl From<VerifyError<'_>> for Error {
+    fn from(error: VerifyError<'_>) -> Self {
+        error.error
+    }
+}
+
+impl fmt::Display for VerifyError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+impl ::std::error::Error for VerifyError<'_> {}
+
+/// A revoked certificate in a path, and the CRL entry revoking it. See
+/// [`VerifyError::revoked`].
+#[derive(Clone, Debug)]
+pub struct RevokedCertDetail<'a> {
+    entry: BorrowedRevokedCert<'a>,
+    crl_issuer: &'a [u8],
+    depth: usize,
+}
+
+impl<'a> RevokedCertDetail<'a> {
+    /// The CRL entry revoking the certificate, with its revocation date, and its reason code
+    /// and invalidity date, if any.
+    pub fn entry(&self) -> &BorrowedRevokedCert<'a> {
+        &self.entry
+    }
+
+    /// The DER encoded issuer of the CRL the entry is in. For a delta CRL, this is the issuer
+    /// of the delta CRL.
+    pub fn crl_issuer(&self) -> &'a [u8] {
+        self.crl_issuer
+    }
+
+    /// The position of the certificate in the path: 0 for the end-entity certificate, 1 for
+    /// its issuer, and so on.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+/// Enforce the certPath controls of a trust anchor over the path ending in `cert`.
+fn check_cert_path_controls(
+    controls: &CertPathControls,
+    cert: &Cert,
+    used_as_ca: UsedAsCa,
+    sub_ca_count: usize,
+) -> Result<(), Error> {
+    // `cert` is the last intermediate of the path, if there are any.
+    let intermediates = match used_as_ca {
+        UsedAsCa::Yes => sub_ca_count + 1,
+        UsedAsCa::No => 0,
+    };
+    if matches!(controls.path_len_constraint, Some(len) if intermediates > len) {
+        return Err(Error::PathLenConstraintViolated);
+    }
+
+    check_policies(controls, cert)
+}
+
+/// When the trust anchor requires an explicit policy, check that every certificate in the path
+/// ending in `cert` asserts a policy in the trust anchor's policy set, following RFC 5280
+/// section 6.1 without policy mapping.
+///
+/// Otherwise the path is accepted whatever its policies: RFC 5937 takes the policy set as the
+/// user-initial-policy-set, which only narrows the policies the path is valid for, and RFC 5280
+/// section 6.1.6 only fails a path without a valid policy when an explicit policy is required.
+///
+/// Since policy mappings are never applied, paths that would only be valid through a mapping
+/// are rejected.
+fn check_policies(controls: &CertPathControls, cert: &Cert) -> Result<(), Error> {
+    if !controls.require_explicit_policy {
+        return Ok(());
+    }
+
+    let any_policy = untrusted::Input::from(&ANY_POLICY);
+
+    let anchor_allows_any = match controls.policy_set {
+        Some(policy_set) => asserts_policy(Some(policy_set), any_policy, true)?,
+        None => true,
+    };
+    let path_asserts = |policy| -> Result<bool, Error> {
+        for cert in path(cert) {
+            if !asserts_policy(
+                cert.certificate_policies,
+                policy,
+                controls.inhibit_any_policy,
+            )? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    };
+
+    if anchor_allows_any {
+        // The path must still agree on a policy, which can only be one asserted along it.
+        for cert in path(cert) {
+            for policy in policies(cert.certificate_policies) {
+                let policy = policy?;
+                if policy == any_policy && controls.inhibit_any_policy {
+                    continue;
                 }
-                EndEntityOrCa::Ca(child_cert) => {
-                    prev = child_cert;
+                if path_asserts(policy)? {
+                    return Ok(());
                 }
             }
         }
+    } else {
+        for policy in policies(controls.policy_set) {
+            if path_asserts(policy?)? {
+                return Ok(());
+            }
+        }
+    }
 
-        untrusted::read_all_optional(potential_issuer.name_constraints, Error::BadDer, |value| {
-            subject_name::check_name_constraints(value, cert, subject_common_name_contents)
-        })?;
+    Err(Error::RequiredPolicyNotFound)
+}
 
-        let next_sub_ca_count = match used_as_ca {
-            UsedAsCa::No => sub_ca_count,
-            UsedAsCa::Yes => sub_ca_count + 1,
-        };
+/// Whether the CertificatePolicies value `policies` contains `policy`, or anyPolicy unless
+/// `inhibit_any_policy` is set.
+fn asserts_policy(
+    policies: Option<untrusted::Input>,
+    policy: untrusted::Input,
+    inhibit_any_policy: bool,
+) -> Result<bool, Error> {
+    for asserted in self::policies(policies) {
+        let asserted = asserted?;
+        if asserted == policy || (!inhibit_any_policy && asserted == ANY_POLICY[..]) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Iterate over the policy identifiers in the value of a CertificatePolicies SEQUENCE.
+fn policies(
+    policies: Option<untrusted::Input>,
+) -> impl Iterator<Item = Result<untrusted::Input, Error>> {
+    policies
+        .map(der::DerIterator::<PolicyIdentifier>::new)
+        .into_iter()
+        .flatten()
+        .map(|policy| policy.map(|policy| policy.0))
+}
+
+/// The policyIdentifier of a PolicyInformation; any policyQualifiers are ignored.
+struct PolicyIdentifier<'a>(untrusted::Input<'a>);
+
+impl<'a> FromDer<'a> for PolicyIdentifier<'a> {
+    fn from_der(reader: &mut untrusted::Reader<'a>) -> Result<Self, Error> {
+        der::nested(reader, der::Tag::Sequence, Error::BadDer, |info| {
+            let id = der::expect_tag_and_get_value(info, der::Tag::OID)?;
+            info.skip_to_end();
+            Ok(Self(id))
+        })
+    }
+}
 
-        build_chain_inner(opts, &potential_issuer, time, next_sub_ca_count)
+/// The certificates of the path ending in `cert`, from `cert` down to the end-entity.
+fn path<'a>(cert: &'a Cert<'a>) -> impl Iterator<Item = &'a Cert<'a>> {
+    core::iter::successors(Some(cert), |cert| match cert.ee_or_ca {
+        EndEntityOrCa::Ca(child) => Some(child),
+        EndEntityOrCa::EndEntity => None,
     })
 }
 
-fn check_signatures(
-    supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+// anyPolicy 2.5.29.32.0
+static ANY_POLICY: [u8; 4] = oid![2, 5, 29, 32, 0];
+
+/// Verify the signatures of the path ending in `cert_chain`, and check the revocation status
+/// of its certificates, returning the status of each.
+fn check_signatures<'r>(
+    opts: &ChainOptions<'_, 'r>,
     cert_chain: &Cert,
     trust_anchor: &TrustAnchor,
-    crls: &[&dyn CertRevocationList],
-) -> Result<(), Error> {
+    time: time::Time,
+    revoked: &mut Option<RevokedCertDetail<'r>>,
+) -> Result<RevocationStatuses, Error> {
+    let mut statuses = RevocationStatuses {
+        len: path(cert_chain).count(),
+        ..RevocationStatuses::default()
+    };
     let mut spki_value = untrusted::Input::from(trust_anchor.spki);
     let mut issuer_subject = untrusted::Input::from(trust_anchor.subject);
     let mut issuer_key_usage = None; // TODO(XXX): Consider whether to track TrustAnchor KU.
     let mut cert = cert_chain;
     loop {
-        signed_data::verify_signed_data(supported_sig_algs, spki_value, &cert.signed_data)?;
+        verify_signed_data(opts, spki_value, &cert.signed_data)?;
 
-        if !crls.is_empty() {
-            check_crls(
-                supported_sig_algs,
-                cert,
-                issuer_subject,
-                spki_value,
-                issuer_key_usage,
-                crls,
-            )?;
+        if let Some(revocation) = &opts.revocation {
+            let checked = match revocation.depth {
+                RevocationCheckDepth::EndEntity => {
+                    matches!(cert.ee_or_ca, EndEntityOrCa::EndEntity)
+                }
+                RevocationCheckDepth::Chain => true,
+            };
+            if checked {
+                let status = check_crls(
+                    opts,
+                    revocation,
+                    cert,
+                    issuer_subject,
+                    spki_value,
+                    issuer_key_usage,
+                    time,
+                    revoked,
+                )?;
+                if status.is_none() && revocation.status_policy == UnknownStatusPolicy::Deny {
+                    return Err(Error::UnknownRevocationStatus);
+                }
+                statuses.statuses[path(cert).count() - 1] = match status {
+                    Some(CertNotRevoked(())) => RevocationStatus::NotRevoked,
+                    None => RevocationStatus::Unknown,
+                };
+            }
         }
 
         match &cert.ee_or_ca {
@@ -175,7 +1027,7 @@ fn check_signatures(
         }
     }
 
-    Ok(())
+    Ok(statuses)
 }
 
 // Zero-sized marker type representing positive assertion that revocation status was checked
@@ -189,46 +1041,386 @@ impl CertNotRevoked {
     }
 }
 
-fn check_crls(
-    supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+/// Verify `signed_data` with the public key `spki_value`, consulting the signature cache of
+/// `opts`, if any.
+fn verify_signed_data(
+    opts: &ChainOptions,
+    spki_value: untrusted::Input,
+    signed_data: &signed_data::SignedData,
+) -> Result<(), Error> {
+    #[cfg(feature = "ring")]
+    if let Some(cache) = opts.options.signature_cache {
+        return signature_cache::verify_signed_data(
+            opts.supported_sig_algs,
+            spki_value,
+            signed_data,
+            cache,
+        );
+    }
+
+    signed_data::verify_signed_data(opts.supported_sig_algs, spki_value, signed_data)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_crls<'r>(
+    opts: &ChainOptions,
+    revocation: &RevocationOptions<'r>,
     cert: &Cert,
     issuer_subject: untrusted::Input,
     issuer_spki: untrusted::Input,
     issuer_ku: Option<untrusted::Input>,
-    crls: &[&dyn CertRevocationList],
+    time: time::Time,
+    revoked: &mut Option<RevokedCertDetail<'r>>,
 ) -> Result<Option<CertNotRevoked>, Error> {
     assert_eq!(cert.issuer, issuer_subject);
 
-    let crl = match crls
-        .iter()
-        .find(|candidate_crl| candidate_crl.issuer() == cert.issuer())
-    {
-        Some(crl) => crl,
-        None => return Ok(None),
-    };
+    // RFC 5280 §6.3.3[^1]: CRLs can be partitioned by revocation reason, so consult CRLs
+    // until every reason is covered, or the certificate is found to be revoked. If some
+    // reasons can't be covered, the certificate's revocation status is unknown.
+    //
+    // [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-6.3.3>
+    let mut reasons_mask = ReasonFlags::NONE;
+    let mut signature_err;
+    loop {
+        // Of the CRLs whose scope includes the certificate for a reason not covered yet, from
+        // its issuer or from a CRL issuer named by its distribution points, use the newest
+        // whose signature verifies: the one with the highest cRLNumber. CRLs without a
+        // cRLNumber are only used if none has one, in which case the first is used. A CRL out
+        // of scope says nothing about the certificate.
+        //
+        // Several CRLs can share an issuer name but not a key, for example during a key
+        // rollover, so a signature that doesn't verify is only an error if no candidate's
+        // does.
+        //
+        // Delta CRLs are only used to update the complete CRL chosen this way. An expired
+        // complete CRL is still current if a delta CRL for it is; see `check_crl_status`.
+        let mut newest: Option<(&'r dyn CertRevocationList, ReasonFlags)> = None;
+        signature_err = None;
+        for candidate_crl in revocation.crls() {
+            if candidate_crl.base_crl_number().is_some()
+                || (revocation.ignores(candidate_crl, time)
+                    && newest_delta_crl(
+                        opts,
+                        revocation,
+                        cert,
+                        candidate_crl,
+                        issuer_spki,
+                        issuer_ku,
+                        time,
+                    )
+                    .is_none())
+            {
+                continue;
+            }
+            let reasons = crl_reasons(candidate_crl, cert);
+            if reasons_mask.contains(reasons) {
+                continue;
+            }
+            match verify_candidate_crl_signature(
+                opts,
+                candidate_crl,
+                cert,
+                issuer_spki,
+                issuer_ku,
+                time,
+            ) {
+                Some(Ok(())) => {}
+                Some(Err(err)) => {
+                    signature_err.get_or_insert(err);
+                    continue;
+                }
+                None => continue,
+            }
+
+            newest = match newest {
+                Some((newest_crl, _)) if !is_newer_crl(candidate_crl, newest_crl) => newest,
+                _ => Some((candidate_crl, reasons)),
+            };
+        }
+
+        let (crl, reasons) = match newest {
+            Some(newest) => newest,
+            None => break,
+        };
+        check_crl_status(
+            opts,
+            revocation,
+            cert,
+            crl,
+            issuer_spki,
+            issuer_ku,
+            time,
+            revoked,
+        )?;
+
+        reasons_mask = reasons_mask.union(reasons);
+        if reasons_mask.contains(ReasonFlags::ALL) {
+            return Ok(Some(CertNotRevoked::assertion()));
+        }
+    }
+
+    match signature_err {
+        Some(err) => Err(err),
+        None => Ok(None),
+    }
+}
 
-    // Verify the CRL signature with the issuer SPKI.
-    // TODO(XXX): consider whether we can refactor so this happens once up-front, instead
-    //            of per-lookup.
-    //            https://github.com/rustls/webpki/issues/81
-    crl.verify_signature(supported_sig_algs, issuer_spki.as_slice_less_safe())
-        .map_err(crl_signature_err)?;
+/// Return the newest of the delta CRLs for the complete CRL `crl` whose signature verifies, if
+/// any. Delta CRLs whose signatures don't verify are skipped, as if they hadn't been supplied.
+fn newest_delta_crl<'r>(
+    opts: &ChainOptions,
+    revocation: &RevocationOptions<'r>,
+    cert: &Cert,
+    crl: &dyn CertRevocationList,
+    issuer_spki: untrusted::Input,
+    issuer_ku: Option<untrusted::Input>,
+    time: time::Time,
+) -> Option<&'r dyn CertRevocationList> {
+    revocation
+        .crls()
+        .filter(|candidate_crl| {
+            candidate_crl.is_delta_for(crl) && !revocation.ignores(*candidate_crl, time)
+        })
+        .filter(|candidate_crl| {
+            matches!(
+                verify_candidate_crl_signature(
+                    opts,
+                    *candidate_crl,
+                    cert,
+                    issuer_spki,
+                    issuer_ku,
+                    time
+                ),
+                Some(Ok(()))
+            )
+        })
+        .reduce(|newest, candidate_crl| {
+            if is_newer_crl(candidate_crl, newest) {
+                candidate_crl
+            } else {
+                newest
+            }
+        })
+}
 
-    // Verify that if the issuer has a KeyUsage bitstring it asserts cRLSign.
-    KeyUsageMode::CrlSign.check(issuer_ku)?;
+/// Check the revocation status of `cert` in the complete CRL `crl`, updated by the newest of
+/// the delta CRLs for it whose signature verifies, if any.
+///
+/// RFC 5280 §5.2.4[^1]: a delta CRL and the complete CRL it updates are equivalent to a
+/// complete CRL published at the time of the delta CRL, so when there is a delta CRL the
+/// expiration policy applies to it alone, and an expired complete CRL is still used.
+///
+/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5.2.4>
+#[allow(clippy::too_many_arguments)]
+fn check_crl_status<'r>(
+    opts: &ChainOptions,
+    revocation: &RevocationOptions<'r>,
+    cert: &Cert,
+    crl: &'r dyn CertRevocationList,
+    issuer_spki: untrusted::Input,
+    issuer_ku: Option<untrusted::Input>,
+    time: time::Time,
+    revoked: &mut Option<RevokedCertDetail<'r>>,
+) -> Result<(), Error> {
+    let delta_crl = newest_delta_crl(opts, revocation, cert, crl, issuer_spki, issuer_ku, time);
 
-    // Try to find the cert serial in the verified CRL contents.
+    if revocation.expiration_policy == ExpirationPolicy::Enforce {
+        revocation.check_current(delta_crl.unwrap_or(crl), time)?;
+    }
+
+    // Try to find the cert issuer and serial in the verified CRL contents, starting with the
+    // delta CRL, which is newer. An entry with the removeFromCRL reason in a delta CRL means
+    // that the certificate was on hold, and has since been released, so it only cancels an
+    // entry of the complete CRL with the certificateHold reason: any other revocation is
+    // permanent.
     let cert_serial = cert.serial.as_slice_less_safe();
-    match crl.find_serial(cert_serial)? {
-        None => Ok(Some(CertNotRevoked::assertion())),
-        Some(_) => Err(Error::CertRevoked),
+    let mut released = false;
+    if let Some(delta_crl) = delta_crl {
+        match delta_crl.find_serial(cert.issuer(), cert_serial)? {
+            Some(BorrowedRevokedCert {
+                reason_code: Some(RevocationReason::RemoveFromCrl),
+                ..
+            }) => released = true,
+            Some(entry) => return Err(record_revoked(revoked, cert, delta_crl, entry)),
+            None => {}
+        }
+    }
+
+    match crl.find_serial(cert.issuer(), cert_serial)? {
+        None => Ok(()),
+        Some(BorrowedRevokedCert {
+            reason_code: Some(RevocationReason::CertificateHold),
+            ..
+        }) if released => Ok(()),
+        Some(entry) => Err(record_revoked(revoked, cert, crl, entry)),
+    }
+}
+
+/// Record in `revoked` that `cert` is revoked by `entry` of `crl`, returning
+/// [`Error::CertRevoked`].
+///
+/// Only the first revoked certificate found during a verification is recorded: when more than
+/// one candidate path fails with [`Error::CertRevoked`], [`Error::most_specific`] keeps the
+/// error of the first.
+fn record_revoked<'r>(
+    revoked: &mut Option<RevokedCertDetail<'r>>,
+    cert: &Cert,
+    crl: &'r dyn CertRevocationList,
+    entry: BorrowedRevokedCert<'r>,
+) -> Error {
+    revoked.get_or_insert(RevokedCertDetail {
+        entry,
+        crl_issuer: crl.issuer(),
+        depth: path(cert).count() - 1,
+    });
+    Error::CertRevoked
+}
+
+/// Return true if `candidate` is newer than `newest`: it has a higher cRLNumber, or has a
+/// cRLNumber when `newest` doesn't.
+fn is_newer_crl(candidate: &dyn CertRevocationList, newest: &dyn CertRevocationList) -> bool {
+    match (candidate.crl_number(), newest.crl_number()) {
+        (Some(candidate), Some(newest)) => {
+            compare_crl_numbers(candidate, newest) == Ordering::Greater
+        }
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+/// Verify the signature of `crl`, a candidate for checking the revocation status of `cert`,
+/// returning `None` if there is no key it could have been signed with.
+///
+/// A CRL from the issuer of `cert` is verified with `issuer_spki`, unless its
+/// authorityKeyIdentifier names another key than that of `cert` does, and if the issuer has a
+/// KeyUsage bitstring it must assert cRLSign. Otherwise it is verified with the key of a
+/// dedicated CRL signing certificate; see [`verify_crl_signer_signature`].
+///
+/// A CRL from a delegated CRL issuer is verified with the key of a CRL signing certificate
+/// for that issuer, issued by the issuer of `cert`.
+fn verify_candidate_crl_signature(
+    opts: &ChainOptions,
+    crl: &dyn CertRevocationList,
+    cert: &Cert,
+    issuer_spki: untrusted::Input,
+    issuer_ku: Option<untrusted::Input>,
+    time: time::Time,
+) -> Option<Result<(), Error>> {
+    if crl.issuer() == cert.issuer() {
+        let issuer_key = match (crl.authority_key_identifier(), cert.authority_key_id()) {
+            (Some(crl_key_id), Some(cert_key_id)) => crl_key_id == cert_key_id.as_slice_less_safe(),
+            _ => true,
+        };
+        let result = issuer_key.then(|| {
+            verify_crl_signature(opts, crl, issuer_spki)
+                .and_then(|()| KeyUsageMode::CrlSign.check(issuer_ku))
+        });
+        return match result {
+            Some(Ok(())) => result,
+            _ if verify_crl_signer_signature(opts, crl, cert, issuer_spki, time) => Some(Ok(())),
+            _ => result,
+        };
+    }
+
+    // An indirect CRL is only used if its issuer is certified by the issuer of `cert`, so that
+    // both chain to the same trust anchor.
+    verify_crl_signer_signature(opts, crl, cert, issuer_spki, time).then_some(Ok(()))
+}
+
+/// Return true if `crl` was signed by the key of a dedicated CRL signing certificate from the
+/// intermediates, issued by the issuer of `cert`.
+///
+/// RFC 5280 §6.3.3 (f)[^1] requires the certification path of the CRL issuer to end at the
+/// trust anchor of the path being checked. This allows a CA to sign its CRLs with a key other
+/// than the one it signs certificates with, by certifying that key for the same name, or to
+/// delegate them to an indirect CRL issuer by certifying that issuer's key. We accept
+/// certificates with the CRL issuer's name, whose signature verifies with `issuer_spki`, which
+/// are valid at `time`, and which have a KeyUsage bitstring asserting cRLSign. The revocation
+/// status of the CRL signing certificate itself is not checked.
+///
+/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-6.3.3>
+fn verify_crl_signer_signature(
+    opts: &ChainOptions,
+    crl: &dyn CertRevocationList,
+    cert: &Cert,
+    issuer_spki: untrusted::Input,
+    time: time::Time,
+) -> bool {
+    let is_signer = |signer: &Cert| {
+        signer.subject.as_slice_less_safe() == crl.issuer()
+            && signer.issuer == cert.issuer
+            && signer.spki.value() != issuer_spki
+            && signer.key_usage.is_some()
+            && KeyUsageMode::CrlSign.check(signer.key_usage).is_ok()
+            && signer
+                .validity
+                .read_all(Error::BadDer, |value| check_validity(value, time))
+                .is_ok()
+            && verify_signed_data(opts, issuer_spki, &signer.signed_data).is_ok()
+            && verify_crl_signature(opts, crl, signer.spki.value()).is_ok()
+    };
+
+    let parsed_signer = |signer_der: &[u8]| match Cert::from_der(
+        untrusted::Input::from(signer_der),
+        EndEntityOrCa::EndEntity,
+    ) {
+        Ok(signer) => is_signer(&signer),
+        Err(_) => false,
+    };
+    if opts.intermediate_certs.iter().copied().any(parsed_signer) {
+        return true;
+    }
+
+    #[cfg(feature = "alloc")]
+    if let Some(store) = opts.options.intermediate_store {
+        return store
+            .with_subject(crl.issuer())
+            .any(|signer| is_signer(&signer));
+    }
+
+    false
+}
+
+/// Verify the signature of `crl` with the issuer SPKI, consulting the signature cache of
+/// `opts`, if any.
+///
+/// This happens per-lookup; a `Verifier` verifies the signatures of its CRLs once up-front,
+/// and this check is then free for them. See https://github.com/rustls/webpki/issues/81
+fn verify_crl_signature(
+    opts: &ChainOptions,
+    crl: &dyn CertRevocationList,
+    issuer_spki: untrusted::Input,
+) -> Result<(), Error> {
+    let issuer_spki = issuer_spki.as_slice_less_safe();
+    #[cfg(feature = "ring")]
+    let result = match opts.options.signature_cache {
+        Some(cache) => crl.verify_signature_with_cache(opts.supported_sig_algs, issuer_spki, cache),
+        None => crl.verify_signature(opts.supported_sig_algs, issuer_spki),
+    };
+    #[cfg(not(feature = "ring"))]
+    let result = crl.verify_signature(opts.supported_sig_algs, issuer_spki);
+    result.map_err(crl_signature_err)
+}
+
+/// Return the revocation reasons `crl` covers for `cert`, if it is within scope for `cert`. A
+/// CRL without an issuing distribution point from the issuer of `cert` covers every reason
+/// for every certificate from that issuer. Otherwise, its issuing distribution point
+/// determines the reasons it covers.
+fn crl_reasons(crl: &dyn CertRevocationList, cert: &Cert) -> ReasonFlags {
+    match crl.issuing_distribution_point() {
+        // The issuing distribution point was checked to parse when the CRL was.
+        Some(idp) => IssuingDistributionPoint::from_der(untrusted::Input::from(idp))
+            .map(|idp| idp.reasons_for(crl.issuer(), cert))
+            .unwrap_or(ReasonFlags::NONE),
+        None if crl.issuer() == cert.issuer() => ReasonFlags::ALL,
+        None => ReasonFlags::NONE,
     }
 }
 
 // When verifying CRL signed data we want to disambiguate the context of possible errors by mapping
 // them to CRL specific variants that a consumer can use to tell the issue was with the CRL's
 // signature, not a certificate.
-fn crl_signature_err(err: Error) -> Error {
+pub(crate) fn crl_signature_err(err: Error) -> Error {
     match err {
         Error::UnsupportedSignatureAlgorithm => Error::UnsupportedCrlSignatureAlgorithm,
         Error::UnsupportedSignatureAlgorithmForPublicKey => {
@@ -290,7 +1482,7 @@ fn check_validity(input: &mut untrusted::Reader, time: time::Time) -> Result<(),
 }
 
 #[derive(Clone, Copy, PartialEq)]
-enum UsedAsCa {
+pub(crate) enum UsedAsCa {
     Yes,
     No,
 }
@@ -303,7 +1495,7 @@ fn used_as_ca(ee_or_ca: &EndEntityOrCa) -> UsedAsCa {
 }
 
 // https://tools.ietf.org/html/rfc5280#section-4.2.1.9
-fn check_basic_constraints(
+pub(crate) fn check_basic_constraints(
     input: Option<&mut untrusted::Reader>,
     used_as_ca: UsedAsCa,
     sub_ca_count: usize,
@@ -485,18 +1677,18 @@ impl KeyUsageMode {
     }
 }
 
-fn loop_while_non_fatal_error<V>(
+fn loop_while_non_fatal_error<V, T>(
     default_error: Error,
     values: V,
-    f: impl Fn(V::Item) -> Result<(), Error>,
-) -> Result<(), Error>
+    mut f: impl FnMut(V::Item) -> Result<T, Error>,
+) -> Result<T, Error>
 where
     V: IntoIterator,
 {
     let mut error = default_error;
     for v in values {
         match f(v) {
-            Ok(()) => return Ok(()),
+            Ok(ok) => return Ok(ok),
             Err(new_error) => error = error.most_specific(new_error),
         }
     }
@@ -0,0 +1,57 @@
+#![cfg(feature = "alloc")]
+
+use webpki::{parse_certdata, Time, TrustAnchor};
+
+#[test]
+fn certdata_roots() {
+    let certdata = include_str!("certdata/certdata.txt");
+    let roots = parse_certdata(certdata).unwrap();
+    assert_eq!(roots.len(), 2);
+
+    let digicert = &roots[0];
+    assert_eq!(digicert.label(), "DigiCert Global Root CA");
+    assert_eq!(digicert.cert_der(), include_bytes!("cloudflare_dns/ca.der"));
+    assert!(digicert.server_auth_trusted());
+    assert!(!digicert.email_protection_trusted());
+    assert_eq!(
+        digicert.server_distrust_after(),
+        Some(Time::from_seconds_since_unix_epoch(1_593_475_200))
+    );
+    assert_eq!(digicert.email_distrust_after(), None);
+
+    let anchor = digicert.trust_anchor().unwrap();
+    let expected = TrustAnchor::try_from_cert_der(include_bytes!("cloudflare_dns/ca.der")).unwrap();
+    assert_eq!(anchor.subject, expected.subject);
+    assert_eq!(anchor.spki, expected.spki);
+
+    // Without a trust object, a certificate is trusted for nothing.
+    let netflix = &roots[1];
+    assert_eq!(netflix.label(), "Netflix \u{e9} Root");
+    assert!(!netflix.server_auth_trusted());
+    assert!(!netflix.email_protection_trusted());
+    assert_eq!(netflix.server_distrust_after(), None);
+    netflix.trust_anchor().unwrap();
+}
+
+#[test]
+fn certdata_malformed() {
+    let certdata = include_str!("certdata/certdata.txt");
+
+    // The object definitions must follow BEGINDATA.
+    assert_eq!(
+        parse_certdata(&certdata.replace("BEGINDATA", "")).unwrap_err(),
+        webpki::Error::BadCertdata
+    );
+
+    // MULTILINE_OCTAL values must be terminated.
+    let truncated = &certdata[..certdata.rfind("END").unwrap()];
+    assert_eq!(
+        parse_certdata(truncated).unwrap_err(),
+        webpki::Error::BadCertdata
+    );
+
+    assert_eq!(
+        parse_certdata(&certdata.replacen("\\060", "\\09", 1)).unwrap_err(),
+        webpki::Error::BadCertdata
+    );
+}
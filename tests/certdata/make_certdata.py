@@ -0,0 +1,146 @@
+"""
+Generates an NSS certdata.txt excerpt and a PEM bundle from existing test roots, for
+exercising `webpki::parse_certdata` and `webpki::pem_sections`.
+"""
+
+import argparse
+import base64
+import textwrap
+from pathlib import Path
+from typing import List, Optional
+
+from cryptography import x509
+from cryptography.hazmat.primitives.serialization import Encoding
+
+HERE = Path(__file__).parent
+
+CLOUDFLARE_CA = HERE.parent / "cloudflare_dns" / "ca.der"
+NETFLIX_CA = HERE.parent / "netflix" / "ca.der"
+CRL = HERE.parent / "crls" / "crl.valid.der"
+
+
+def write(path: Path, content: str, force: bool) -> None:
+    # Avoid churn from regenerating existing on-disk resources unless force is enabled.
+    if path.exists() and not force:
+        return None
+
+    with path.open("w") as f:
+        f.write(content)
+
+
+def octal(data: bytes) -> str:
+    escaped = "".join("\\%03o" % b for b in data)
+    return "\n".join(textwrap.wrap(escaped, 64)) + "\nEND\n"
+
+
+def der_integer(value: int) -> bytes:
+    body = value.to_bytes((value.bit_length() + 8) // 8, "big")
+    return bytes([0x02, len(body)]) + body
+
+
+def certificate_object(
+    label: str,
+    der: bytes,
+    server_distrust_after: Optional[str],
+) -> str:
+    cert = x509.load_der_x509_certificate(der)
+    distrust = (
+        "CKA_NSS_SERVER_DISTRUST_AFTER MULTILINE_OCTAL\n"
+        + octal(server_distrust_after.encode())
+        if server_distrust_after
+        else "CKA_NSS_SERVER_DISTRUST_AFTER CK_BBOOL CK_FALSE\n"
+    )
+    return (
+        "#\n# Certificate \"%s\"\n#\n" % label
+        + "CKA_CLASS CK_OBJECT_CLASS CKO_CERTIFICATE\n"
+        + "CKA_TOKEN CK_BBOOL CK_TRUE\n"
+        + "CKA_PRIVATE CK_BBOOL CK_FALSE\n"
+        + "CKA_MODIFIABLE CK_BBOOL CK_FALSE\n"
+        + 'CKA_LABEL UTF8 "%s"\n' % label
+        + "CKA_CERTIFICATE_TYPE CK_CERTIFICATE_TYPE CKC_X_509\n"
+        + "CKA_SUBJECT MULTILINE_OCTAL\n"
+        + octal(cert.subject.public_bytes())
+        + "CKA_ID UTF8 \"0\"\n"
+        + "CKA_ISSUER MULTILINE_OCTAL\n"
+        + octal(cert.issuer.public_bytes())
+        + "CKA_SERIAL_NUMBER MULTILINE_OCTAL\n"
+        + octal(der_integer(cert.serial_number))
+        + "CKA_VALUE MULTILINE_OCTAL\n"
+        + octal(der)
+        + "CKA_NSS_MOZILLA_CA_POLICY CK_BBOOL CK_TRUE\n"
+        + distrust
+        + "CKA_NSS_EMAIL_DISTRUST_AFTER CK_BBOOL CK_FALSE\n"
+        + "\n"
+    )
+
+
+def trust_object(label: str, der: bytes, server_auth: str, email: str) -> str:
+    cert = x509.load_der_x509_certificate(der)
+    return (
+        "# Trust for \"%s\"\n" % label
+        + "CKA_CLASS CK_OBJECT_CLASS CKO_NSS_TRUST\n"
+        + "CKA_TOKEN CK_BBOOL CK_TRUE\n"
+        + "CKA_PRIVATE CK_BBOOL CK_FALSE\n"
+        + "CKA_MODIFIABLE CK_BBOOL CK_FALSE\n"
+        + 'CKA_LABEL UTF8 "%s"\n' % label
+        + "CKA_ISSUER MULTILINE_OCTAL\n"
+        + octal(cert.issuer.public_bytes())
+        + "CKA_SERIAL_NUMBER MULTILINE_OCTAL\n"
+        + octal(der_integer(cert.serial_number))
+        + "CKA_TRUST_SERVER_AUTH CK_TRUST %s\n" % server_auth
+        + "CKA_TRUST_EMAIL_PROTECTION CK_TRUST %s\n" % email
+        + "CKA_TRUST_CODE_SIGNING CK_TRUST CKT_NSS_MUST_VERIFY_TRUST\n"
+        + "CKA_TRUST_STEP_UP_APPROVED CK_BBOOL CK_FALSE\n"
+        + "\n"
+    )
+
+
+def pem(label: str, der: bytes) -> str:
+    body = "\n".join(textwrap.wrap(base64.b64encode(der).decode(), 64))
+    return "-----BEGIN %s-----\n%s\n-----END %s-----\n" % (label, body, label)
+
+
+def certdata(force: bool) -> None:
+    cloudflare = CLOUDFLARE_CA.read_bytes()
+    netflix = NETFLIX_CA.read_bytes()
+
+    objects: List[str] = [
+        "# This Source Code Form is an excerpt in the format of NSS certdata.txt.\n",
+        "CVS_ID \"@(#) $RCSfile$ $Revision$ $Date$\"\n\n",
+        "BEGINDATA\n",
+        certificate_object("DigiCert Global Root CA", cloudflare, "200630000000Z"),
+        trust_object(
+            "DigiCert Global Root CA",
+            cloudflare,
+            "CKT_NSS_TRUSTED_DELEGATOR",
+            "CKT_NSS_MUST_VERIFY_TRUST",
+        ),
+        # The label exercises octal escapes in UTF8 strings.
+        certificate_object("Netflix \\303\\251 Root", netflix, None),
+    ]
+    write(HERE / "certdata.txt", "".join(objects), force)
+
+
+def bundle(force: bool) -> None:
+    sections: List[str] = [
+        "Roots for exercising PEM parsing.\n\n",
+        pem("CERTIFICATE", CLOUDFLARE_CA.read_bytes()),
+        "\n",
+        pem("X509 CRL", CRL.read_bytes()),
+        pem("CERTIFICATE", NETFLIX_CA.read_bytes()),
+    ]
+    write(HERE / "roots.pem", "".join(sections), force)
+
+
+if __name__ == "__main__":
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--force",
+        action="store_true",
+        default=False,
+        help="Overwrite existing test files",
+    )
+    args = parser.parse_args()
+
+    certdata(args.force)
+    bundle(args.force)
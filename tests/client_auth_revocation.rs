@@ -14,7 +14,7 @@
 
 #![cfg(feature = "ring")]
 
-use webpki::KeyUsage;
+use webpki::{KeyUsage, RevocationOptions};
 
 fn check_cert(
     ee: &[u8],
@@ -32,7 +32,7 @@ fn check_cert(
         intermediates,
         time,
         KeyUsage::client_auth(),
-        crls,
+        Some(RevocationOptions::new(crls)),
     )
 }
 
@@ -0,0 +1,68 @@
+// Each test crate uses only some of these helpers.
+#![allow(dead_code)]
+
+use webpki::{
+    BorrowedCertRevocationList, CertRevocationList, Error, KeyUsage, RevocationOptions, TrustAnchor,
+};
+
+/// The time the CRLs generated by the `make_testcerts.py` scripts are current at: their
+/// thisUpdate is 30 seconds before, and their nextUpdate 30 seconds after, unless the script says
+/// otherwise.
+pub const CRL_TIME: u64 = 0x1fed_f00d;
+
+/// Parse the DER-encoded CRL `der`.
+pub fn crl(der: &[u8]) -> BorrowedCertRevocationList<'_> {
+    BorrowedCertRevocationList::from_der(der).unwrap()
+}
+
+/// Parse the DER-encoded CRLs `crls`, and call `f` with them in the form
+/// [`webpki::RevocationOptions::new`] takes.
+pub fn with_crls<T>(crls: &[&[u8]], f: impl FnOnce(&[&dyn CertRevocationList]) -> T) -> T {
+    let crls = crls.iter().map(|der| crl(der)).collect::<Vec<_>>();
+    let crls = crls
+        .iter()
+        .map(|crl| -> &dyn CertRevocationList { crl })
+        .collect::<Vec<_>>();
+    f(&crls)
+}
+
+/// Verify the end-entity certificate `ee` for client authentication at [`CRL_TIME`], with `ca`
+/// as the trust anchor and a path through `intermediates`, checking the revocation status of the
+/// path against the DER-encoded CRLs `crls`.
+pub fn check_revocation(
+    ca: &[u8],
+    ee: &[u8],
+    intermediates: &[&[u8]],
+    crls: &[&[u8]],
+) -> Result<(), Error> {
+    with_crls(crls, |crls| {
+        check_revocation_with(
+            ca,
+            ee,
+            intermediates,
+            CRL_TIME,
+            RevocationOptions::new(crls),
+        )
+    })
+}
+
+/// Like [`check_revocation`], but at `secs`, with the revocation options `revocation`.
+pub fn check_revocation_with(
+    ca: &[u8],
+    ee: &[u8],
+    intermediates: &[&[u8]],
+    secs: u64,
+    revocation: RevocationOptions,
+) -> Result<(), Error> {
+    let anchors = [TrustAnchor::try_from_cert_der(ca).unwrap()];
+    webpki::EndEntityCert::try_from(ee)
+        .unwrap()
+        .verify_for_usage(
+            &[webpki::ECDSA_P256_SHA256],
+            &anchors[..],
+            intermediates,
+            webpki::Time::from_seconds_since_unix_epoch(secs),
+            KeyUsage::client_auth(),
+            Some(revocation),
+        )
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "ring")]
+
+mod common;
+
+use common::crl;
+use webpki::{CertRevocationList, Error, ExpirationPolicy, RevocationOptions};
+
+const CA: &[u8] = include_bytes!("crl_delta/ca.der");
+
+const REVOKED_IN_BASE: &[u8] = include_bytes!("crl_delta/ee.revoked_in_base.der");
+const HELD_IN_BASE: &[u8] = include_bytes!("crl_delta/ee.held_in_base.der");
+const REVOKED_IN_DELTA: &[u8] = include_bytes!("crl_delta/ee.revoked_in_delta.der");
+const NOT_REVOKED: &[u8] = include_bytes!("crl_delta/ee.not_revoked.der");
+
+/// Revokes `REVOKED_IN_BASE`, and puts `HELD_IN_BASE` on hold.
+const BASE_10: &[u8] = include_bytes!("crl_delta/base.10.crl.der");
+
+/// `BASE_10`, past its nextUpdate time.
+const BASE_10_EXPIRED: &[u8] = include_bytes!("crl_delta/base.10.expired.crl.der");
+
+/// Updates `BASE_10`: releases `HELD_IN_BASE`, and revokes `REVOKED_IN_DELTA`. It also has a
+/// removeFromCRL entry for `REVOKED_IN_BASE`, which wasn't on hold.
+const DELTA_11: &[u8] = include_bytes!("crl_delta/delta.11.crl.der");
+
+/// `DELTA_11`, past its nextUpdate time.
+const DELTA_11_EXPIRED: &[u8] = include_bytes!("crl_delta/delta.11.expired.crl.der");
+
+/// Updates a complete CRL numbered 12, and revokes `NOT_REVOKED`.
+const DELTA_13_BASE_12: &[u8] = include_bytes!("crl_delta/delta.13.base.12.crl.der");
+
+/// Claims to update `BASE_10`, and revokes `NOT_REVOKED`, but was signed by another key.
+const DELTA_OTHER_KEY: &[u8] = include_bytes!("crl_delta/delta.other_key.crl.der");
+
+fn check(ee: &[u8], crls: &[&[u8]]) -> Result<(), Error> {
+    common::check_revocation(CA, ee, &[], crls)
+}
+
+fn check_with_expiration_policy(
+    ee: &[u8],
+    crls: &[&[u8]],
+    policy: ExpirationPolicy,
+) -> Result<(), Error> {
+    common::with_crls(crls, |crls| {
+        let revocation = RevocationOptions::new(crls).with_expiration_policy(policy);
+        common::check_revocation_with(CA, ee, &[], common::CRL_TIME, revocation)
+    })
+}
+
+#[test]
+fn delta_crl_numbers() {
+    let (base, delta) = (crl(BASE_10), crl(DELTA_11));
+    assert_eq!(base.base_crl_number(), None);
+    assert_eq!(delta.crl_number(), Some(&[11][..]));
+    assert_eq!(delta.base_crl_number(), Some(&[10][..]));
+
+    #[cfg(feature = "alloc")]
+    {
+        let delta = delta.to_owned().unwrap();
+        assert_eq!(delta.base_crl_number(), Some(&[10][..]));
+    }
+}
+
+#[test]
+fn is_delta_for() {
+    let base = crl(BASE_10);
+    assert!(crl(DELTA_11).is_delta_for(&base));
+    assert!(!base.is_delta_for(&crl(DELTA_11)));
+    assert!(!base.is_delta_for(&base));
+
+    // The delta CRL was built from a complete CRL newer than `base`.
+    assert!(!crl(DELTA_13_BASE_12).is_delta_for(&base));
+
+    // Delta CRLs and complete CRLs don't supersede each other.
+    assert!(!crl(DELTA_11).supersedes(&base));
+}
+
+#[test]
+fn base_crl_alone() {
+    assert_eq!(check(REVOKED_IN_BASE, &[BASE_10]), Err(Error::CertRevoked));
+    assert_eq!(check(HELD_IN_BASE, &[BASE_10]), Err(Error::CertRevoked));
+    assert_eq!(check(REVOKED_IN_DELTA, &[BASE_10]), Ok(()));
+    assert_eq!(check(NOT_REVOKED, &[BASE_10]), Ok(()));
+}
+
+#[test]
+fn base_and_delta_crls() {
+    for crls in [[BASE_10, DELTA_11], [DELTA_11, BASE_10]] {
+        assert_eq!(check(REVOKED_IN_BASE, &crls), Err(Error::CertRevoked));
+        assert_eq!(check(HELD_IN_BASE, &crls), Ok(()));
+        assert_eq!(check(REVOKED_IN_DELTA, &crls), Err(Error::CertRevoked));
+        assert_eq!(check(NOT_REVOKED, &crls), Ok(()));
+    }
+}
+
+#[test]
+fn delta_crl_alone_is_not_used() {
+    assert_eq!(check(REVOKED_IN_DELTA, &[DELTA_11]), Ok(()));
+}
+
+#[test]
+fn delta_crl_for_other_base_is_not_used() {
+    assert_eq!(check(NOT_REVOKED, &[BASE_10, DELTA_13_BASE_12]), Ok(()));
+}
+
+#[test]
+fn delta_crl_with_bad_signature_is_not_used() {
+    let crls = [BASE_10, DELTA_OTHER_KEY, DELTA_11];
+    assert_eq!(check(NOT_REVOKED, &crls), Ok(()));
+    assert_eq!(check(REVOKED_IN_DELTA, &crls), Err(Error::CertRevoked));
+}
+
+#[test]
+fn delta_crl_only_releases_held_certificates() {
+    let crls = [BASE_10, DELTA_11];
+    assert_eq!(check(HELD_IN_BASE, &crls), Ok(()));
+    // The removeFromCRL entry doesn't undo a revocation for another reason than certificateHold.
+    assert_eq!(check(REVOKED_IN_BASE, &crls), Err(Error::CertRevoked));
+}
+
+#[test]
+fn delta_crl_freshness_enforced() {
+    let check =
+        |ee, crls: &[&[u8]]| check_with_expiration_policy(ee, crls, ExpirationPolicy::Enforce);
+
+    // A current delta CRL brings its expired complete CRL up to date.
+    let crls = [BASE_10_EXPIRED, DELTA_11];
+    assert_eq!(check(NOT_REVOKED, &crls), Ok(()));
+    assert_eq!(check(HELD_IN_BASE, &crls), Ok(()));
+    assert_eq!(check(REVOKED_IN_BASE, &crls), Err(Error::CertRevoked));
+    assert_eq!(check(REVOKED_IN_DELTA, &crls), Err(Error::CertRevoked));
+
+    assert_eq!(
+        check(NOT_REVOKED, &[BASE_10_EXPIRED]),
+        Err(Error::CrlExpired)
+    );
+
+    // An expired delta CRL isn't made current by its complete CRL.
+    assert_eq!(
+        check(NOT_REVOKED, &[BASE_10, DELTA_11_EXPIRED]),
+        Err(Error::CrlExpired)
+    );
+}
+
+#[test]
+fn delta_crl_freshness_ignored() {
+    let check =
+        |ee, crls: &[&[u8]]| check_with_expiration_policy(ee, crls, ExpirationPolicy::Ignore);
+
+    // A current delta CRL brings its expired complete CRL up to date.
+    let crls = [BASE_10_EXPIRED, DELTA_11];
+    assert_eq!(check(HELD_IN_BASE, &crls), Ok(()));
+    assert_eq!(check(REVOKED_IN_BASE, &crls), Err(Error::CertRevoked));
+    assert_eq!(check(REVOKED_IN_DELTA, &crls), Err(Error::CertRevoked));
+
+    // An expired delta CRL is skipped, leaving the complete CRL.
+    let crls = [BASE_10, DELTA_11_EXPIRED];
+    assert_eq!(check(HELD_IN_BASE, &crls), Err(Error::CertRevoked));
+    assert_eq!(check(REVOKED_IN_DELTA, &crls), Ok(()));
+}
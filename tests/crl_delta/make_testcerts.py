@@ -0,0 +1,116 @@
+"""
+Generates a CA, end-entity certificates it issued, a complete CRL and delta CRLs updating it,
+to test revocation checking with delta CRLs.
+"""
+
+import datetime
+import functools
+import sys
+from pathlib import Path
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.x509.oid import ExtendedKeyUsageOID
+
+HERE = Path(__file__).parent
+sys.path.insert(0, str(HERE.parent))
+
+import testcerts  # noqa: E402
+from testcerts import NOT_AFTER, NOT_BEFORE, crl, revoked_cert, write_der  # noqa: E402
+
+name = functools.partial(testcerts.name, organization="delta crl")
+
+
+def generate(force: bool) -> None:
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+    ca = (
+        x509.CertificateBuilder()
+        .subject_name(name("CA"))
+        .issuer_name(name("CA"))
+        .public_key(ca_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .add_extension(
+            x509.SubjectKeyIdentifier.from_public_key(ca_key.public_key()),
+            critical=False,
+        )
+        .sign(ca_key, hashes.SHA256())
+    )
+    write_der(HERE / "ca.der", ca.public_bytes(Encoding.DER), force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1())
+    serials = {}
+    for ee_name in ["revoked_in_base", "held_in_base", "revoked_in_delta", "not_revoked"]:
+        ee = (
+            x509.CertificateBuilder()
+            .subject_name(name(f"{ee_name}.example.com"))
+            .issuer_name(ca.subject)
+            .public_key(ee_key.public_key())
+            .serial_number(x509.random_serial_number())
+            .not_valid_before(NOT_BEFORE)
+            .not_valid_after(NOT_AFTER)
+            .add_extension(
+                x509.BasicConstraints(ca=False, path_length=None), critical=True
+            )
+            .add_extension(
+                x509.AuthorityKeyIdentifier.from_issuer_public_key(ca_key.public_key()),
+                critical=False,
+            )
+            .add_extension(
+                x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]), critical=False
+            )
+            .sign(ca_key, hashes.SHA256())
+        )
+        write_der(HERE / f"ee.{ee_name}.der", ee.public_bytes(Encoding.DER), force)
+        serials[ee_name] = ee.serial_number
+
+    base_revoked = [
+        revoked_cert(serials["revoked_in_base"], x509.ReasonFlags.key_compromise),
+        revoked_cert(serials["held_in_base"], x509.ReasonFlags.certificate_hold),
+    ]
+    # Releasing the held certificate, and trying to release the revoked one, which isn't on
+    # hold.
+    delta_revoked = [
+        revoked_cert(serials["held_in_base"], x509.ReasonFlags.remove_from_crl),
+        revoked_cert(serials["revoked_in_base"], x509.ReasonFlags.remove_from_crl),
+        revoked_cert(serials["revoked_in_delta"], x509.ReasonFlags.key_compromise),
+    ]
+    not_revoked = [revoked_cert(serials["not_revoked"], x509.ReasonFlags.key_compromise)]
+    expired = NOT_BEFORE + datetime.timedelta(seconds=10)
+
+    other_key = ec.generate_private_key(ec.SECP256R1())
+    for (file_name, signing_key, number, base_number, revoked, next_update) in [
+        # The complete CRL.
+        ("base.10.crl.der", ca_key, 10, None, base_revoked, NOT_AFTER),
+        # The same complete CRL, past its nextUpdate time.
+        ("base.10.expired.crl.der", ca_key, 10, None, base_revoked, expired),
+        # A delta CRL updating it.
+        ("delta.11.crl.der", ca_key, 11, 10, delta_revoked, NOT_AFTER),
+        # The same delta CRL, past its nextUpdate time.
+        ("delta.11.expired.crl.der", ca_key, 11, 10, delta_revoked, expired),
+        # A delta CRL updating a newer complete CRL than the one we have.
+        ("delta.13.base.12.crl.der", ca_key, 13, 12, not_revoked, NOT_AFTER),
+        # A delta CRL claiming to be from the CA, but signed by a different key.
+        ("delta.other_key.crl.der", other_key, 12, 10, not_revoked, NOT_AFTER),
+    ]:
+        write_der(
+            HERE / file_name,
+            crl(
+                ca.subject,
+                signing_key,
+                revoked,
+                number=number,
+                authority_key=ca_key.public_key(),
+                base_number=base_number,
+                next_update=next_update,
+            ),
+            force,
+        )
+
+
+if __name__ == "__main__":
+    testcerts.main(generate)
@@ -0,0 +1,93 @@
+#![cfg(feature = "ring")]
+
+mod common;
+
+use core::time::Duration;
+
+use common::CRL_TIME;
+use webpki::{CertRevocationList, Error, ExpirationPolicy, RevocationOptions};
+
+const EE: &[u8] = include_bytes!("crl_freshness/ee.der");
+const CA: &[u8] = include_bytes!("crl_freshness/ca.der");
+const CRL: &[u8] = include_bytes!("crl_freshness/crl.der");
+
+// The certificates are valid for a year either side of `CRL_TIME`.
+
+fn check(
+    secs: u64,
+    configure: impl FnOnce(RevocationOptions) -> RevocationOptions,
+) -> Result<(), Error> {
+    let crl = common::crl(CRL);
+    common::check_revocation_with(
+        CA,
+        EE,
+        &[],
+        secs,
+        configure(RevocationOptions::new(&[&crl])),
+    )
+}
+
+fn enforced(revocation: RevocationOptions) -> RevocationOptions {
+    revocation
+}
+
+fn ignored(revocation: RevocationOptions) -> RevocationOptions {
+    revocation.with_expiration_policy(ExpirationPolicy::Ignore)
+}
+
+fn grace_period(revocation: RevocationOptions) -> RevocationOptions {
+    revocation.with_grace_period(Duration::from_secs(3600))
+}
+
+#[test]
+fn crl_update_times() {
+    let crl = common::crl(CRL);
+    let this_update = webpki::Time::from_seconds_since_unix_epoch(CRL_TIME - 30);
+    let next_update = webpki::Time::from_seconds_since_unix_epoch(CRL_TIME + 30);
+    assert_eq!(crl.this_update(), this_update);
+    assert_eq!(crl.next_update(), next_update);
+
+    #[cfg(feature = "alloc")]
+    {
+        let crl = crl.to_owned().unwrap();
+        assert_eq!(crl.this_update(), this_update);
+        assert_eq!(crl.next_update(), next_update);
+    }
+}
+
+#[test]
+fn fresh_crl() {
+    for configure in [enforced, ignored] {
+        assert_eq!(check(CRL_TIME, configure), Ok(()));
+        assert_eq!(check(CRL_TIME - 30, configure), Ok(()));
+        assert_eq!(check(CRL_TIME + 30, configure), Ok(()));
+    }
+}
+
+#[test]
+fn expired_crl() {
+    // Expiration is enforced by default.
+    assert_eq!(check(CRL_TIME + 31, enforced), Err(Error::CrlExpired));
+
+    // An ignored CRL leaves the certificate's revocation status unknown, which is allowed by
+    // default.
+    assert_eq!(check(CRL_TIME + 31, ignored), Ok(()));
+}
+
+#[test]
+fn expired_crl_within_grace_period() {
+    assert_eq!(check(CRL_TIME + 30 + 3600, grace_period), Ok(()));
+    assert_eq!(
+        check(CRL_TIME + 30 + 3601, grace_period),
+        Err(Error::CrlExpired)
+    );
+}
+
+#[test]
+fn crl_not_valid_yet() {
+    assert_eq!(
+        check(CRL_TIME - 31, grace_period),
+        Err(Error::CrlNotValidYet)
+    );
+    assert_eq!(check(CRL_TIME - 31, ignored), Ok(()));
+}
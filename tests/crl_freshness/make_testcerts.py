@@ -0,0 +1,85 @@
+"""
+Generates a CA, an end-entity certificate it issued, and a CRL from the CA whose validity
+period is much shorter than those of the certificates, to test CRL freshness checks.
+"""
+
+import datetime
+import functools
+import sys
+from pathlib import Path
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.x509.oid import ExtendedKeyUsageOID
+
+HERE = Path(__file__).parent
+sys.path.insert(0, str(HERE.parent))
+
+import testcerts  # noqa: E402
+from testcerts import crl, write_der  # noqa: E402
+
+# The CRL is valid for a minute either side of this time.
+CRL_TIME: int = 0x1FEDF00D
+THIS_UPDATE: datetime.datetime = datetime.datetime.utcfromtimestamp(CRL_TIME - 30)
+NEXT_UPDATE: datetime.datetime = datetime.datetime.utcfromtimestamp(CRL_TIME + 30)
+
+# The certificates are valid for a year either side.
+NOT_BEFORE: datetime.datetime = datetime.datetime.utcfromtimestamp(CRL_TIME - 365 * 86400)
+NOT_AFTER: datetime.datetime = datetime.datetime.utcfromtimestamp(CRL_TIME + 365 * 86400)
+
+name = functools.partial(testcerts.name, organization="crl freshness")
+
+
+def generate(force: bool) -> None:
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+    ca = (
+        x509.CertificateBuilder()
+        .subject_name(name("CA"))
+        .issuer_name(name("CA"))
+        .public_key(ca_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .add_extension(
+            x509.SubjectKeyIdentifier.from_public_key(ca_key.public_key()),
+            critical=False,
+        )
+        .sign(ca_key, hashes.SHA256())
+    )
+    write_der(HERE / "ca.der", ca.public_bytes(Encoding.DER), force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1())
+    ee = (
+        x509.CertificateBuilder()
+        .subject_name(name("ee.example.com"))
+        .issuer_name(ca.subject)
+        .public_key(ee_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=False, path_length=None), critical=True)
+        .add_extension(
+            x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]), critical=False
+        )
+        .sign(ca_key, hashes.SHA256())
+    )
+    write_der(HERE / "ee.der", ee.public_bytes(Encoding.DER), force)
+
+    write_der(
+        HERE / "crl.der",
+        crl(
+            ca.subject,
+            ca_key,
+            authority_key=ca_key.public_key(),
+            this_update=THIS_UPDATE,
+            next_update=NEXT_UPDATE,
+        ),
+        force,
+    )
+
+
+if __name__ == "__main__":
+    testcerts.main(generate)
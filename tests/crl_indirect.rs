@@ -0,0 +1,174 @@
+#![cfg(feature = "ring")]
+
+mod common;
+
+use webpki::{CertRevocationList, Error, KeyUsage, RevocationOptions, TrustAnchor};
+
+const CA: &[u8] = include_bytes!("crl_indirect/ca.der");
+
+/// Certified by `CA` to issue CRLs on its behalf.
+const CRL_ISSUER: &[u8] = include_bytes!("crl_indirect/crl_issuer.der");
+
+/// Like `CRL_ISSUER`, but without cRLSign in its key usage.
+const CRL_ISSUER_NO_CRL_SIGN: &[u8] = include_bytes!("crl_indirect/crl_issuer.no_crl_sign.der");
+
+/// Has the name and key of `CRL_ISSUER`, but is self-signed.
+const CRL_ISSUER_SELF_SIGNED: &[u8] = include_bytes!("crl_indirect/crl_issuer.self_signed.der");
+
+// Each of the end-entity certificates has a distribution point naming `CRL_ISSUER` as its CRL
+// issuer, except for `NO_CRL_ISSUER`.
+const REVOKED: &[u8] = include_bytes!("crl_indirect/ee.revoked.der");
+const REVOKED_CARRIED_FORWARD: &[u8] =
+    include_bytes!("crl_indirect/ee.revoked_carried_forward.der");
+const REVOKED_BY_OTHER_CA: &[u8] = include_bytes!("crl_indirect/ee.revoked_by_other_ca.der");
+const NO_CRL_ISSUER: &[u8] = include_bytes!("crl_indirect/ee.no_crl_issuer.der");
+
+/// Revokes `REVOKED` in an entry naming `CA` as its certificate issuer, `REVOKED_CARRIED_FORWARD`
+/// and `NO_CRL_ISSUER` in the entries following it, and the serial number of
+/// `REVOKED_BY_OTHER_CA` in an entry naming another certificate issuer.
+const INDIRECT: &[u8] = include_bytes!("crl_indirect/indirect.crl.der");
+
+/// Has the same entries as `INDIRECT`, but its issuing distribution point doesn't claim it's an
+/// indirect CRL.
+const NOT_INDIRECT: &[u8] = include_bytes!("crl_indirect/not_indirect.crl.der");
+
+/// Has the same entries as `INDIRECT`, but is issued by `CA` and has no issuing distribution
+/// point, so is a direct CRL.
+const DIRECT: &[u8] = include_bytes!("crl_indirect/direct.crl.der");
+
+fn check(ee: &[u8], crl: &[u8], intermediates: &[&[u8]]) -> Result<(), Error> {
+    common::check_revocation(CA, ee, intermediates, &[crl])
+}
+
+#[test]
+fn indirect_crl_entries() {
+    let intermediates = [CRL_ISSUER];
+    assert_eq!(
+        check(REVOKED, INDIRECT, &intermediates),
+        Err(Error::CertRevoked)
+    );
+    assert_eq!(
+        check(REVOKED_CARRIED_FORWARD, INDIRECT, &intermediates),
+        Err(Error::CertRevoked)
+    );
+    assert_eq!(check(REVOKED_BY_OTHER_CA, INDIRECT, &intermediates), Ok(()));
+}
+
+#[test]
+fn indirect_crl_for_distribution_point_without_crl_issuer_is_not_used() {
+    assert_eq!(check(NO_CRL_ISSUER, INDIRECT, &[CRL_ISSUER]), Ok(()));
+}
+
+#[test]
+fn crl_not_claiming_to_be_indirect_is_not_used() {
+    assert_eq!(check(REVOKED, NOT_INDIRECT, &[CRL_ISSUER]), Ok(()));
+}
+
+#[test]
+fn direct_crl_with_certificate_issuer_is_malformed() {
+    // The entry for `NO_CRL_ISSUER` follows one naming another certificate issuer.
+    assert_eq!(
+        check(NO_CRL_ISSUER, DIRECT, &[]),
+        Err(Error::MalformedExtensions)
+    );
+
+    for der in [DIRECT, NOT_INDIRECT] {
+        let crl = common::crl(der);
+        let entries = (&crl).into_iter().collect::<Vec<_>>();
+        // The first entry has no certificate issuer.
+        assert!(entries[0].is_ok());
+        assert!(matches!(entries[1], Err(Error::MalformedExtensions)));
+
+        #[cfg(feature = "alloc")]
+        assert!(matches!(crl.to_owned(), Err(Error::MalformedExtensions)));
+    }
+}
+
+#[test]
+fn indirect_crl_issuer_must_be_certified_for_crl_signing() {
+    assert_eq!(check(REVOKED, INDIRECT, &[]), Ok(()));
+    assert_eq!(check(REVOKED, INDIRECT, &[CRL_ISSUER_NO_CRL_SIGN]), Ok(()));
+    assert_eq!(check(REVOKED, INDIRECT, &[CRL_ISSUER_SELF_SIGNED]), Ok(()));
+    assert_eq!(
+        check(
+            REVOKED,
+            INDIRECT,
+            &[CRL_ISSUER_NO_CRL_SIGN, CRL_ISSUER_SELF_SIGNED, CRL_ISSUER]
+        ),
+        Err(Error::CertRevoked)
+    );
+}
+
+#[test]
+fn indirect_crl_issuer_as_trust_anchor_is_not_used() {
+    // Trusting the CRL issuer for certificates doesn't make it an issuer of CRLs for `CA`.
+    let crl = common::crl(INDIRECT);
+    let crls: &[&dyn CertRevocationList] = &[&crl];
+    let anchors = [
+        TrustAnchor::try_from_cert_der(CA).unwrap(),
+        TrustAnchor::try_from_cert_der(CRL_ISSUER_SELF_SIGNED).unwrap(),
+    ];
+    let result = webpki::EndEntityCert::try_from(REVOKED)
+        .unwrap()
+        .verify_for_usage(
+            &[webpki::ECDSA_P256_SHA256],
+            &anchors[..],
+            &[],
+            webpki::Time::from_seconds_since_unix_epoch(common::CRL_TIME),
+            KeyUsage::client_auth(),
+            Some(RevocationOptions::new(crls)),
+        );
+    assert_eq!(result, Ok(()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn indirect_crl_issuer_from_intermediate_store() {
+    let crl = common::crl(INDIRECT);
+    let crls: &[&dyn CertRevocationList] = &[&crl];
+    let mut store = webpki::IntermediateStore::new();
+    store.add(CRL_ISSUER).unwrap();
+
+    let anchors = [TrustAnchor::try_from_cert_der(CA).unwrap()];
+    let result = webpki::EndEntityCert::try_from(REVOKED)
+        .unwrap()
+        .verify_for_usage_with_options(
+            &[webpki::ECDSA_P256_SHA256],
+            &anchors[..],
+            &[],
+            webpki::Time::from_seconds_since_unix_epoch(common::CRL_TIME),
+            KeyUsage::client_auth(),
+            Some(RevocationOptions::new(crls)),
+            &webpki::VerifyOptions::new().with_intermediate_store(&store),
+        );
+    assert_eq!(
+        result.map(|_| ()).map_err(Error::from),
+        Err(Error::CertRevoked)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_indirect_crl_entries() {
+    let crl = common::crl(INDIRECT);
+    let owned = crl.to_owned().unwrap();
+
+    // Every entry is attributed to the same certificate issuer in both representations.
+    for entry in &crl {
+        let entry = entry.unwrap();
+        let issuer = entry.certificate_issuer.unwrap_or(crl.issuer());
+        let found = owned.find_serial(issuer, entry.serial_number).unwrap();
+        assert_eq!(
+            found.map(|found| found.serial_number),
+            Some(entry.serial_number)
+        );
+    }
+
+    // The first entry has no certificate issuer, so is from the CRL issuer.
+    let first = (&crl).into_iter().next().unwrap().unwrap();
+    assert_eq!(first.certificate_issuer, None);
+    assert!(owned
+        .find_serial(crl.issuer(), first.serial_number)
+        .unwrap()
+        .is_some());
+}
@@ -0,0 +1,169 @@
+"""
+Generates a CA, a separate CRL issuer certified by the CA, end-entity certificates from the CA
+whose distribution points name that CRL issuer, and indirect CRLs from it revoking them, to test
+revocation checking with indirect CRLs.
+"""
+
+import functools
+import sys
+from pathlib import Path
+from typing import Optional
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.x509.oid import ExtendedKeyUsageOID
+
+HERE = Path(__file__).parent
+sys.path.insert(0, str(HERE.parent))
+
+import testcerts  # noqa: E402
+from testcerts import (  # noqa: E402
+    NOT_AFTER,
+    NOT_BEFORE,
+    crl,
+    issuing_distribution_point,
+    revoked_cert,
+    write_der,
+)
+
+CRL_URI = "http://example.com/indirect.crl"
+
+name = functools.partial(testcerts.name, organization="indirect crl")
+
+
+def self_signed(subject: x509.Name, key: ec.EllipticCurvePrivateKey) -> x509.Certificate:
+    return (
+        x509.CertificateBuilder()
+        .subject_name(subject)
+        .issuer_name(subject)
+        .public_key(key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .sign(key, hashes.SHA256())
+    )
+
+
+def crl_issuer_cert(
+    subject: x509.Name,
+    key: ec.EllipticCurvePrivateKey,
+    issuer: x509.Certificate,
+    issuer_key: ec.EllipticCurvePrivateKey,
+    crl_sign: bool,
+) -> x509.Certificate:
+    return (
+        x509.CertificateBuilder()
+        .subject_name(subject)
+        .issuer_name(issuer.subject)
+        .public_key(key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=False, path_length=None), critical=True)
+        .add_extension(
+            x509.KeyUsage(
+                digital_signature=not crl_sign,
+                content_commitment=False,
+                key_encipherment=False,
+                data_encipherment=False,
+                key_agreement=False,
+                key_cert_sign=False,
+                crl_sign=crl_sign,
+                encipher_only=False,
+                decipher_only=False,
+            ),
+            critical=True,
+        )
+        .sign(issuer_key, hashes.SHA256())
+    )
+
+
+def generate(force: bool) -> None:
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+    ca = self_signed(name("CA"), ca_key)
+    write_der(HERE / "ca.der", ca.public_bytes(Encoding.DER), force)
+
+    # The CRL issuer is certified by the CA for signing CRLs. The same key and name are also
+    # in a certificate without cRLSign, and in a self-signed certificate.
+    crl_issuer_key = ec.generate_private_key(ec.SECP256R1())
+    crl_issuer = crl_issuer_cert(name("CRL Issuer"), crl_issuer_key, ca, ca_key, True)
+    write_der(HERE / "crl_issuer.der", crl_issuer.public_bytes(Encoding.DER), force)
+    no_crl_sign = crl_issuer_cert(name("CRL Issuer"), crl_issuer_key, ca, ca_key, False)
+    write_der(HERE / "crl_issuer.no_crl_sign.der", no_crl_sign.public_bytes(Encoding.DER), force)
+    self_signed_crl_issuer = self_signed(name("CRL Issuer"), crl_issuer_key)
+    write_der(
+        HERE / "crl_issuer.self_signed.der",
+        self_signed_crl_issuer.public_bytes(Encoding.DER),
+        force,
+    )
+
+    ee_key = ec.generate_private_key(ec.SECP256R1())
+    serials = {}
+    for ee_name, dp_crl_issuer in [
+        ("revoked", [x509.DirectoryName(crl_issuer.subject)]),
+        ("revoked_carried_forward", [x509.DirectoryName(crl_issuer.subject)]),
+        ("revoked_by_other_ca", [x509.DirectoryName(crl_issuer.subject)]),
+        # Revoked, but its distribution point doesn't name the CRL issuer.
+        ("no_crl_issuer", None),
+    ]:
+        ee = (
+            x509.CertificateBuilder()
+            .subject_name(name(f"{ee_name}.example.com"))
+            .issuer_name(ca.subject)
+            .public_key(ee_key.public_key())
+            .serial_number(x509.random_serial_number())
+            .not_valid_before(NOT_BEFORE)
+            .not_valid_after(NOT_AFTER)
+            .add_extension(
+                x509.BasicConstraints(ca=False, path_length=None), critical=True
+            )
+            .add_extension(
+                x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]), critical=False
+            )
+            .add_extension(
+                x509.CRLDistributionPoints(
+                    [
+                        x509.DistributionPoint(
+                            full_name=[x509.UniformResourceIdentifier(CRL_URI)],
+                            relative_name=None,
+                            reasons=None,
+                            crl_issuer=dp_crl_issuer,
+                        )
+                    ]
+                ),
+                critical=False,
+            )
+            .sign(ca_key, hashes.SHA256())
+        )
+        write_der(HERE / f"ee.{ee_name}.der", ee.public_bytes(Encoding.DER), force)
+        serials[ee_name] = ee.serial_number
+
+    # The first entries are from the CRL issuer itself, then entries from another CA using the
+    # serial number of one of our certificates, then entries from the CA.
+    revoked = [
+        revoked_cert(serials["revoked_by_other_ca"] + 1),
+        revoked_cert(serials["revoked_by_other_ca"], certificate_issuer=name("Other CA")),
+        revoked_cert(serials["revoked"], certificate_issuer=ca.subject),
+        revoked_cert(serials["revoked_carried_forward"]),
+        revoked_cert(serials["no_crl_issuer"]),
+    ]
+    for (file_name, issuer, issuer_key, indirect) in [
+        ("indirect.crl.der", crl_issuer.subject, crl_issuer_key, True),
+        # Names certificate issuers in its entries, but doesn't claim to be indirect.
+        ("not_indirect.crl.der", crl_issuer.subject, crl_issuer_key, False),
+        # From the CA itself, with no issuing distribution point, but naming certificate issuers
+        # in its entries.
+        ("direct.crl.der", ca.subject, ca_key, None),
+    ]:
+        # Without an indirectCRL indicator, there's no issuing distribution point.
+        idp: Optional[x509.IssuingDistributionPoint] = None
+        if indirect is not None:
+            idp = issuing_distribution_point(CRL_URI, indirect_crl=indirect)
+        write_der(HERE / file_name, crl(issuer, issuer_key, revoked, idp=idp), force)
+
+
+if __name__ == "__main__":
+    testcerts.main(generate)
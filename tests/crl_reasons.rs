@@ -0,0 +1,85 @@
+#![cfg(feature = "ring")]
+
+mod common;
+
+use webpki::Error;
+
+const CA: &[u8] = include_bytes!("crl_reasons/ca.der");
+
+const REVOKED_FOR_KEY_COMPROMISE: &[u8] =
+    include_bytes!("crl_reasons/ee.revoked_for_key_compromise.der");
+const REVOKED_FOR_SUPERSEDED: &[u8] = include_bytes!("crl_reasons/ee.revoked_for_superseded.der");
+const NOT_REVOKED: &[u8] = include_bytes!("crl_reasons/ee.not_revoked.der");
+
+/// Its distribution point only covers the keyCompromise and cACompromise reasons.
+const DP_KEY_COMPROMISE: &[u8] = include_bytes!("crl_reasons/ee.dp_key_compromise.der");
+
+/// Covers the keyCompromise and cACompromise reasons, and revokes `REVOKED_FOR_KEY_COMPROMISE`
+/// and `DP_KEY_COMPROMISE`.
+const KEY_COMPROMISE: &[u8] = include_bytes!("crl_reasons/key_compromise.crl.der");
+
+/// Covers every other reason, and revokes `REVOKED_FOR_SUPERSEDED` and `DP_KEY_COMPROMISE`.
+const OTHER_REASONS: &[u8] = include_bytes!("crl_reasons/other_reasons.crl.der");
+
+/// The newest CRL, covering every reason, and revoking `REVOKED_FOR_KEY_COMPROMISE` and
+/// `DP_KEY_COMPROMISE`.
+const ALL_REASONS: &[u8] = include_bytes!("crl_reasons/all_reasons.crl.der");
+
+fn check(ee: &[u8], crls: &[&[u8]]) -> Result<(), Error> {
+    common::check_revocation(CA, ee, &[], crls)
+}
+
+#[test]
+fn crls_partitioned_by_reason() {
+    for crls in [
+        [KEY_COMPROMISE, OTHER_REASONS],
+        [OTHER_REASONS, KEY_COMPROMISE],
+    ] {
+        assert_eq!(
+            check(REVOKED_FOR_KEY_COMPROMISE, &crls),
+            Err(Error::CertRevoked)
+        );
+        assert_eq!(
+            check(REVOKED_FOR_SUPERSEDED, &crls),
+            Err(Error::CertRevoked)
+        );
+        assert_eq!(check(NOT_REVOKED, &crls), Ok(()));
+    }
+}
+
+#[test]
+fn crls_covering_some_reasons() {
+    assert_eq!(
+        check(REVOKED_FOR_KEY_COMPROMISE, &[KEY_COMPROMISE]),
+        Err(Error::CertRevoked)
+    );
+
+    // The revocation status of the certificate is unknown for the other reasons.
+    assert_eq!(check(REVOKED_FOR_SUPERSEDED, &[KEY_COMPROMISE]), Ok(()));
+}
+
+#[test]
+fn crl_covering_every_reason_is_enough() {
+    // The partitioned CRLs cover no reasons the newest CRL doesn't, so aren't consulted.
+    let crls = [KEY_COMPROMISE, OTHER_REASONS, ALL_REASONS];
+    assert_eq!(
+        check(REVOKED_FOR_KEY_COMPROMISE, &crls),
+        Err(Error::CertRevoked)
+    );
+    assert_eq!(check(REVOKED_FOR_SUPERSEDED, &crls), Ok(()));
+}
+
+#[test]
+fn distribution_point_with_reasons() {
+    assert_eq!(
+        check(DP_KEY_COMPROMISE, &[KEY_COMPROMISE]),
+        Err(Error::CertRevoked)
+    );
+    assert_eq!(
+        check(DP_KEY_COMPROMISE, &[ALL_REASONS]),
+        Err(Error::CertRevoked)
+    );
+
+    // None of the reasons the CRL covers are covered by the distribution point.
+    assert_eq!(check(DP_KEY_COMPROMISE, &[OTHER_REASONS]), Ok(()));
+}
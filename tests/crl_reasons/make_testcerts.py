@@ -0,0 +1,141 @@
+"""
+Generates a CA, end-entity certificates it issued, and CRLs partitioned by revocation reason,
+to test revocation checking with CRLs that only cover some reasons.
+"""
+
+import functools
+import sys
+from pathlib import Path
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.x509.oid import ExtendedKeyUsageOID
+
+HERE = Path(__file__).parent
+sys.path.insert(0, str(HERE.parent))
+
+import testcerts  # noqa: E402
+from testcerts import (  # noqa: E402
+    NOT_AFTER,
+    NOT_BEFORE,
+    crl,
+    issuing_distribution_point,
+    revoked_cert,
+    write_der,
+)
+
+CRL_URI = "http://example.com/ca.crl"
+
+KEY_COMPROMISE_REASONS = frozenset(
+    [x509.ReasonFlags.key_compromise, x509.ReasonFlags.ca_compromise]
+)
+OTHER_REASONS = frozenset(
+    [
+        x509.ReasonFlags.affiliation_changed,
+        x509.ReasonFlags.superseded,
+        x509.ReasonFlags.cessation_of_operation,
+        x509.ReasonFlags.certificate_hold,
+        x509.ReasonFlags.privilege_withdrawn,
+        x509.ReasonFlags.aa_compromise,
+    ]
+)
+
+
+name = functools.partial(testcerts.name, organization="crl reasons")
+
+
+def generate(force: bool) -> None:
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+    ca = (
+        x509.CertificateBuilder()
+        .subject_name(name("CA"))
+        .issuer_name(name("CA"))
+        .public_key(ca_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .sign(ca_key, hashes.SHA256())
+    )
+    write_der(HERE / "ca.der", ca.public_bytes(Encoding.DER), force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1())
+    serials = {}
+    for ee_name, dp_reasons in [
+        ("revoked_for_key_compromise", None),
+        ("revoked_for_superseded", None),
+        ("not_revoked", None),
+        # Its distribution point only covers key compromise.
+        ("dp_key_compromise", KEY_COMPROMISE_REASONS),
+    ]:
+        ee = (
+            x509.CertificateBuilder()
+            .subject_name(name(f"{ee_name}.example.com"))
+            .issuer_name(ca.subject)
+            .public_key(ee_key.public_key())
+            .serial_number(x509.random_serial_number())
+            .not_valid_before(NOT_BEFORE)
+            .not_valid_after(NOT_AFTER)
+            .add_extension(
+                x509.BasicConstraints(ca=False, path_length=None), critical=True
+            )
+            .add_extension(
+                x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]), critical=False
+            )
+            .add_extension(
+                x509.CRLDistributionPoints(
+                    [
+                        x509.DistributionPoint(
+                            full_name=[x509.UniformResourceIdentifier(CRL_URI)],
+                            relative_name=None,
+                            reasons=dp_reasons,
+                            crl_issuer=None,
+                        )
+                    ]
+                ),
+                critical=False,
+            )
+            .sign(ca_key, hashes.SHA256())
+        )
+        write_der(HERE / f"ee.{ee_name}.der", ee.public_bytes(Encoding.DER), force)
+        serials[ee_name] = ee.serial_number
+
+    for (file_name, number, only_some_reasons, revoked) in [
+        (
+            "key_compromise.crl.der",
+            1,
+            KEY_COMPROMISE_REASONS,
+            ["revoked_for_key_compromise", "dp_key_compromise"],
+        ),
+        (
+            "other_reasons.crl.der",
+            2,
+            OTHER_REASONS,
+            ["revoked_for_superseded", "dp_key_compromise"],
+        ),
+        (
+            "all_reasons.crl.der",
+            3,
+            None,
+            ["revoked_for_key_compromise", "dp_key_compromise"],
+        ),
+    ]:
+        write_der(
+            HERE / file_name,
+            crl(
+                ca.subject,
+                ca_key,
+                [revoked_cert(serials[ee_name]) for ee_name in revoked],
+                number=number,
+                idp=issuing_distribution_point(
+                    CRL_URI, only_some_reasons=only_some_reasons
+                ),
+            ),
+            force,
+        )
+
+
+if __name__ == "__main__":
+    testcerts.main(generate)
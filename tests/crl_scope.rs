@@ -0,0 +1,98 @@
+#![cfg(feature = "ring")]
+
+mod common;
+
+use webpki::Error;
+
+const ROOT: &[u8] = include_bytes!("crl_scope/root.ca.der");
+const INT: &[u8] = include_bytes!("crl_scope/int.ca.der");
+
+/// Has a distribution point named "http://example.com/int.crl".
+const EE: &[u8] = include_bytes!("crl_scope/ee.der");
+
+/// Has no distribution points.
+const EE_NO_DP: &[u8] = include_bytes!("crl_scope/ee.no_dp.der");
+
+fn check(ee: &[u8], crls: &[&[u8]]) -> Result<(), Error> {
+    common::check_revocation(ROOT, ee, &[INT], crls)
+}
+
+// Each of the intermediate's CRLs revokes both end-entity certificates, and each of the root's
+// CRLs revokes the intermediate.
+const INT_NO_IDP: &[u8] = include_bytes!("crl_scope/int.no_idp.crl.der");
+const INT_IDP: &[u8] = include_bytes!("crl_scope/int.idp.crl.der");
+const INT_IDP_OTHER_DP: &[u8] = include_bytes!("crl_scope/int.idp.other_dp.crl.der");
+const INT_IDP_ONLY_USER: &[u8] = include_bytes!("crl_scope/int.idp.only_user_certs.crl.der");
+const INT_IDP_ONLY_CA: &[u8] = include_bytes!("crl_scope/int.idp.only_ca_certs.crl.der");
+const ROOT_IDP_ONLY_USER: &[u8] = include_bytes!("crl_scope/root.idp.only_user_certs.crl.der");
+const ROOT_IDP_ONLY_CA: &[u8] = include_bytes!("crl_scope/root.idp.only_ca_certs.crl.der");
+
+// CRLs whose issuing distribution point has no distribution point name.
+const INT_IDP_ONLY_USER_NO_NAME: &[u8] =
+    include_bytes!("crl_scope/int.idp.only_user_certs.no_name.crl.der");
+const ROOT_IDP_ONLY_USER_NO_NAME: &[u8] =
+    include_bytes!("crl_scope/root.idp.only_user_certs.no_name.crl.der");
+const ROOT_IDP_ONLY_CA_NO_NAME: &[u8] =
+    include_bytes!("crl_scope/root.idp.only_ca_certs.no_name.crl.der");
+
+#[test]
+fn crl_without_idp_covers_every_cert() {
+    assert_eq!(check(EE, &[INT_NO_IDP]), Err(Error::CertRevoked));
+    assert_eq!(check(EE_NO_DP, &[INT_NO_IDP]), Err(Error::CertRevoked));
+}
+
+#[test]
+fn crl_with_matching_idp() {
+    assert_eq!(check(EE, &[INT_IDP]), Err(Error::CertRevoked));
+}
+
+#[test]
+fn crl_with_other_idp_is_out_of_scope() {
+    assert_eq!(check(EE, &[INT_IDP_OTHER_DP]), Ok(()));
+
+    // An out of scope CRL doesn't stop an in scope one from being used.
+    assert_eq!(
+        check(EE, &[INT_IDP_OTHER_DP, INT_IDP]),
+        Err(Error::CertRevoked)
+    );
+}
+
+#[test]
+fn crl_with_idp_is_out_of_scope_for_cert_without_distribution_points() {
+    assert_eq!(check(EE_NO_DP, &[INT_IDP]), Ok(()));
+}
+
+#[test]
+fn only_contains_user_certs() {
+    assert_eq!(check(EE, &[INT_IDP_ONLY_USER]), Err(Error::CertRevoked));
+    assert_eq!(check(EE, &[ROOT_IDP_ONLY_USER]), Ok(()));
+}
+
+#[test]
+fn only_contains_ca_certs() {
+    assert_eq!(check(EE, &[INT_IDP_ONLY_CA]), Ok(()));
+    assert_eq!(check(EE, &[ROOT_IDP_ONLY_CA]), Err(Error::CertRevoked));
+}
+
+#[test]
+fn crl_with_unnamed_idp_covers_every_distribution_point() {
+    assert_eq!(
+        check(EE, &[INT_IDP_ONLY_USER_NO_NAME]),
+        Err(Error::CertRevoked)
+    );
+    assert_eq!(
+        check(EE, &[ROOT_IDP_ONLY_CA_NO_NAME]),
+        Err(Error::CertRevoked)
+    );
+
+    // Its other fields still limit its scope.
+    assert_eq!(check(EE, &[ROOT_IDP_ONLY_USER_NO_NAME]), Ok(()));
+}
+
+#[test]
+fn crl_with_unnamed_idp_covers_cert_without_distribution_points() {
+    assert_eq!(
+        check(EE_NO_DP, &[INT_IDP_ONLY_USER_NO_NAME]),
+        Err(Error::CertRevoked)
+    );
+}
@@ -0,0 +1,138 @@
+"""
+Generates a root CA, an intermediate CA, end-entity certificates with and without CRL
+distribution points, and CRLs with issuing distribution points of varying scope, to test which
+CRLs are used to check each certificate's revocation status.
+"""
+
+import functools
+import sys
+from pathlib import Path
+from typing import Optional
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.x509.oid import ExtendedKeyUsageOID
+
+HERE = Path(__file__).parent
+sys.path.insert(0, str(HERE.parent))
+
+import testcerts  # noqa: E402
+from testcerts import (  # noqa: E402
+    NOT_AFTER,
+    NOT_BEFORE,
+    crl,
+    issuing_distribution_point,
+    revoked_cert,
+    write_der,
+)
+
+ROOT_CRL_URI = "http://example.com/root.crl"
+INT_CRL_URI = "http://example.com/int.crl"
+OTHER_CRL_URI = "http://example.com/other.crl"
+
+name = functools.partial(testcerts.name, organization="crl scope")
+
+
+def distribution_points(uri: str) -> x509.CRLDistributionPoints:
+    return x509.CRLDistributionPoints(
+        [
+            x509.DistributionPoint(
+                full_name=[x509.UniformResourceIdentifier(uri)],
+                relative_name=None,
+                reasons=None,
+                crl_issuer=None,
+            )
+        ]
+    )
+
+
+def cert(
+    subject: x509.Name,
+    issuer: x509.Name,
+    issuer_key: ec.EllipticCurvePrivateKey,
+    key: ec.EllipticCurvePrivateKey,
+    ca: bool,
+    crl_uri: Optional[str],
+) -> x509.Certificate:
+    builder = (
+        x509.CertificateBuilder()
+        .subject_name(subject)
+        .issuer_name(issuer)
+        .public_key(key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=ca, path_length=None), critical=True)
+    )
+    if not ca:
+        builder = builder.add_extension(
+            x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]), critical=False
+        )
+    if crl_uri is not None:
+        builder = builder.add_extension(distribution_points(crl_uri), critical=False)
+    return builder.sign(issuer_key, hashes.SHA256())
+
+
+def generate(force: bool) -> None:
+    root_key = ec.generate_private_key(ec.SECP256R1())
+    root = cert(name("Root"), name("Root"), root_key, root_key, True, None)
+    write_der(HERE / "root.ca.der", root.public_bytes(Encoding.DER), force)
+
+    int_key = ec.generate_private_key(ec.SECP256R1())
+    int_ca = cert(name("Intermediate"), root.subject, root_key, int_key, True, ROOT_CRL_URI)
+    write_der(HERE / "int.ca.der", int_ca.public_bytes(Encoding.DER), force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1())
+    ee = cert(name("ee.example.com"), int_ca.subject, int_key, ee_key, False, INT_CRL_URI)
+    write_der(HERE / "ee.der", ee.public_bytes(Encoding.DER), force)
+    ee_no_dp = cert(name("ee.example.com"), int_ca.subject, int_key, ee_key, False, None)
+    write_der(HERE / "ee.no_dp.der", ee_no_dp.public_bytes(Encoding.DER), force)
+
+    # CRLs from the intermediate, each revoking both end-entity certificates.
+    revoked = [revoked_cert(ee.serial_number), revoked_cert(ee_no_dp.serial_number)]
+    for (file_name, idp) in [
+        ("int.no_idp.crl.der", None),
+        ("int.idp.crl.der", issuing_distribution_point(INT_CRL_URI)),
+        ("int.idp.other_dp.crl.der", issuing_distribution_point(OTHER_CRL_URI)),
+        (
+            "int.idp.only_user_certs.crl.der",
+            issuing_distribution_point(INT_CRL_URI, only_user_certs=True),
+        ),
+        (
+            "int.idp.only_ca_certs.crl.der",
+            issuing_distribution_point(INT_CRL_URI, only_ca_certs=True),
+        ),
+        (
+            "int.idp.only_user_certs.no_name.crl.der",
+            issuing_distribution_point(None, only_user_certs=True),
+        ),
+    ]:
+        write_der(HERE / file_name, crl(int_ca.subject, int_key, revoked, idp=idp), force)
+
+    # CRLs from the root, each revoking the intermediate.
+    revoked = [revoked_cert(int_ca.serial_number)]
+    for (file_name, idp) in [
+        (
+            "root.idp.only_user_certs.crl.der",
+            issuing_distribution_point(ROOT_CRL_URI, only_user_certs=True),
+        ),
+        (
+            "root.idp.only_ca_certs.crl.der",
+            issuing_distribution_point(ROOT_CRL_URI, only_ca_certs=True),
+        ),
+        (
+            "root.idp.only_user_certs.no_name.crl.der",
+            issuing_distribution_point(None, only_user_certs=True),
+        ),
+        (
+            "root.idp.only_ca_certs.no_name.crl.der",
+            issuing_distribution_point(None, only_ca_certs=True),
+        ),
+    ]:
+        write_der(HERE / file_name, crl(root.subject, root_key, revoked, idp=idp), force)
+
+
+if __name__ == "__main__":
+    testcerts.main(generate)
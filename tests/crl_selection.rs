@@ -0,0 +1,94 @@
+#![cfg(feature = "ring")]
+
+mod common;
+
+use common::crl;
+use webpki::{CertRevocationList, Error};
+
+const EE: &[u8] = include_bytes!("crl_selection/ee.der");
+const CA: &[u8] = include_bytes!("crl_selection/ca.der");
+
+const CRL_1: &[u8] = include_bytes!("crl_selection/crl.1.der");
+/// Revokes `EE`.
+const CRL_256: &[u8] = include_bytes!("crl_selection/crl.256.der");
+/// Has the CA's name, but was signed by a different key.
+const CRL_OTHER_KEY: &[u8] = include_bytes!("crl_selection/crl.other_key.der");
+
+fn check(crls: &[&[u8]]) -> Result<(), Error> {
+    common::check_revocation(CA, EE, &[], crls)
+}
+
+#[test]
+fn crl_number_and_authority_key_identifier() {
+    let (crl_1, crl_256, crl_other_key) = (crl(CRL_1), crl(CRL_256), crl(CRL_OTHER_KEY));
+    assert_eq!(crl_1.crl_number(), Some(&[1][..]));
+    assert_eq!(crl_256.crl_number(), Some(&[1, 0][..]));
+    assert_eq!(crl_other_key.crl_number(), Some(&[1, 1][..]));
+
+    assert!(crl_1.authority_key_identifier().is_some());
+    assert_eq!(
+        crl_1.authority_key_identifier(),
+        crl_256.authority_key_identifier()
+    );
+    assert_ne!(
+        crl_1.authority_key_identifier(),
+        crl_other_key.authority_key_identifier()
+    );
+
+    #[cfg(feature = "alloc")]
+    {
+        let owned = crl_256.to_owned().unwrap();
+        assert_eq!(owned.crl_number(), crl_256.crl_number());
+        assert_eq!(
+            owned.authority_key_identifier(),
+            crl_256.authority_key_identifier()
+        );
+    }
+}
+
+#[test]
+fn supersedes() {
+    let (crl_1, crl_256, crl_other_key) = (crl(CRL_1), crl(CRL_256), crl(CRL_OTHER_KEY));
+    assert!(crl_256.supersedes(&crl_1));
+    assert!(!crl_1.supersedes(&crl_256));
+    assert!(!crl_256.supersedes(&crl_256));
+
+    // A CRL from another key isn't comparable, whatever its number.
+    assert!(!crl_other_key.supersedes(&crl_256));
+}
+
+#[test]
+fn newest_crl_is_used() {
+    assert_eq!(check(&[CRL_1]), Ok(()));
+    assert_eq!(check(&[CRL_1, CRL_256]), Err(Error::CertRevoked));
+    assert_eq!(check(&[CRL_256, CRL_1]), Err(Error::CertRevoked));
+}
+
+#[test]
+fn crl_from_other_key_is_not_used() {
+    assert_eq!(check(&[CRL_OTHER_KEY, CRL_256]), Err(Error::CertRevoked));
+}
+
+fn check_rollover(crls: &[&[u8]]) -> Result<(), Error> {
+    common::check_revocation(
+        include_bytes!("crl_selection/rollover.ca.der"),
+        include_bytes!("crl_selection/rollover.ee.der"),
+        &[],
+        crls,
+    )
+}
+
+#[test]
+fn crl_signed_by_rolled_over_key_is_skipped() {
+    // The CRL from the old key has a higher cRLNumber, but isn't signed by the issuer's key.
+    let old_key = include_bytes!("crl_selection/rollover.crl.old_key.der").as_slice();
+    let new_key = include_bytes!("crl_selection/rollover.crl.new_key.der").as_slice();
+    assert_eq!(check_rollover(&[old_key, new_key]), Err(Error::CertRevoked));
+    assert_eq!(check_rollover(&[new_key, old_key]), Err(Error::CertRevoked));
+
+    // Only when no CRL's signature verifies is that an error.
+    assert_eq!(
+        check_rollover(&[old_key]),
+        Err(Error::InvalidCrlSignatureForPublicKey)
+    );
+}
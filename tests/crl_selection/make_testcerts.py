@@ -0,0 +1,142 @@
+"""
+Generates a CA, an end-entity certificate it issued, and several CRLs with the CA's name, to
+test which of them is used for revocation checking. Also generates a CA whose key was rolled
+over, with a CRL from each key.
+"""
+
+import functools
+import sys
+from pathlib import Path
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.x509.oid import ExtendedKeyUsageOID
+
+HERE = Path(__file__).parent
+sys.path.insert(0, str(HERE.parent))
+
+import testcerts  # noqa: E402
+from testcerts import NOT_AFTER, NOT_BEFORE, crl, revoked_cert, write_der  # noqa: E402
+
+name = functools.partial(testcerts.name, organization="crl selection")
+
+
+def generate(force: bool) -> None:
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+    ca = (
+        x509.CertificateBuilder()
+        .subject_name(name("CA"))
+        .issuer_name(name("CA"))
+        .public_key(ca_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .add_extension(
+            x509.SubjectKeyIdentifier.from_public_key(ca_key.public_key()),
+            critical=False,
+        )
+        .sign(ca_key, hashes.SHA256())
+    )
+    write_der(HERE / "ca.der", ca.public_bytes(Encoding.DER), force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1())
+    ee = (
+        x509.CertificateBuilder()
+        .subject_name(name("ee.example.com"))
+        .issuer_name(ca.subject)
+        .public_key(ee_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=False, path_length=None), critical=True)
+        .add_extension(
+            x509.AuthorityKeyIdentifier.from_issuer_public_key(ca_key.public_key()),
+            critical=False,
+        )
+        .add_extension(
+            x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]), critical=False
+        )
+        .sign(ca_key, hashes.SHA256())
+    )
+    write_der(HERE / "ee.der", ee.public_bytes(Encoding.DER), force)
+
+    # An older CRL that doesn't revoke the end-entity certificate, and a newer one that does.
+    write_der(
+        HERE / "crl.1.der",
+        crl(ca.subject, ca_key, number=1, authority_key=ca_key.public_key()),
+        force,
+    )
+    write_der(
+        HERE / "crl.256.der",
+        crl(
+            ca.subject,
+            ca_key,
+            [revoked_cert(ee.serial_number)],
+            number=256,
+            authority_key=ca_key.public_key(),
+        ),
+        force,
+    )
+
+    # The newest CRL, but from a different key with the CA's name.
+    other_key = ec.generate_private_key(ec.SECP256R1())
+    write_der(
+        HERE / "crl.other_key.der",
+        crl(ca.subject, other_key, number=257, authority_key=other_key.public_key()),
+        force,
+    )
+
+    rollover(force)
+
+
+def rollover(force: bool) -> None:
+    """
+    A CA whose key was rolled over, without authority key identifiers to tell its CRLs apart.
+    The CRL signed by the new key revokes the end-entity certificate, and the CRL signed by the
+    old key has a higher cRLNumber but doesn't.
+    """
+    old_key = ec.generate_private_key(ec.SECP256R1())
+    new_key = ec.generate_private_key(ec.SECP256R1())
+    ca = (
+        x509.CertificateBuilder()
+        .subject_name(name("Rollover CA"))
+        .issuer_name(name("Rollover CA"))
+        .public_key(new_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .sign(new_key, hashes.SHA256())
+    )
+    write_der(HERE / "rollover.ca.der", ca.public_bytes(Encoding.DER), force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1())
+    ee = (
+        x509.CertificateBuilder()
+        .subject_name(name("rollover.example.com"))
+        .issuer_name(ca.subject)
+        .public_key(ee_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=False, path_length=None), critical=True)
+        .add_extension(
+            x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]), critical=False
+        )
+        .sign(new_key, hashes.SHA256())
+    )
+    write_der(HERE / "rollover.ee.der", ee.public_bytes(Encoding.DER), force)
+
+    write_der(HERE / "rollover.crl.old_key.der", crl(ca.subject, old_key, number=2), force)
+    write_der(
+        HERE / "rollover.crl.new_key.der",
+        crl(ca.subject, new_key, [revoked_cert(ee.serial_number)], number=1),
+        force,
+    )
+
+
+if __name__ == "__main__":
+    testcerts.main(generate)
@@ -0,0 +1,91 @@
+#![cfg(feature = "ring")]
+
+mod common;
+
+use webpki::Error;
+
+/// Its key usage doesn't assert cRLSign.
+const CA: &[u8] = include_bytes!("crl_signer/ca.der");
+const EE: &[u8] = include_bytes!("crl_signer/ee.der");
+
+// CRL signing certificates issued by `CA`, with its name.
+const SIGNER: &[u8] = include_bytes!("crl_signer/signer.der");
+const SIGNER_NO_CRL_SIGN: &[u8] = include_bytes!("crl_signer/signer.no_crl_sign.der");
+const SIGNER_EXPIRED: &[u8] = include_bytes!("crl_signer/signer.expired.der");
+const SIGNER_WRONG_KEY: &[u8] = include_bytes!("crl_signer/signer.wrong_key.der");
+
+// CRLs revoking `EE`, signed by the key of the certificate they're named after.
+const CA_CRL: &[u8] = include_bytes!("crl_signer/ca.crl.der");
+const SIGNER_CRL: &[u8] = include_bytes!("crl_signer/signer.crl.der");
+const SIGNER_NO_CRL_SIGN_CRL: &[u8] = include_bytes!("crl_signer/signer.no_crl_sign.crl.der");
+const SIGNER_EXPIRED_CRL: &[u8] = include_bytes!("crl_signer/signer.expired.crl.der");
+const SIGNER_WRONG_KEY_CRL: &[u8] = include_bytes!("crl_signer/signer.wrong_key.crl.der");
+
+fn check(crl: &[u8], intermediates: &[&[u8]]) -> Result<(), Error> {
+    common::check_revocation(CA, EE, intermediates, &[crl])
+}
+
+#[test]
+fn crl_signed_by_issuer() {
+    // The key usage of trust anchors isn't tracked, so the CA's own CRLs are still used.
+    assert_eq!(check(CA_CRL, &[SIGNER]), Err(Error::CertRevoked));
+}
+
+#[test]
+fn crl_signed_by_crl_signer() {
+    assert_eq!(check(SIGNER_CRL, &[SIGNER]), Err(Error::CertRevoked));
+
+    // Other intermediates don't get in the way.
+    assert_eq!(
+        check(SIGNER_CRL, &[SIGNER_NO_CRL_SIGN, SIGNER]),
+        Err(Error::CertRevoked)
+    );
+}
+
+#[test]
+fn crl_signed_by_missing_crl_signer_is_not_used() {
+    assert_eq!(check(SIGNER_CRL, &[]), Ok(()));
+}
+
+#[test]
+fn crl_signer_must_assert_crl_sign() {
+    assert_eq!(check(SIGNER_NO_CRL_SIGN_CRL, &[SIGNER_NO_CRL_SIGN]), Ok(()));
+}
+
+#[test]
+fn crl_signer_must_be_valid() {
+    assert_eq!(check(SIGNER_EXPIRED_CRL, &[SIGNER_EXPIRED]), Ok(()));
+}
+
+#[test]
+fn crl_signer_must_be_issued_by_crl_issuer() {
+    assert_eq!(check(SIGNER_WRONG_KEY_CRL, &[SIGNER_WRONG_KEY]), Ok(()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn crl_signer_from_intermediate_store() {
+    use webpki::{CertRevocationList, KeyUsage, RevocationOptions, TrustAnchor};
+
+    let crl = common::crl(SIGNER_CRL);
+    let crls: &[&dyn CertRevocationList] = &[&crl];
+
+    let mut store = webpki::IntermediateStore::new();
+    store.add(SIGNER).unwrap();
+
+    let anchors = [TrustAnchor::try_from_cert_der(CA).unwrap()];
+    let result = webpki::EndEntityCert::try_from(EE)
+        .unwrap()
+        .verify_for_usage_with_options(
+            &[webpki::ECDSA_P256_SHA256],
+            &anchors[..],
+            &[],
+            webpki::Time::from_seconds_since_unix_epoch(common::CRL_TIME),
+            KeyUsage::client_auth(),
+            Some(RevocationOptions::new(crls)),
+            &webpki::VerifyOptions::new().with_intermediate_store(&store),
+        )
+        .map(|_| ())
+        .map_err(Error::from);
+    assert_eq!(result, Err(Error::CertRevoked));
+}
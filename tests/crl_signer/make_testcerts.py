@@ -0,0 +1,134 @@
+"""
+Generates a CA that doesn't sign CRLs itself, CRL signing certificates it issued with its own
+name, an end-entity certificate, and CRLs signed by each CRL signing certificate's key, to test
+revocation checking with CRLs signed by a dedicated CRL signing certificate.
+"""
+
+import datetime
+import functools
+import sys
+from pathlib import Path
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.x509.oid import ExtendedKeyUsageOID
+
+HERE = Path(__file__).parent
+sys.path.insert(0, str(HERE.parent))
+
+import testcerts  # noqa: E402
+from testcerts import NOT_AFTER, NOT_BEFORE, crl, revoked_cert, write_der  # noqa: E402
+
+name = functools.partial(testcerts.name, organization="crl signer")
+
+
+def key_usage(key_cert_sign: bool, crl_sign: bool) -> x509.KeyUsage:
+    return x509.KeyUsage(
+        digital_signature=not crl_sign,
+        content_commitment=False,
+        key_encipherment=False,
+        data_encipherment=False,
+        key_agreement=False,
+        key_cert_sign=key_cert_sign,
+        crl_sign=crl_sign,
+        encipher_only=False,
+        decipher_only=False,
+    )
+
+
+def generate(force: bool) -> None:
+    # The CA's key usage doesn't allow it to sign CRLs itself.
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+    ca = (
+        x509.CertificateBuilder()
+        .subject_name(name("CA"))
+        .issuer_name(name("CA"))
+        .public_key(ca_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .add_extension(key_usage(key_cert_sign=True, crl_sign=False), critical=True)
+        .add_extension(
+            x509.SubjectKeyIdentifier.from_public_key(ca_key.public_key()),
+            critical=False,
+        )
+        .sign(ca_key, hashes.SHA256())
+    )
+    write_der(HERE / "ca.der", ca.public_bytes(Encoding.DER), force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1())
+    ee = (
+        x509.CertificateBuilder()
+        .subject_name(name("ee.example.com"))
+        .issuer_name(ca.subject)
+        .public_key(ee_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=False, path_length=None), critical=True)
+        .add_extension(
+            x509.AuthorityKeyIdentifier.from_issuer_public_key(ca_key.public_key()),
+            critical=False,
+        )
+        .add_extension(
+            x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]), critical=False
+        )
+        .sign(ca_key, hashes.SHA256())
+    )
+    write_der(HERE / "ee.der", ee.public_bytes(Encoding.DER), force)
+
+    # A CRL signed by the CA itself.
+    revoked = [revoked_cert(ee.serial_number)]
+    write_der(
+        HERE / "ca.crl.der",
+        crl(ca.subject, ca_key, revoked, authority_key=ca_key.public_key()),
+        force,
+    )
+
+    # CRL signing certificates, and a CRL revoking the end-entity certificate signed by each.
+    wrong_key = ec.generate_private_key(ec.SECP256R1())
+    for (signer_name, signing_key, crl_sign, not_after) in [
+        ("signer", ca_key, True, NOT_AFTER),
+        # Doesn't assert cRLSign.
+        ("signer.no_crl_sign", ca_key, False, NOT_AFTER),
+        # Expired before the time the end-entity certificate is verified at.
+        ("signer.expired", ca_key, True, NOT_BEFORE + datetime.timedelta(seconds=1)),
+        # Claims to be issued by the CA, but was signed by another key.
+        ("signer.wrong_key", wrong_key, True, NOT_AFTER),
+    ]:
+        signer_key = ec.generate_private_key(ec.SECP256R1())
+        signer = (
+            x509.CertificateBuilder()
+            .subject_name(ca.subject)
+            .issuer_name(ca.subject)
+            .public_key(signer_key.public_key())
+            .serial_number(x509.random_serial_number())
+            .not_valid_before(NOT_BEFORE)
+            .not_valid_after(not_after)
+            .add_extension(
+                x509.BasicConstraints(ca=False, path_length=None), critical=True
+            )
+            .add_extension(key_usage(key_cert_sign=False, crl_sign=crl_sign), critical=True)
+            .add_extension(
+                x509.AuthorityKeyIdentifier.from_issuer_public_key(ca_key.public_key()),
+                critical=False,
+            )
+            .add_extension(
+                x509.SubjectKeyIdentifier.from_public_key(signer_key.public_key()),
+                critical=False,
+            )
+            .sign(signing_key, hashes.SHA256())
+        )
+        write_der(HERE / f"{signer_name}.der", signer.public_bytes(Encoding.DER), force)
+        write_der(
+            HERE / f"{signer_name}.crl.der",
+            crl(ca.subject, signer_key, revoked, authority_key=signer_key.public_key()),
+            force,
+        )
+
+
+if __name__ == "__main__":
+    testcerts.main(generate)
@@ -14,12 +14,18 @@ fn parse_valid_crl() {
     // We should be able to parse a valid CRL without error, and find the revoked serial.
     let crl = include_bytes!("crls/crl.valid.der");
     let crl = BorrowedCertRevocationList::from_der(&crl[..]).expect("failed to parse valid crl");
-    assert!(crl.find_serial(REVOKED_SERIAL).unwrap().is_some());
+    assert!(crl
+        .find_serial(crl.issuer(), REVOKED_SERIAL)
+        .unwrap()
+        .is_some());
 
     #[cfg(feature = "alloc")]
     {
         let crl = crl.to_owned().unwrap();
-        assert!(crl.find_serial(REVOKED_SERIAL).unwrap().is_some());
+        assert!(crl
+            .find_serial(crl.issuer(), REVOKED_SERIAL)
+            .unwrap()
+            .is_some());
     }
 }
 
@@ -80,10 +86,16 @@ fn parse_missing_exts_crl() {
 
 #[test]
 fn parse_delta_crl() {
-    // Parsing a CRL with an extension indicating its a delta CRL should error.
+    // Parsing a CRL with an extension indicating its a delta CRL should yield the base CRL
+    // number it updates.
     let crl = include_bytes!("crls/crl.delta.der");
-    let res = BorrowedCertRevocationList::from_der(&crl[..]);
-    assert!(matches!(res, Err(Error::UnsupportedDeltaCrl)));
+    let crl = BorrowedCertRevocationList::from_der(&crl[..]).unwrap();
+    let number = &[0x17, 0x1c, 0xce, 0x3d, 0xe4, 0x82, 0xba, 0x61][..];
+    assert_eq!(crl.crl_number(), Some(number));
+    assert_eq!(crl.base_crl_number(), Some(number));
+
+    // A delta CRL doesn't update itself.
+    assert!(!crl.is_delta_for(&crl));
 }
 
 #[test]
@@ -116,11 +128,11 @@ fn parse_entry_negative_serial_crl() {
     let crl = BorrowedCertRevocationList::from_der(&crl[..]).unwrap();
 
     assert!(crl
-        .find_serial(REVOKED_SERIAL)
+        .find_serial(crl.issuer(), REVOKED_SERIAL)
         .expect("looking for REVOKED_SERIAL failed")
         .is_none());
     assert!(crl
-        .find_serial(REVOKED_SERIAL_NEGATIVE)
+        .find_serial(crl.issuer(), REVOKED_SERIAL_NEGATIVE)
         .expect("looking for REVOKED_SERIAL_NEGATIVE failed")
         .is_some());
 
@@ -128,11 +140,11 @@ fn parse_entry_negative_serial_crl() {
     {
         let crl = crl.to_owned().unwrap();
         assert!(crl
-            .find_serial(REVOKED_SERIAL)
+            .find_serial(crl.issuer(), REVOKED_SERIAL)
             .expect("looking for REVOKED_SERIAL failed")
             .is_none());
         assert!(crl
-            .find_serial(REVOKED_SERIAL_NEGATIVE)
+            .find_serial(crl.issuer(), REVOKED_SERIAL_NEGATIVE)
             .expect("looking for REVOKED_SERIAL_NEGATIVE failed")
             .is_some());
     }
@@ -144,7 +156,7 @@ fn parse_entry_topbit_serial_crl() {
     let crl = BorrowedCertRevocationList::from_der(&crl[..]).unwrap();
 
     assert!(crl
-        .find_serial(REVOKED_SERIAL_WITH_TOP_BIT_SET)
+        .find_serial(crl.issuer(), REVOKED_SERIAL_WITH_TOP_BIT_SET)
         .expect("failed to look for REVOKED_SERIAL_WITH_TOP_BIT_SET")
         .is_some());
 
@@ -152,7 +164,7 @@ fn parse_entry_topbit_serial_crl() {
     {
         let crl = crl.to_owned().unwrap();
         assert!(crl
-            .find_serial(REVOKED_SERIAL_WITH_TOP_BIT_SET)
+            .find_serial(crl.issuer(), REVOKED_SERIAL_WITH_TOP_BIT_SET)
             .expect("failed to look for REVOKED_SERIAL_WITH_TOP_BIT_SET")
             .is_some());
     }
@@ -164,12 +176,18 @@ fn parse_entry_without_exts_crl() {
     // should find the expected revoked certificate.
     let crl = include_bytes!("crls/crl.no.entry.exts.der");
     let crl = BorrowedCertRevocationList::from_der(&crl[..]).expect("unexpected error parsing crl");
-    assert!(crl.find_serial(REVOKED_SERIAL).unwrap().is_some());
+    assert!(crl
+        .find_serial(crl.issuer(), REVOKED_SERIAL)
+        .unwrap()
+        .is_some());
 
     #[cfg(feature = "alloc")]
     {
         let crl = crl.to_owned().unwrap();
-        assert!(crl.find_serial(REVOKED_SERIAL).unwrap().is_some());
+        assert!(crl
+            .find_serial(crl.issuer(), REVOKED_SERIAL)
+            .unwrap()
+            .is_some());
     }
 }
 
@@ -196,7 +214,7 @@ fn parse_entry_unknown_crit_ext_crl() {
 
     // but should error when we try to find a revoked serial due to the entry with the unsupported
     // critical ext.
-    let res = crl.find_serial(REVOKED_SERIAL);
+    let res = crl.find_serial(crl.issuer(), REVOKED_SERIAL);
     assert!(matches!(res, Err(Error::UnsupportedCriticalExtension)));
 
     #[cfg(feature = "alloc")]
@@ -215,7 +233,7 @@ fn parse_entry_invalid_reason_crl() {
     let crl = BorrowedCertRevocationList::from_der(&crl[..]).unwrap();
 
     // But searching for a serial should error due to the revoked cert with the unknown reason.
-    let res = crl.find_serial(REVOKED_SERIAL);
+    let res = crl.find_serial(crl.issuer(), REVOKED_SERIAL);
     assert!(matches!(res, Err(Error::UnsupportedRevocationReason)));
 
     #[cfg(feature = "alloc")]
@@ -233,7 +251,7 @@ fn parse_entry_invalidity_date_crl() {
     let crl = include_bytes!("crls/crl.entry.invalidity.date.der");
     let crl = BorrowedCertRevocationList::from_der(&crl[..]).expect("unexpected err parsing CRL");
     assert!(crl
-        .find_serial(REVOKED_SERIAL)
+        .find_serial(crl.issuer(), REVOKED_SERIAL)
         .unwrap()
         .unwrap()
         .invalidity_date
@@ -243,7 +261,7 @@ fn parse_entry_invalidity_date_crl() {
     {
         let crl = crl.to_owned().unwrap();
         assert!(crl
-            .find_serial(REVOKED_SERIAL)
+            .find_serial(crl.issuer(), REVOKED_SERIAL)
             .unwrap()
             .unwrap()
             .invalidity_date
@@ -253,19 +271,19 @@ fn parse_entry_invalidity_date_crl() {
 
 #[test]
 fn parse_entry_indirect_issuer_crl() {
-    // Parsing a CRL that includes a revoked entry that has a issuer certificate extension
-    // shouldn't error up-front - we expect the error to be surfaced when we iterate the revoked
-    // certs.
+    // Parsing a CRL that includes a revoked entry that has a malformed certificate issuer
+    // extension shouldn't error up-front - we expect the error to be surfaced when we iterate the
+    // revoked certs.
     let crl = include_bytes!("crls/crl.entry.issuer.ext.der");
     let crl = BorrowedCertRevocationList::from_der(&crl[..]).unwrap();
 
-    let res = crl.find_serial(REVOKED_SERIAL);
-    assert!(matches!(res, Err(Error::UnsupportedIndirectCrl)));
+    let res = crl.find_serial(crl.issuer(), REVOKED_SERIAL);
+    assert!(matches!(res, Err(Error::BadDer)));
 
     #[cfg(feature = "alloc")]
     {
         // Building an owned CRL should error up front since it will process the revoked certs.
         let res = crl.to_owned();
-        assert!(matches!(res, Err(Error::UnsupportedIndirectCrl)));
+        assert!(matches!(res, Err(Error::BadDer)));
     }
 }
@@ -0,0 +1,67 @@
+#![cfg(all(feature = "alloc", feature = "ring"))]
+
+use webpki::{IntermediateStore, KeyUsage, VerifyOptions};
+
+fn check_cloudflare(
+    intermediates: &[&[u8]],
+    anchors: &[webpki::TrustAnchor],
+    store: &IntermediateStore,
+) -> Result<(), webpki::Error> {
+    let ee: &[u8] = include_bytes!("cloudflare_dns/ee.der");
+    let time = webpki::Time::from_seconds_since_unix_epoch(1_663_495_771);
+
+    let cert = webpki::EndEntityCert::try_from(ee).unwrap();
+    cert.verify_for_usage_with_options(
+        &[
+            webpki::ECDSA_P256_SHA256,
+            webpki::ECDSA_P384_SHA384,
+            webpki::RSA_PKCS1_2048_8192_SHA384,
+        ],
+        anchors,
+        intermediates,
+        time,
+        KeyUsage::server_auth(),
+        None,
+        &VerifyOptions::new().with_intermediate_store(store),
+    )
+    .map_err(webpki::Error::from)
+    .map(|_| ())
+}
+
+#[test]
+fn intermediate_store_completes_chain() {
+    let inter = include_bytes!("cloudflare_dns/inter.der");
+    let ca = include_bytes!("cloudflare_dns/ca.der");
+    let anchors = [webpki::TrustAnchor::try_from_cert_der(ca).unwrap()];
+
+    let mut store = IntermediateStore::new();
+    store.add(inter).unwrap();
+    assert_eq!(check_cloudflare(&[], &anchors, &store), Ok(()));
+
+    // Without the store the chain is incomplete.
+    assert_eq!(
+        check_cloudflare(&[], &anchors, &IntermediateStore::new()),
+        Err(webpki::Error::UnknownIssuer)
+    );
+}
+
+#[test]
+fn intermediate_store_certs_are_not_trusted() {
+    let inter = include_bytes!("cloudflare_dns/inter.der");
+    let ca = include_bytes!("cloudflare_dns/ca.der");
+
+    let mut store = IntermediateStore::new();
+    store.add(inter).unwrap();
+    store.add(ca).unwrap();
+    assert_eq!(
+        check_cloudflare(&[], &[], &store),
+        Err(webpki::Error::UnknownIssuer)
+    );
+}
+
+#[test]
+fn intermediate_store_rejects_malformed_certs() {
+    let mut store = IntermediateStore::new();
+    assert_eq!(store.add(b"not a certificate"), Err(webpki::Error::BadDer));
+    assert!(store.is_empty());
+}
@@ -0,0 +1,118 @@
+#![cfg(all(feature = "alloc", feature = "ring"))]
+
+use std::cell::RefCell;
+
+use webpki::{IssuerQuery, IssuerSource, KeyUsage, VerifyOptions};
+
+/// An issuer source that hands out a fixed set of certificates, recording what it was asked for.
+struct FixedIssuers {
+    certs: Vec<Vec<u8>>,
+    queries: RefCell<Vec<RecordedQuery>>,
+}
+
+struct RecordedQuery {
+    issuer: Vec<u8>,
+    authority_key_id: Option<Vec<u8>>,
+    ca_issuers: Vec<Vec<u8>>,
+}
+
+impl FixedIssuers {
+    fn new(certs: &[&[u8]]) -> Self {
+        Self {
+            certs: certs.iter().map(|der| der.to_vec()).collect(),
+            queries: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl IssuerSource for FixedIssuers {
+    fn issuers(&self, query: &IssuerQuery) -> Vec<Vec<u8>> {
+        self.queries.borrow_mut().push(RecordedQuery {
+            issuer: query.issuer().to_vec(),
+            authority_key_id: query.authority_key_id().map(<[u8]>::to_vec),
+            ca_issuers: query.ca_issuers().map(<[u8]>::to_vec).collect(),
+        });
+        self.certs.clone()
+    }
+}
+
+fn check_cloudflare(
+    intermediates: &[&[u8]],
+    anchors: &[webpki::TrustAnchor],
+    source: &FixedIssuers,
+) -> Result<(), webpki::Error> {
+    let ee: &[u8] = include_bytes!("cloudflare_dns/ee.der");
+    let time = webpki::Time::from_seconds_since_unix_epoch(1_663_495_771);
+
+    let cert = webpki::EndEntityCert::try_from(ee).unwrap();
+    cert.verify_for_usage_with_options(
+        &[
+            webpki::ECDSA_P256_SHA256,
+            webpki::ECDSA_P384_SHA384,
+            webpki::RSA_PKCS1_2048_8192_SHA384,
+        ],
+        anchors,
+        intermediates,
+        time,
+        KeyUsage::server_auth(),
+        None,
+        &VerifyOptions::new().with_issuer_source(source),
+    )
+    .map_err(webpki::Error::from)
+    .map(|_| ())
+}
+
+#[test]
+fn issuer_source_completes_chain() {
+    let inter: &[u8] = include_bytes!("cloudflare_dns/inter.der");
+    let ca = include_bytes!("cloudflare_dns/ca.der");
+    let anchors = [webpki::TrustAnchor::try_from_cert_der(ca).unwrap()];
+
+    let source = FixedIssuers::new(&[inter]);
+    assert_eq!(check_cloudflare(&[], &anchors, &source), Ok(()));
+
+    // The source should have been asked once, about the end-entity certificate.
+    let queries = source.queries.borrow();
+    assert_eq!(queries.len(), 1);
+    let query = &queries[0];
+    let inter_cert = webpki::TrustAnchor::try_from_cert_der(inter).unwrap();
+    assert_eq!(query.issuer, inter_cert.subject);
+    assert_eq!(
+        query.authority_key_id.as_deref(),
+        Some(
+            &[
+                0x0a, 0xbc, 0x08, 0x29, 0x17, 0x8c, 0xa5, 0x39, 0x6d, 0x7a, 0x0e, 0xce, 0x33, 0xc7,
+                0x2e, 0xb3, 0xed, 0xfb, 0xc3, 0x7a
+            ][..]
+        )
+    );
+    assert_eq!(
+        query.ca_issuers,
+        [b"http://cacerts.digicert.com/DigiCertTLSHybridECCSHA3842020CA1-1.crt".to_vec()]
+    );
+}
+
+#[test]
+fn issuer_source_not_consulted_when_intermediates_suffice() {
+    let inter: &[u8] = include_bytes!("cloudflare_dns/inter.der");
+    let ca = include_bytes!("cloudflare_dns/ca.der");
+    let anchors = [webpki::TrustAnchor::try_from_cert_der(ca).unwrap()];
+
+    let source = FixedIssuers::new(&[]);
+    assert_eq!(check_cloudflare(&[inter], &anchors, &source), Ok(()));
+    assert!(source.queries.borrow().is_empty());
+}
+
+#[test]
+fn issuer_source_candidates_are_not_trusted() {
+    // A source can't make up for a missing trust anchor, and handing back the same
+    // certificate repeatedly mustn't loop.
+    let inter: &[u8] = include_bytes!("cloudflare_dns/inter.der");
+
+    let source = FixedIssuers::new(&[inter]);
+    assert_eq!(
+        check_cloudflare(&[], &[], &source),
+        Err(webpki::Error::UnknownIssuer)
+    );
+    assert_eq!(source.queries.borrow().len(), 2);
+}
@@ -0,0 +1,83 @@
+#![cfg(all(feature = "alloc", feature = "ring"))]
+
+use webpki::{Error, KeyUsage, TrustAnchor};
+
+const EE: &[u8] = include_bytes!("cloudflare_dns/ee.der");
+const INTER: &[u8] = include_bytes!("cloudflare_dns/inter.der");
+const CA: &[u8] = include_bytes!("cloudflare_dns/ca.der");
+
+static ALGS: &[&dyn webpki::SignatureVerificationAlgorithm] = &[
+    webpki::ECDSA_P256_SHA256,
+    webpki::ECDSA_P384_SHA384,
+    webpki::RSA_PKCS1_2048_8192_SHA384,
+];
+
+fn time() -> webpki::Time {
+    webpki::Time::from_seconds_since_unix_epoch(1_663_495_771)
+}
+
+fn check_ordered(anchor: &[u8], intermediates: &[&[u8]], time: webpki::Time) -> Result<(), Error> {
+    let cert = webpki::EndEntityCert::try_from(EE).unwrap();
+    cert.verify_ordered_chain(
+        ALGS,
+        &TrustAnchor::try_from_cert_der(anchor).unwrap(),
+        intermediates,
+        time,
+        KeyUsage::server_auth(),
+        None,
+    )
+    .map(|_| ())
+    .map_err(Error::from)
+}
+
+#[test]
+fn ordered_chain_valid() {
+    assert_eq!(check_ordered(CA, &[INTER], time()), Ok(()));
+}
+
+#[test]
+fn ordered_chain_missing_intermediate() {
+    assert_eq!(check_ordered(CA, &[], time()), Err(Error::UnknownIssuer));
+}
+
+#[test]
+fn ordered_chain_rejects_unused_certificates() {
+    assert_eq!(
+        check_ordered(CA, &[INTER, INTER], time()),
+        Err(Error::UnknownIssuer)
+    );
+
+    // Path building would happily ignore the duplicate.
+    let cert = webpki::EndEntityCert::try_from(EE).unwrap();
+    assert_eq!(
+        cert.verify_for_usage(
+            ALGS,
+            &[TrustAnchor::try_from_cert_der(CA).unwrap()],
+            &[INTER, INTER],
+            time(),
+            KeyUsage::server_auth(),
+            None,
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn ordered_chain_wrong_anchor() {
+    assert_eq!(
+        check_ordered(include_bytes!("netflix/ca.der"), &[INTER], time()),
+        Err(Error::UnknownIssuer)
+    );
+}
+
+#[test]
+fn ordered_chain_checks_validity() {
+    assert_eq!(
+        check_ordered(
+            CA,
+            &[INTER],
+            webpki::Time::from_seconds_since_unix_epoch(2_000_000_000)
+        ),
+        Err(Error::CertExpired)
+    );
+}
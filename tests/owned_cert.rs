@@ -0,0 +1,181 @@
+#![cfg(all(feature = "alloc", feature = "ring"))]
+
+use std::thread;
+
+use webpki::{
+    DnsNameRef, Error, KeyUsage, OwnedEndEntityCert, OwnedIntermediateCert, SubjectNameRef,
+    TrustAnchor, VerifyOptions,
+};
+
+const EE: &[u8] = include_bytes!("cloudflare_dns/ee.der");
+const INTER: &[u8] = include_bytes!("cloudflare_dns/inter.der");
+const CA: &[u8] = include_bytes!("cloudflare_dns/ca.der");
+
+static ALGS: &[&dyn webpki::SignatureVerificationAlgorithm] = &[
+    webpki::ECDSA_P256_SHA256,
+    webpki::ECDSA_P384_SHA384,
+    webpki::RSA_PKCS1_2048_8192_SHA384,
+];
+
+fn time() -> webpki::Time {
+    webpki::Time::from_seconds_since_unix_epoch(1_663_495_771)
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn owned_certs_are_send_and_sync() {
+    assert_send_sync::<OwnedEndEntityCert>();
+    assert_send_sync::<OwnedIntermediateCert>();
+}
+
+#[test]
+fn owned_end_entity_verifies_across_threads() {
+    let ee = OwnedEndEntityCert::try_from(EE).unwrap();
+    let inter = OwnedIntermediateCert::try_from(INTER).unwrap();
+
+    let handles = (0..4)
+        .map(|_| {
+            let (ee, inter) = (ee.clone(), inter.clone());
+            thread::spawn(move || {
+                let anchors = [TrustAnchor::try_from_cert_der(CA).unwrap()];
+                ee.verify_for_usage(
+                    ALGS,
+                    &anchors[..],
+                    &[inter.der()],
+                    time(),
+                    KeyUsage::server_auth(),
+                    None,
+                )?;
+                ee.verify_is_valid_for_subject_name(SubjectNameRef::DnsName(
+                    DnsNameRef::try_from_ascii_str("one.one.one.one").unwrap(),
+                ))
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Ok(()));
+    }
+}
+
+#[test]
+fn owned_end_entity_verify_with_options() {
+    let ee = OwnedEndEntityCert::try_from(EE).unwrap();
+    let inter = OwnedIntermediateCert::try_from(INTER).unwrap();
+    let anchors = [TrustAnchor::try_from_cert_der(CA).unwrap()];
+
+    let verify = |anchors: &[TrustAnchor], intermediates: &[&[u8]], options| {
+        ee.verify_for_usage_with_options(
+            ALGS,
+            anchors,
+            intermediates,
+            time(),
+            KeyUsage::server_auth(),
+            None,
+            &options,
+        )
+        .map_err(Error::from)
+    };
+    assert!(verify(&anchors, &[inter.der()], VerifyOptions::new()).is_ok());
+
+    // With a partial chain, the end-entity certificate can itself be the trust anchor.
+    let ee_anchor = [TrustAnchor::try_from_cert_der(EE).unwrap()];
+    assert_eq!(
+        verify(&ee_anchor, &[], VerifyOptions::new()).unwrap_err(),
+        Error::UnknownIssuer
+    );
+    assert!(verify(&ee_anchor, &[], VerifyOptions::new().with_partial_chain()).is_ok());
+}
+
+#[test]
+fn owned_end_entity_verify_ordered_chain() {
+    let ee = OwnedEndEntityCert::try_from(EE).unwrap();
+    let inter = OwnedIntermediateCert::try_from(INTER).unwrap();
+    let anchor = TrustAnchor::try_from_cert_der(CA).unwrap();
+
+    let verify = |intermediates: &[&[u8]]| {
+        ee.verify_ordered_chain(
+            ALGS,
+            &anchor,
+            intermediates,
+            time(),
+            KeyUsage::server_auth(),
+            None,
+        )
+        .map(|_| ())
+        .map_err(Error::from)
+    };
+    assert_eq!(verify(&[inter.der()]), Ok(()));
+    assert_eq!(
+        verify(&[inter.der(), inter.der()]),
+        Err(Error::UnknownIssuer)
+    );
+}
+
+#[test]
+fn owned_end_entity_clone_shares_der() {
+    let ee = OwnedEndEntityCert::try_from(EE).unwrap();
+    let clone = ee.clone();
+    assert_eq!(ee.der(), EE);
+    assert!(core::ptr::eq(ee.der(), clone.der()));
+}
+
+#[test]
+fn owned_end_entity_dns_names() {
+    let ee = OwnedEndEntityCert::try_from(&include_bytes!("netflix/ee.der")[..]).unwrap();
+    let borrowed = webpki::EndEntityCert::try_from(&include_bytes!("netflix/ee.der")[..]).unwrap();
+
+    let owned_names = ee
+        .dns_names()
+        .unwrap()
+        .map(<&str>::from)
+        .collect::<Vec<_>>();
+    let borrowed_names = borrowed
+        .dns_names()
+        .unwrap()
+        .map(<&str>::from)
+        .collect::<Vec<_>>();
+    assert!(!owned_names.is_empty());
+    assert_eq!(owned_names, borrowed_names);
+}
+
+#[test]
+fn owned_end_entity_verify_signature() {
+    let ee =
+        OwnedEndEntityCert::try_from(&include_bytes!("signatures/ecdsa_p256.ee.der")[..]).unwrap();
+    let message = include_bytes!("signatures/message.bin");
+
+    assert_eq!(
+        ee.verify_signature(
+            webpki::ECDSA_P256_SHA256,
+            message,
+            include_bytes!(
+                "signatures/ecdsa_p256_key_and_ecdsa_p256_sha256_good_signature.sig.bin"
+            ),
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        ee.verify_signature(
+            webpki::ECDSA_P256_SHA256,
+            message,
+            include_bytes!(
+                "signatures/ecdsa_p256_key_and_ecdsa_p256_sha256_detects_bad_signature.sig.bin"
+            ),
+        ),
+        Err(Error::InvalidSignatureForPublicKey)
+    );
+}
+
+#[test]
+fn owned_certs_reject_invalid_der() {
+    assert_eq!(
+        OwnedEndEntityCert::try_from(&b"not a certificate"[..]).unwrap_err(),
+        Error::BadDer
+    );
+    assert_eq!(
+        OwnedIntermediateCert::try_from(&b"not a certificate"[..]).unwrap_err(),
+        Error::BadDer
+    );
+}
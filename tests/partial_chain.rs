@@ -0,0 +1,75 @@
+#![cfg(all(feature = "alloc", feature = "ring"))]
+
+use webpki::{Error, KeyUsage, TrustAnchor, TrustAnchorStore, TrustAnchors, VerifyOptions};
+
+const EE: &[u8] = include_bytes!("partial_chain/ee.der");
+const EE_OTHER_KEY: &[u8] = include_bytes!("partial_chain/ee.other_key.der");
+const EE_SELF_SIGNED: &[u8] = include_bytes!("partial_chain/ee.self_signed.der");
+
+const TIME: u64 = 0x1fed_f00d;
+
+fn check<A: TrustAnchors + ?Sized>(
+    ee: &[u8],
+    anchors: &A,
+    options: &VerifyOptions,
+    time: u64,
+) -> Result<(), Error> {
+    let cert = webpki::EndEntityCert::try_from(ee).unwrap();
+    cert.verify_for_usage_with_options(
+        &[webpki::ECDSA_P256_SHA256],
+        anchors,
+        &[],
+        webpki::Time::from_seconds_since_unix_epoch(time),
+        KeyUsage::server_auth(),
+        None,
+        options,
+    )
+    .map(|_| ())
+    .map_err(Error::from)
+}
+
+#[test]
+fn end_entity_as_anchor() {
+    let partial = VerifyOptions::new().with_partial_chain();
+
+    let anchors = [TrustAnchor::try_from_cert_der(EE).unwrap()];
+    assert_eq!(check(EE, &anchors, &partial, TIME), Ok(()));
+
+    let store = [TrustAnchor::try_from_cert_der(EE).unwrap()]
+        .into_iter()
+        .collect::<TrustAnchorStore>();
+    assert_eq!(check(EE, &store, &partial, TIME), Ok(()));
+
+    let anchors = [TrustAnchor::try_from_cert_der(EE_SELF_SIGNED).unwrap()];
+    assert_eq!(check(EE_SELF_SIGNED, &anchors, &partial, TIME), Ok(()));
+}
+
+#[test]
+fn end_entity_as_anchor_requires_option() {
+    // The issuer of the certificate isn't trusted.
+    let anchors = [TrustAnchor::try_from_cert_der(EE).unwrap()];
+    assert_eq!(
+        check(EE, &anchors, &VerifyOptions::new(), TIME),
+        Err(Error::UnknownIssuer)
+    );
+}
+
+#[test]
+fn end_entity_as_anchor_requires_matching_key() {
+    let anchors = [TrustAnchor::try_from_cert_der(EE_OTHER_KEY).unwrap()];
+    let partial = VerifyOptions::new().with_partial_chain();
+    assert_eq!(
+        check(EE, &anchors, &partial, TIME),
+        Err(Error::UnknownIssuer)
+    );
+}
+
+#[test]
+fn end_entity_as_anchor_checks_validity() {
+    let anchors = [TrustAnchor::try_from_cert_der(EE).unwrap()];
+    let partial = VerifyOptions::new().with_partial_chain();
+    assert_eq!(
+        check(EE, &anchors, &partial, TIME + 3600),
+        Err(Error::CertExpired)
+    );
+}
@@ -0,0 +1,66 @@
+"""
+Generates end-entity certificates of the kind pinned directly as trust anchors: one issued by
+a CA that is not trusted, one with the same subject and a different key, and one self-signed.
+"""
+
+import functools
+import sys
+from pathlib import Path
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.x509.oid import ExtendedKeyUsageOID
+
+HERE = Path(__file__).parent
+sys.path.insert(0, str(HERE.parent))
+
+import testcerts  # noqa: E402
+from testcerts import NOT_AFTER, NOT_BEFORE, write_der  # noqa: E402
+
+name = functools.partial(testcerts.name, organization="partial chain")
+
+
+def end_entity(
+    key: ec.EllipticCurvePrivateKey,
+    issuer: x509.Name,
+    issuer_key: ec.EllipticCurvePrivateKey,
+) -> x509.Certificate:
+    return (
+        x509.CertificateBuilder()
+        .subject_name(name("service.cluster.internal"))
+        .issuer_name(issuer)
+        .public_key(key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=False, path_length=None), critical=True)
+        .add_extension(
+            x509.SubjectAlternativeName([x509.DNSName("service.cluster.internal")]),
+            critical=False,
+        )
+        .add_extension(
+            x509.ExtendedKeyUsage([ExtendedKeyUsageOID.SERVER_AUTH]), critical=False
+        )
+        .sign(issuer_key, hashes.SHA256())
+    )
+
+
+def generate(force: bool) -> None:
+    # The CA certificate itself is never needed, only its name and key.
+    ca_name = name("Untrusted CA")
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+
+    for file_name in ("ee", "ee.other_key"):
+        key = ec.generate_private_key(ec.SECP256R1())
+        cert = end_entity(key, ca_name, ca_key)
+        write_der(HERE / f"{file_name}.der", cert.public_bytes(Encoding.DER), force)
+
+    key = ec.generate_private_key(ec.SECP256R1())
+    cert = end_entity(key, name("service.cluster.internal"), key)
+    write_der(HERE / "ee.self_signed.der", cert.public_bytes(Encoding.DER), force)
+
+
+if __name__ == "__main__":
+    testcerts.main(generate)
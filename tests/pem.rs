@@ -0,0 +1,53 @@
+#![cfg(feature = "alloc")]
+
+use webpki::{pem_sections, PemSectionKind};
+
+#[test]
+fn pem_bundle() {
+    let bundle = include_bytes!("certdata/roots.pem");
+    let sections = pem_sections(bundle).collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert_eq!(
+        sections.iter().map(|s| s.kind()).collect::<Vec<_>>(),
+        [
+            Some(PemSectionKind::Certificate),
+            Some(PemSectionKind::Crl),
+            Some(PemSectionKind::Certificate),
+        ]
+    );
+    assert_eq!(
+        sections[0].decode().unwrap(),
+        include_bytes!("cloudflare_dns/ca.der")
+    );
+    assert_eq!(
+        sections[1].decode().unwrap(),
+        include_bytes!("crls/crl.valid.der")
+    );
+    assert_eq!(
+        sections[2].decode().unwrap(),
+        include_bytes!("netflix/ca.der")
+    );
+
+    for section in sections {
+        if section.kind() == Some(PemSectionKind::Certificate) {
+            let der = section.decode().unwrap();
+            webpki::TrustAnchor::try_from_cert_der(&der).unwrap();
+        }
+    }
+}
+
+#[test]
+fn pem_decode_into_buffer() {
+    let bundle = include_bytes!("certdata/roots.pem");
+    let section = pem_sections(bundle).next().unwrap().unwrap();
+
+    let mut buf = [0; 2048];
+    assert_eq!(
+        section.decode_into(&mut buf),
+        Ok(&include_bytes!("cloudflare_dns/ca.der")[..])
+    );
+    assert_eq!(
+        section.decode_into(&mut [0; 16]),
+        Err(webpki::Error::BadPem)
+    );
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "ring")]
+
+use webpki::{Error, RawPublicKeyEntity};
+
+// The keys are those of the certificates in `signatures/`, so their signature fixtures apply.
+const ED25519_SPKI: &[u8] = include_bytes!("raw_public_key/ed25519.spki.der");
+const ECDSA_P256_SPKI: &[u8] = include_bytes!("raw_public_key/ecdsa_p256.spki.der");
+const MESSAGE: &[u8] = include_bytes!("signatures/message.bin");
+
+#[test]
+fn pinned_key_with_good_signature() {
+    let key = RawPublicKeyEntity::try_from(ED25519_SPKI).unwrap();
+    assert_eq!(key.spki_der(), ED25519_SPKI);
+    assert_eq!(
+        key.verify_is_pinned(&[ECDSA_P256_SPKI, ED25519_SPKI]),
+        Ok(())
+    );
+
+    let signature = include_bytes!("signatures/ed25519_key_and_ed25519_good_signature.sig.bin");
+    assert_eq!(
+        key.verify_signature(webpki::ED25519, MESSAGE, signature),
+        Ok(())
+    );
+}
+
+#[test]
+fn unpinned_key() {
+    let key = RawPublicKeyEntity::try_from(ED25519_SPKI).unwrap();
+    assert_eq!(
+        key.verify_is_pinned(&[ECDSA_P256_SPKI]),
+        Err(Error::UnknownPublicKey)
+    );
+    assert_eq!(key.verify_is_pinned(&[]), Err(Error::UnknownPublicKey));
+}
+
+#[test]
+fn bad_signature() {
+    let key = RawPublicKeyEntity::try_from(ED25519_SPKI).unwrap();
+    let signature =
+        include_bytes!("signatures/ed25519_key_and_ed25519_detects_bad_signature.sig.bin");
+    assert_eq!(
+        key.verify_signature(webpki::ED25519, MESSAGE, signature),
+        Err(Error::InvalidSignatureForPublicKey)
+    );
+
+    let key = RawPublicKeyEntity::try_from(ECDSA_P256_SPKI).unwrap();
+    let signature =
+        include_bytes!("signatures/ecdsa_p256_key_and_ecdsa_p256_sha256_good_signature.sig.bin");
+    assert_eq!(
+        key.verify_signature(webpki::ECDSA_P256_SHA256, MESSAGE, signature),
+        Ok(())
+    );
+    assert_eq!(
+        key.verify_signature(webpki::ED25519, MESSAGE, signature),
+        Err(Error::UnsupportedSignatureAlgorithmForPublicKey)
+    );
+}
+
+#[test]
+fn malformed_spki() {
+    assert!(matches!(
+        RawPublicKeyEntity::try_from(&ED25519_SPKI[..ED25519_SPKI.len() - 1]),
+        Err(Error::BadDer)
+    ));
+    assert!(matches!(
+        RawPublicKeyEntity::try_from(include_bytes!("signatures/ed25519.ee.der").as_slice()),
+        Err(Error::BadDer)
+    ));
+}
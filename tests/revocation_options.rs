@@ -0,0 +1,282 @@
+#![cfg(feature = "ring")]
+
+mod common;
+
+use common::CRL_TIME;
+use webpki::{
+    CertRevocationList, Error, ExpirationPolicy, KeyUsage, RevocationCheckDepth, RevocationOptions,
+    RevocationStatus, TrustAnchor, UnknownStatusPolicy, VerifiedPath, VerifyError, VerifyOptions,
+};
+
+const ROOT: &[u8] = include_bytes!("crl_scope/root.ca.der");
+const INT: &[u8] = include_bytes!("crl_scope/int.ca.der");
+const EE: &[u8] = include_bytes!("crl_scope/ee.der");
+
+/// Revokes `EE`.
+const INT_CRL: &[u8] = include_bytes!("crl_scope/int.no_idp.crl.der");
+
+/// Revokes `INT`.
+const ROOT_CRL: &[u8] = include_bytes!("crl_scope/root.idp.only_ca_certs.crl.der");
+
+fn check(
+    crls: &[&[u8]],
+    depth: RevocationCheckDepth,
+    status_policy: UnknownStatusPolicy,
+) -> Result<(), Error> {
+    common::with_crls(crls, |crls| {
+        let revocation = RevocationOptions::new(crls)
+            .with_depth(depth)
+            .with_status_policy(status_policy);
+        common::check_revocation_with(ROOT, EE, &[INT], CRL_TIME, revocation)
+    })
+}
+
+#[test]
+fn chain_depth() {
+    let check = |crls: &[&[u8]]| {
+        check(
+            crls,
+            RevocationCheckDepth::Chain,
+            UnknownStatusPolicy::Allow,
+        )
+    };
+    assert_eq!(check(&[INT_CRL]), Err(Error::CertRevoked));
+    assert_eq!(check(&[ROOT_CRL]), Err(Error::CertRevoked));
+    assert_eq!(check(&[]), Ok(()));
+}
+
+#[test]
+fn end_entity_depth() {
+    let check = |crls: &[&[u8]]| {
+        check(
+            crls,
+            RevocationCheckDepth::EndEntity,
+            UnknownStatusPolicy::Allow,
+        )
+    };
+    assert_eq!(check(&[INT_CRL]), Err(Error::CertRevoked));
+
+    // The intermediate's revocation status isn't checked.
+    assert_eq!(check(&[ROOT_CRL]), Ok(()));
+}
+
+#[test]
+fn deny_unknown_status() {
+    let check =
+        |crls: &[&[u8]]| check(crls, RevocationCheckDepth::Chain, UnknownStatusPolicy::Deny);
+    assert_eq!(check(&[]), Err(Error::UnknownRevocationStatus));
+
+    // There's no CRL for the intermediate, which is checked before the end-entity certificate.
+    assert_eq!(check(&[INT_CRL]), Err(Error::UnknownRevocationStatus));
+    assert_eq!(check(&[INT_CRL, ROOT_CRL]), Err(Error::CertRevoked));
+}
+
+#[test]
+fn deny_unknown_status_at_end_entity_depth() {
+    let check = |crls: &[&[u8]]| {
+        check(
+            crls,
+            RevocationCheckDepth::EndEntity,
+            UnknownStatusPolicy::Deny,
+        )
+    };
+    assert_eq!(check(&[]), Err(Error::UnknownRevocationStatus));
+    assert_eq!(check(&[INT_CRL]), Err(Error::CertRevoked));
+}
+
+#[test]
+fn deny_unknown_status_for_uncovered_reasons() {
+    const CA: &[u8] = include_bytes!("crl_reasons/ca.der");
+    const NOT_REVOKED: &[u8] = include_bytes!("crl_reasons/ee.not_revoked.der");
+    const KEY_COMPROMISE: &[u8] = include_bytes!("crl_reasons/key_compromise.crl.der");
+    const OTHER_REASONS: &[u8] = include_bytes!("crl_reasons/other_reasons.crl.der");
+
+    let check = |crls: &[&[u8]]| {
+        common::with_crls(crls, |crls| {
+            let revocation =
+                RevocationOptions::new(crls).with_status_policy(UnknownStatusPolicy::Deny);
+            common::check_revocation_with(CA, NOT_REVOKED, &[], CRL_TIME, revocation)
+        })
+    };
+
+    assert_eq!(
+        check(&[KEY_COMPROMISE]),
+        Err(Error::UnknownRevocationStatus)
+    );
+    assert_eq!(check(&[KEY_COMPROMISE, OTHER_REASONS]), Ok(()));
+}
+
+#[test]
+fn expiration_policy() {
+    const CA: &[u8] = include_bytes!("crl_freshness/ca.der");
+    const EE: &[u8] = include_bytes!("crl_freshness/ee.der");
+    /// Its nextUpdate is 30 seconds after `CRL_TIME`.
+    const CRL: &[u8] = include_bytes!("crl_freshness/crl.der");
+
+    let check = |secs: u64, policy: ExpirationPolicy| {
+        let crl = common::crl(CRL);
+        let crls: &[&dyn CertRevocationList] = &[&crl];
+        let revocation = RevocationOptions::new(crls)
+            .with_status_policy(UnknownStatusPolicy::Deny)
+            .with_expiration_policy(policy);
+        common::check_revocation_with(CA, EE, &[], secs, revocation)
+    };
+
+    assert_eq!(check(CRL_TIME, ExpirationPolicy::Enforce), Ok(()));
+    assert_eq!(
+        check(CRL_TIME + 31, ExpirationPolicy::Enforce),
+        Err(Error::CrlExpired)
+    );
+
+    // An ignored CRL leaves the certificate's revocation status unknown.
+    assert_eq!(check(CRL_TIME, ExpirationPolicy::Ignore), Ok(()));
+    assert_eq!(
+        check(CRL_TIME + 31, ExpirationPolicy::Ignore),
+        Err(Error::UnknownRevocationStatus)
+    );
+}
+
+fn verify<'r>(
+    crls: &'r [&'r dyn CertRevocationList],
+    depth: RevocationCheckDepth,
+) -> Result<VerifiedPath, VerifyError<'r>> {
+    let anchors = [TrustAnchor::try_from_cert_der(ROOT).unwrap()];
+    webpki::EndEntityCert::try_from(EE)
+        .unwrap()
+        .verify_for_usage_with_options(
+            &[webpki::ECDSA_P256_SHA256],
+            &anchors[..],
+            &[INT],
+            webpki::Time::from_seconds_since_unix_epoch(CRL_TIME),
+            KeyUsage::client_auth(),
+            Some(RevocationOptions::new(crls).with_depth(depth)),
+            &VerifyOptions::new(),
+        )
+}
+
+#[test]
+fn revoked_cert_detail() {
+    common::with_crls(&[ROOT_CRL], |crls| {
+        let err = verify(crls, RevocationCheckDepth::Chain).unwrap_err();
+        assert_eq!(err.error(), Error::CertRevoked);
+        let revoked = err.revoked().unwrap();
+        assert_eq!(revoked.depth(), 1);
+        assert_eq!(revoked.crl_issuer(), crls[0].issuer());
+        assert_eq!(
+            revoked.entry().revocation_date,
+            webpki::Time::from_seconds_since_unix_epoch(CRL_TIME - 30)
+        );
+        assert_eq!(revoked.entry().reason_code, None);
+    });
+
+    common::with_crls(&[INT_CRL], |crls| {
+        let err = verify(crls, RevocationCheckDepth::Chain).unwrap_err();
+        assert_eq!(err.error(), Error::CertRevoked);
+        let revoked = err.revoked().unwrap();
+        assert_eq!(revoked.depth(), 0);
+        assert_eq!(revoked.crl_issuer(), crls[0].issuer());
+    });
+
+    // Only a failure because a certificate was revoked has the details of one.
+    common::with_crls(&[INT_CRL], |crls| {
+        let anchors = [TrustAnchor::try_from_cert_der(INT).unwrap()];
+        let err = webpki::EndEntityCert::try_from(EE)
+            .unwrap()
+            .verify_for_usage_with_options(
+                &[webpki::ECDSA_P256_SHA256],
+                &anchors[..],
+                &[],
+                webpki::Time::from_seconds_since_unix_epoch(CRL_TIME),
+                KeyUsage::server_auth(),
+                Some(RevocationOptions::new(crls)),
+                &VerifyOptions::new(),
+            )
+            .unwrap_err();
+        assert_ne!(err.error(), Error::CertRevoked);
+        assert!(err.revoked().is_none());
+    });
+}
+
+#[test]
+fn revoked_cert_detail_from_delta_crl() {
+    use webpki::RevocationReason;
+
+    const CA: &[u8] = include_bytes!("crl_delta/ca.der");
+    const REVOKED_IN_DELTA: &[u8] = include_bytes!("crl_delta/ee.revoked_in_delta.der");
+    const BASE_10: &[u8] = include_bytes!("crl_delta/base.10.crl.der");
+    /// Revokes `REVOKED_IN_DELTA` for keyCompromise.
+    const DELTA_11: &[u8] = include_bytes!("crl_delta/delta.11.crl.der");
+
+    let base = common::crl(BASE_10);
+    let delta = common::crl(DELTA_11);
+    let crls: &[&dyn CertRevocationList] = &[&base, &delta];
+
+    let anchors = [TrustAnchor::try_from_cert_der(CA).unwrap()];
+    let err = webpki::EndEntityCert::try_from(REVOKED_IN_DELTA)
+        .unwrap()
+        .verify_for_usage_with_options(
+            &[webpki::ECDSA_P256_SHA256],
+            &anchors[..],
+            &[],
+            webpki::Time::from_seconds_since_unix_epoch(CRL_TIME),
+            KeyUsage::client_auth(),
+            Some(RevocationOptions::new(crls)),
+            &VerifyOptions::new(),
+        )
+        .unwrap_err();
+    assert_eq!(err.error(), Error::CertRevoked);
+
+    let revoked = err.revoked().unwrap();
+    assert_eq!(revoked.depth(), 0);
+    assert_eq!(revoked.crl_issuer(), delta.issuer());
+    assert_eq!(
+        revoked.entry().reason_code,
+        Some(RevocationReason::KeyCompromise)
+    );
+    assert_eq!(revoked.entry().invalidity_date, None);
+}
+
+#[test]
+fn verified_path_revocation_status() {
+    use RevocationStatus::{NotChecked, NotRevoked, Unknown};
+
+    let path = verify(&[], RevocationCheckDepth::Chain).unwrap();
+    assert_eq!(path.revocation_status(), [Unknown, Unknown]);
+
+    // `ROOT_CRL` only covers CA certificates, so says nothing about `EE`.
+    common::with_crls(&[ROOT_CRL], |crls| {
+        let path = verify(crls, RevocationCheckDepth::EndEntity).unwrap();
+        assert_eq!(path.revocation_status(), [Unknown, NotChecked]);
+    });
+
+    const CA: &[u8] = include_bytes!("crl_delta/ca.der");
+    const NOT_REVOKED: &[u8] = include_bytes!("crl_delta/ee.not_revoked.der");
+    const BASE_10: &[u8] = include_bytes!("crl_delta/base.10.crl.der");
+
+    let anchors = [TrustAnchor::try_from_cert_der(CA).unwrap()];
+    let verify = |revocation| {
+        webpki::EndEntityCert::try_from(NOT_REVOKED)
+            .unwrap()
+            .verify_for_usage_with_options(
+                &[webpki::ECDSA_P256_SHA256],
+                &anchors[..],
+                &[],
+                webpki::Time::from_seconds_since_unix_epoch(CRL_TIME),
+                KeyUsage::client_auth(),
+                revocation,
+                &VerifyOptions::new(),
+            )
+            .unwrap()
+    };
+    let base = common::crl(BASE_10);
+    let crls: &[&dyn CertRevocationList] = &[&base];
+    let path = verify(Some(RevocationOptions::new(crls)));
+    assert_eq!(path.revocation_status(), [NotRevoked]);
+    assert_eq!(verify(None).revocation_status(), [NotChecked]);
+}
+
+#[test]
+fn revocation_options_are_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<RevocationOptions>();
+}
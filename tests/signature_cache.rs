@@ -0,0 +1,250 @@
+#![cfg(feature = "std")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use webpki::{
+    BoundedSignatureCache, Error, KeyUsage, RevocationOptions, SignatureCache, SignatureCacheKey,
+    TrustAnchor, VerifyOptions,
+};
+
+/// A [`BoundedSignatureCache`] counting the lookups that hit.
+struct CountingCache {
+    inner: BoundedSignatureCache,
+    hits: AtomicUsize,
+}
+
+impl CountingCache {
+    fn new() -> Self {
+        Self {
+            inner: BoundedSignatureCache::new(16),
+            hits: AtomicUsize::new(0),
+        }
+    }
+
+    fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+}
+
+impl SignatureCache for CountingCache {
+    fn contains(&self, key: &SignatureCacheKey) -> bool {
+        let found = self.inner.contains(key);
+        if found {
+            self.hits.fetch_add(1, Ordering::SeqCst);
+        }
+        found
+    }
+
+    fn insert(&self, key: SignatureCacheKey) {
+        self.inner.insert(key)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check(
+    algs: &[&dyn webpki::SignatureVerificationAlgorithm],
+    ee: &[u8],
+    intermediates: &[&[u8]],
+    ca: &[u8],
+    time: webpki::Time,
+    usage: KeyUsage,
+    crls: &[&dyn webpki::CertRevocationList],
+    cache: &dyn SignatureCache,
+) -> Result<(), Error> {
+    let anchors = [TrustAnchor::try_from_cert_der(ca).unwrap()];
+    webpki::EndEntityCert::try_from(ee)
+        .unwrap()
+        .verify_for_usage_with_options(
+            algs,
+            &anchors[..],
+            intermediates,
+            time,
+            usage,
+            Some(RevocationOptions::new(crls)),
+            &VerifyOptions::new().with_signature_cache(cache),
+        )
+        .map(|_| ())
+        .map_err(Error::from)
+}
+
+const EE: &[u8] = include_bytes!("cloudflare_dns/ee.der");
+const INTER: &[u8] = include_bytes!("cloudflare_dns/inter.der");
+const CA: &[u8] = include_bytes!("cloudflare_dns/ca.der");
+
+static ALGS: &[&dyn webpki::SignatureVerificationAlgorithm] = &[
+    webpki::ECDSA_P256_SHA256,
+    webpki::ECDSA_P384_SHA384,
+    webpki::RSA_PKCS1_2048_8192_SHA384,
+];
+
+fn time() -> webpki::Time {
+    webpki::Time::from_seconds_since_unix_epoch(1_663_495_771)
+}
+
+#[test]
+fn cached_certificate_signatures() {
+    let cache = CountingCache::new();
+    let verify = |algs| {
+        check(
+            algs,
+            EE,
+            &[INTER],
+            CA,
+            time(),
+            KeyUsage::server_auth(),
+            &[],
+            &cache,
+        )
+    };
+
+    assert_eq!(verify(ALGS), Ok(()));
+    assert_eq!(cache.hits(), 0);
+    assert_eq!(cache.inner.len(), 2);
+
+    assert_eq!(verify(ALGS), Ok(()));
+    assert_eq!(cache.hits(), 2);
+    assert_eq!(cache.inner.len(), 2);
+
+    // A cached signature still needs a supported algorithm.
+    assert_eq!(
+        verify(&[webpki::ECDSA_P256_SHA256, webpki::ECDSA_P384_SHA384]),
+        Err(Error::UnsupportedSignatureAlgorithm)
+    );
+}
+
+#[test]
+fn cached_crl_signatures() {
+    let cache = CountingCache::new();
+    let crl = webpki::BorrowedCertRevocationList::from_der(include_bytes!(
+        "client_auth_revocation/ee_not_revoked_ee_depth.crl.der"
+    ))
+    .unwrap();
+    let verify = || {
+        check(
+            &[webpki::ECDSA_P256_SHA256],
+            include_bytes!("client_auth_revocation/no_ku_chain.ee.der"),
+            &[
+                include_bytes!("client_auth_revocation/no_ku_chain.int.a.ca.der"),
+                include_bytes!("client_auth_revocation/no_ku_chain.int.b.ca.der"),
+            ],
+            include_bytes!("client_auth_revocation/no_ku_chain.root.ca.der"),
+            webpki::Time::from_seconds_since_unix_epoch(0x1fed_f00d),
+            KeyUsage::client_auth(),
+            &[&crl],
+            &cache,
+        )
+    };
+
+    assert_eq!(verify(), Ok(()));
+    assert_eq!(cache.hits(), 0);
+    // Three certificate signatures, and the CRL's.
+    assert_eq!(cache.inner.len(), 4);
+
+    assert_eq!(verify(), Ok(()));
+    assert_eq!(cache.hits(), 4);
+}
+
+#[test]
+fn cached_signatures_respect_key_size_limits() {
+    let cache = CountingCache::new();
+    let verify = |algs| {
+        check(
+            algs,
+            EE,
+            &[INTER],
+            CA,
+            time(),
+            KeyUsage::server_auth(),
+            &[],
+            &cache,
+        )
+    };
+    assert_eq!(verify(ALGS), Ok(()));
+
+    // The root has a 2048-bit RSA key, which the intermediate's signature was verified with
+    // above. That success mustn't be reused by an algorithm requiring larger keys.
+    assert_eq!(
+        verify(&[
+            webpki::ECDSA_P256_SHA256,
+            webpki::ECDSA_P384_SHA384,
+            webpki::RSA_PKCS1_3072_8192_SHA384,
+        ]),
+        Err(Error::InvalidSignatureForPublicKey)
+    );
+    assert_eq!(cache.hits(), 0);
+}
+
+/// A zero-sized P-256 algorithm that accepts every signature when `LENIENT`, and none otherwise.
+struct FixedOutcome<const LENIENT: bool, const CACHED: bool>;
+
+impl<const LENIENT: bool, const CACHED: bool> webpki::SignatureVerificationAlgorithm
+    for FixedOutcome<LENIENT, CACHED>
+{
+    fn public_key_alg_id(&self) -> webpki::alg_id::AlgorithmIdentifier {
+        webpki::alg_id::ECDSA_P256
+    }
+
+    fn signature_alg_id(&self) -> webpki::alg_id::AlgorithmIdentifier {
+        webpki::alg_id::ECDSA_SHA256
+    }
+
+    fn cache_id(&self) -> Option<&'static str> {
+        match (CACHED, LENIENT) {
+            (false, _) => None,
+            (true, true) => Some("tests::Lenient"),
+            (true, false) => Some("tests::Strict"),
+        }
+    }
+
+    fn verify_signature(
+        &self,
+        _public_key: &[u8],
+        _message: &[u8],
+        _signature: &[u8],
+    ) -> Result<(), webpki::InvalidSignature> {
+        match LENIENT {
+            true => Ok(()),
+            false => Err(webpki::InvalidSignature),
+        }
+    }
+}
+
+#[test]
+fn cached_signatures_are_keyed_by_algorithm_identity() {
+    // Zero-sized statics needn't have distinct addresses, so they must be told apart by name.
+    static LENIENT: FixedOutcome<true, true> = FixedOutcome;
+    static STRICT: FixedOutcome<false, true> = FixedOutcome;
+    static UNCACHED: FixedOutcome<true, false> = FixedOutcome;
+
+    let cache = CountingCache::new();
+    let verify = |alg: &dyn webpki::SignatureVerificationAlgorithm| {
+        check(
+            &[alg],
+            include_bytes!("client_auth_revocation/no_ku_chain.ee.der"),
+            &[
+                include_bytes!("client_auth_revocation/no_ku_chain.int.a.ca.der"),
+                include_bytes!("client_auth_revocation/no_ku_chain.int.b.ca.der"),
+            ],
+            include_bytes!("client_auth_revocation/no_ku_chain.root.ca.der"),
+            webpki::Time::from_seconds_since_unix_epoch(0x1fed_f00d),
+            KeyUsage::client_auth(),
+            &[],
+            &cache,
+        )
+    };
+
+    // Verifications by an algorithm without a name aren't cached.
+    assert_eq!(verify(&UNCACHED), Ok(()));
+    assert_eq!(cache.inner.len(), 0);
+
+    assert_eq!(verify(&LENIENT), Ok(()));
+    assert_eq!(cache.inner.len(), 3);
+
+    // The strict algorithm has the same identifiers, but mustn't reuse the lenient one's
+    // successes.
+    assert_eq!(verify(&STRICT), Err(Error::InvalidSignatureForPublicKey));
+    assert_eq!(cache.hits(), 0);
+
+    assert_eq!(verify(&LENIENT), Ok(()));
+    assert_eq!(cache.hits(), 3);
+}
@@ -0,0 +1,157 @@
+#![cfg(all(feature = "alloc", feature = "ring"))]
+
+use ring::digest;
+use webpki::{DnsNameRef, KeyUsage, SpkiPins, SpkiSha256, TrustAnchor, VerifyOptions};
+
+fn check_cloudflare(
+    anchors: &[TrustAnchor],
+    pins: &SpkiPins,
+    host: &str,
+) -> Result<Option<SpkiSha256>, webpki::Error> {
+    let ee: &[u8] = include_bytes!("cloudflare_dns/ee.der");
+    let inter: &[u8] = include_bytes!("cloudflare_dns/inter.der");
+    let time = webpki::Time::from_seconds_since_unix_epoch(1_663_495_771);
+
+    let cert = webpki::EndEntityCert::try_from(ee).unwrap();
+    cert.verify_for_usage_with_options(
+        &[
+            webpki::ECDSA_P256_SHA256,
+            webpki::ECDSA_P384_SHA384,
+            webpki::RSA_PKCS1_2048_8192_SHA384,
+        ],
+        anchors,
+        &[inter],
+        time,
+        KeyUsage::server_auth(),
+        None,
+        &VerifyOptions::new().with_spki_pins(pins, Some(dns_name(host))),
+    )
+    .map(|path| path.spki_pin().copied())
+    .map_err(webpki::Error::from)
+}
+
+fn dns_name(host: &str) -> DnsNameRef<'_> {
+    DnsNameRef::try_from_ascii_str(host).unwrap()
+}
+
+fn pin(spki_der: &[u8]) -> SpkiSha256 {
+    let mut pin = [0; 32];
+    pin.copy_from_slice(digest::digest(&digest::SHA256, spki_der).as_ref());
+    pin
+}
+
+const EE_SPKI: &[u8] = include_bytes!("spki_pins/cloudflare_ee.spki.der");
+const INTER_SPKI: &[u8] = include_bytes!("spki_pins/cloudflare_inter.spki.der");
+const CA_SPKI: &[u8] = include_bytes!("spki_pins/cloudflare_ca.spki.der");
+
+fn anchors() -> [TrustAnchor<'static>; 1] {
+    [TrustAnchor::try_from_cert_der(include_bytes!("cloudflare_dns/ca.der")).unwrap()]
+}
+
+#[test]
+fn no_applicable_pins() {
+    assert_eq!(
+        check_cloudflare(&anchors(), &SpkiPins::new(), "cloudflare-dns.com"),
+        Ok(None)
+    );
+
+    // Pins for other hosts don't apply.
+    let mut pins = SpkiPins::new();
+    pins.add_for_host(dns_name("example.com"), pin(b"unrelated"));
+    assert_eq!(
+        check_cloudflare(&anchors(), &pins, "cloudflare-dns.com"),
+        Ok(None)
+    );
+}
+
+#[test]
+fn any_key_in_path_matches() {
+    for spki in [EE_SPKI, INTER_SPKI, CA_SPKI] {
+        let mut pins = SpkiPins::new();
+        pins.add_global(pin(b"unrelated"));
+        pins.add_global(pin(spki));
+        assert_eq!(
+            check_cloudflare(&anchors(), &pins, "cloudflare-dns.com"),
+            Ok(Some(pin(spki)))
+        );
+    }
+}
+
+#[test]
+fn unpinned_path_rejected() {
+    let mut pins = SpkiPins::new();
+    pins.add_for_host(dns_name("cloudflare-dns.com"), pin(b"unrelated"));
+    assert_eq!(
+        check_cloudflare(&anchors(), &pins, "cloudflare-dns.com"),
+        Err(webpki::Error::SpkiPinMismatch)
+    );
+    assert_eq!(check_cloudflare(&anchors(), &pins, "example.com"), Ok(None));
+}
+
+#[test]
+fn pinning_selects_between_paths() {
+    // With the intermediate also trusted, the shortest path doesn't include the root.
+    let anchors = [
+        TrustAnchor::try_from_cert_der(include_bytes!("cloudflare_dns/inter.der")).unwrap(),
+        TrustAnchor::try_from_cert_der(include_bytes!("cloudflare_dns/ca.der")).unwrap(),
+    ];
+
+    let mut pins = SpkiPins::new();
+    pins.add_global(pin(CA_SPKI));
+    assert_eq!(
+        check_cloudflare(&anchors, &pins, "cloudflare-dns.com"),
+        Ok(Some(pin(CA_SPKI)))
+    );
+
+    // Without the root as an anchor, no path contains the pinned key.
+    assert_eq!(
+        check_cloudflare(&anchors[..1], &pins, "cloudflare-dns.com"),
+        Err(webpki::Error::SpkiPinMismatch)
+    );
+}
+
+#[test]
+fn pinned_partial_chain_falls_back_to_path_building() {
+    let ee: &[u8] = include_bytes!("cloudflare_dns/ee.der");
+    let inter: &[u8] = include_bytes!("cloudflare_dns/inter.der");
+    let anchors = [
+        TrustAnchor::try_from_cert_der(ee).unwrap(),
+        TrustAnchor::try_from_cert_der(include_bytes!("cloudflare_dns/ca.der")).unwrap(),
+    ];
+
+    let check = |pins: &SpkiPins| {
+        webpki::EndEntityCert::try_from(ee)
+            .unwrap()
+            .verify_for_usage_with_options(
+                &[
+                    webpki::ECDSA_P256_SHA256,
+                    webpki::ECDSA_P384_SHA384,
+                    webpki::RSA_PKCS1_2048_8192_SHA384,
+                ],
+                &anchors[..],
+                &[inter],
+                webpki::Time::from_seconds_since_unix_epoch(1_663_495_771),
+                KeyUsage::server_auth(),
+                None,
+                &VerifyOptions::new()
+                    .with_partial_chain()
+                    .with_spki_pins(pins, None),
+            )
+            .map(|path| path.spki_pin().copied())
+            .map_err(webpki::Error::from)
+    };
+
+    let mut pins = SpkiPins::new();
+    pins.add_global(pin(EE_SPKI));
+    assert_eq!(check(&pins), Ok(Some(pin(EE_SPKI))));
+
+    // The end-entity certificate is trusted directly, but isn't pinned; the path through the
+    // intermediate to the pinned root is used instead.
+    let mut pins = SpkiPins::new();
+    pins.add_global(pin(CA_SPKI));
+    assert_eq!(check(&pins), Ok(Some(pin(CA_SPKI))));
+
+    let mut pins = SpkiPins::new();
+    pins.add_global(pin(b"unrelated"));
+    assert_eq!(check(&pins), Err(webpki::Error::SpkiPinMismatch));
+}
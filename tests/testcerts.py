@@ -0,0 +1,123 @@
+"""
+Helpers shared by the make_testcerts.py scripts, which generate the certificates and CRLs for
+a test in the directory of the same name. Each script is run from its own directory, and
+imports this module from the parent directory.
+"""
+
+import argparse
+import datetime
+from pathlib import Path
+from typing import Callable, FrozenSet, Iterable, Optional
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.x509.oid import NameOID
+
+NOT_BEFORE: datetime.datetime = datetime.datetime.utcfromtimestamp(0x1FEDF00D - 30)
+NOT_AFTER: datetime.datetime = datetime.datetime.utcfromtimestamp(0x1FEDF00D + 30)
+
+
+def write_der(path: Path, content: bytes, force: bool) -> None:
+    # Avoid churn from regenerating existing on-disk resources unless force is enabled.
+    if path.exists() and not force:
+        return None
+
+    with path.open("wb") as f:
+        f.write(content)
+
+
+def name(common_name: str, organization: str) -> x509.Name:
+    return x509.Name(
+        [
+            x509.NameAttribute(NameOID.COMMON_NAME, common_name),
+            x509.NameAttribute(NameOID.ORGANIZATION_NAME, organization),
+        ]
+    )
+
+
+def issuing_distribution_point(
+    uri: Optional[str],
+    only_user_certs: bool = False,
+    only_ca_certs: bool = False,
+    only_some_reasons: Optional[FrozenSet[x509.ReasonFlags]] = None,
+    indirect_crl: bool = False,
+) -> x509.IssuingDistributionPoint:
+    # Without a URI, the issuing distribution point is only limited by its other fields.
+    return x509.IssuingDistributionPoint(
+        full_name=None if uri is None else [x509.UniformResourceIdentifier(uri)],
+        relative_name=None,
+        only_contains_user_certs=only_user_certs,
+        only_contains_ca_certs=only_ca_certs,
+        only_some_reasons=only_some_reasons,
+        indirect_crl=indirect_crl,
+        only_contains_attribute_certs=False,
+    )
+
+
+def revoked_cert(
+    serial: int,
+    reason: Optional[x509.ReasonFlags] = None,
+    certificate_issuer: Optional[x509.Name] = None,
+) -> x509.RevokedCertificate:
+    builder = x509.RevokedCertificateBuilder().serial_number(serial).revocation_date(NOT_BEFORE)
+    if reason is not None:
+        builder = builder.add_extension(x509.CRLReason(reason), critical=False)
+    # Entries without a certificate issuer are from the issuer of the previous entry, or the
+    # CRL's issuer if there is none.
+    if certificate_issuer is not None:
+        builder = builder.add_extension(
+            x509.CertificateIssuer([x509.DirectoryName(certificate_issuer)]),
+            critical=True,
+        )
+    return builder.build()
+
+
+def crl(
+    issuer: x509.Name,
+    signing_key: ec.EllipticCurvePrivateKey,
+    revoked: Iterable[x509.RevokedCertificate] = (),
+    number: int = 1,
+    authority_key: Optional[ec.EllipticCurvePublicKey] = None,
+    base_number: Optional[int] = None,
+    idp: Optional[x509.IssuingDistributionPoint] = None,
+    this_update: datetime.datetime = NOT_BEFORE,
+    next_update: datetime.datetime = NOT_AFTER,
+) -> bytes:
+    """
+    A DER-encoded CRL from `issuer`, signed by `signing_key`, with the cRLNumber `number`. It has
+    an authority key identifier for `authority_key`, a delta CRL indicator for `base_number`, and
+    the issuing distribution point `idp`, if given.
+    """
+    builder = (
+        x509.CertificateRevocationListBuilder()
+        .issuer_name(issuer)
+        .last_update(this_update)
+        .next_update(next_update)
+        .add_extension(x509.CRLNumber(number), critical=False)
+    )
+    if authority_key is not None:
+        builder = builder.add_extension(
+            x509.AuthorityKeyIdentifier.from_issuer_public_key(authority_key),
+            critical=False,
+        )
+    if base_number is not None:
+        builder = builder.add_extension(x509.DeltaCRLIndicator(base_number), critical=True)
+    if idp is not None:
+        builder = builder.add_extension(idp, critical=True)
+    for entry in revoked:
+        builder = builder.add_revoked_certificate(entry)
+    return builder.sign(signing_key, hashes.SHA256()).public_bytes(Encoding.DER)
+
+
+def main(generate: Callable[[bool], None]) -> None:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--force",
+        action="store_true",
+        default=False,
+        help="Overwrite existing test files",
+    )
+    args = parser.parse_args()
+    generate(args.force)
@@ -0,0 +1,94 @@
+#![cfg(feature = "std")]
+
+use std::path::Path;
+
+use webpki::{
+    generate_trust_anchors_source, load_trust_anchors_from_dir, Error, OwnedTrustAnchor,
+    TrustAnchor,
+};
+
+// Generated by `generate_trust_anchors_source("webpki", "ROOTS", ..)` from the certificates in
+// tests/certdata.
+include!("certdata/roots.rs");
+
+fn certdata_dir() -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/certdata")
+}
+
+#[test]
+fn load_from_dir() {
+    let anchors = load_trust_anchors_from_dir(&certdata_dir()).unwrap();
+    assert_eq!(
+        anchors,
+        [
+            OwnedTrustAnchor::try_from_cert_der(include_bytes!("cloudflare_dns/ca.der")).unwrap(),
+            OwnedTrustAnchor::try_from_cert_der(include_bytes!("netflix/ca.der")).unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn generated_source() {
+    let anchors = load_trust_anchors_from_dir(&certdata_dir()).unwrap();
+    assert_eq!(
+        generate_trust_anchors_source("webpki", "ROOTS", &anchors).as_deref(),
+        Ok(include_str!("certdata/roots.rs"))
+    );
+
+    // The static produced from that source round-trips.
+    assert_eq!(
+        ROOTS.iter().map(OwnedTrustAnchor::from).collect::<Vec<_>>(),
+        anchors
+    );
+}
+
+#[test]
+fn generated_source_names() {
+    let anchors = load_trust_anchors_from_dir(&certdata_dir()).unwrap();
+    let source = generate_trust_anchors_source("::reexported::webpki", "_ROOTS", &anchors).unwrap();
+    assert!(source.starts_with("pub static _ROOTS: &[::reexported::webpki::TrustAnchor<'static>]"));
+    assert!(source.contains("    ::reexported::webpki::TrustAnchor {\n"));
+    assert!(generate_trust_anchors_source("crate::webpki", "ROOTS", &anchors).is_ok());
+
+    for (crate_path, name) in [
+        ("webpki", ""),
+        ("webpki", "1ROOTS"),
+        ("webpki", "static"),
+        ("webpki", "_"),
+        ("webpki", "ROOTS: &[u8] = &[]; pub static OTHER"),
+        ("", "ROOTS"),
+        ("webpki::", "ROOTS"),
+        ("::crate", "ROOTS"),
+        ("webpki::self", "ROOTS"),
+        ("webpki;", "ROOTS"),
+    ] {
+        assert_eq!(
+            generate_trust_anchors_source(crate_path, name, &anchors),
+            Err(Error::InvalidRustIdentifier),
+            "{:?} {:?}",
+            crate_path,
+            name
+        );
+    }
+}
+
+#[test]
+fn owned_trust_anchor_conversions() {
+    let ca = include_bytes!("cloudflare_dns/ca.der");
+    let anchor = TrustAnchor::try_from_cert_der(ca).unwrap();
+    let owned = OwnedTrustAnchor::from(&anchor);
+
+    let borrowed = owned.to_trust_anchor();
+    assert_eq!(borrowed.subject, anchor.subject);
+    assert_eq!(borrowed.spki, anchor.spki);
+    assert_eq!(borrowed.name_constraints, anchor.name_constraints);
+
+    assert_eq!(
+        OwnedTrustAnchor::from_subject_spki_name_constraints(
+            anchor.subject,
+            anchor.spki,
+            anchor.name_constraints,
+        ),
+        owned
+    );
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "ring")]
+
+#[cfg(feature = "alloc")]
+use webpki::TrustAnchorStore;
+use webpki::{Error, KeyUsage, TrustAnchor, TrustAnchorInfo, TrustAnchors};
+
+fn check<A: TrustAnchors + ?Sized>(anchors: &A, ee: &[u8]) -> Result<(), Error> {
+    let inter = include_bytes!("trust_anchor_info/inter.der");
+    let time = webpki::Time::from_seconds_since_unix_epoch(0x1fed_f00d);
+
+    let cert = webpki::EndEntityCert::try_from(ee).unwrap();
+    cert.verify_for_usage(
+        &[webpki::ECDSA_P256_SHA256],
+        anchors,
+        &[inter],
+        time,
+        KeyUsage::server_auth(),
+        None,
+    )
+}
+
+#[cfg(feature = "alloc")]
+fn tai(der: &[u8]) -> TrustAnchorStore<'_> {
+    let mut store = TrustAnchorStore::new();
+    store.add_trust_anchor_info_der(der).unwrap();
+    store
+}
+
+const EE: &[u8] = include_bytes!("trust_anchor_info/ee.der");
+#[cfg(feature = "alloc")]
+const EE_NO_POLICY: &[u8] = include_bytes!("trust_anchor_info/ee.no_policy.der");
+
+#[test]
+fn subject_spki_anchor() {
+    let anchor = TrustAnchor::try_from_subject_spki_der(
+        include_bytes!("trust_anchor_info/root.name.der"),
+        include_bytes!("trust_anchor_info/root.spki.der"),
+    )
+    .unwrap();
+
+    let from_cert =
+        TrustAnchor::try_from_cert_der(include_bytes!("trust_anchor_info/root.der")).unwrap();
+    assert_eq!(anchor.subject, from_cert.subject);
+    assert_eq!(anchor.spki, from_cert.spki);
+    assert_eq!(check(&[anchor], EE), Ok(()));
+
+    assert_eq!(
+        TrustAnchor::try_from_subject_spki_der(b"", b"").unwrap_err(),
+        Error::BadDer
+    );
+}
+
+#[test]
+fn trust_anchor_info_from_der() {
+    let info = TrustAnchorInfo::from_der(include_bytes!("trust_anchor_info/tai.der")).unwrap();
+    let anchor = info.trust_anchor();
+    let from_cert =
+        TrustAnchor::try_from_cert_der(include_bytes!("trust_anchor_info/root.der")).unwrap();
+    assert_eq!(anchor.subject, from_cert.subject);
+    assert_eq!(anchor.spki, from_cert.spki);
+    assert_eq!(anchor.name_constraints, None);
+    assert_eq!(check(&[TrustAnchor { ..*anchor }], EE), Ok(()));
+
+    // The certPath controls start with the name of the trust anchor.
+    assert!(info.cert_path_controls().starts_with(&[0x30]));
+
+    let info =
+        TrustAnchorInfo::from_der(include_bytes!("trust_anchor_info/tai.name_constraints.der"))
+            .unwrap();
+    assert!(info.trust_anchor().name_constraints.is_some());
+
+    // DER forbids encoding the DEFAULT version.
+    assert_eq!(
+        TrustAnchorInfo::from_der(include_bytes!("trust_anchor_info/tai.explicit_version.der"))
+            .unwrap_err(),
+        Error::BadDer
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn trust_anchor_info() {
+    let store = tai(include_bytes!("trust_anchor_info/tai.der"));
+    assert_eq!(store.len(), 1);
+    assert_eq!(check(&store, EE), Ok(()));
+
+    // A certificate is not a TrustAnchorInfo.
+    assert_eq!(
+        TrustAnchorStore::new()
+            .add_trust_anchor_info_der(include_bytes!("trust_anchor_info/root.der"))
+            .unwrap_err(),
+        Error::BadDer
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn trust_anchor_info_policy_set() {
+    // Without requireExplicitPolicy, the policy set only narrows the policies a path is valid
+    // for, so doesn't reject paths.
+    let der = include_bytes!("trust_anchor_info/tai.policy_one.der");
+    assert_eq!(check(&tai(der), EE), Ok(()));
+    assert_eq!(check(&tai(der), EE_NO_POLICY), Ok(()));
+
+    let der = include_bytes!("trust_anchor_info/tai.policy_two.der");
+    assert_eq!(check(&tai(der), EE), Ok(()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn trust_anchor_info_require_explicit_policy() {
+    let der = include_bytes!("trust_anchor_info/tai.require_explicit_policy.der");
+    assert_eq!(check(&tai(der), EE), Ok(()));
+    assert_eq!(
+        check(&tai(der), EE_NO_POLICY),
+        Err(Error::RequiredPolicyNotFound)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn trust_anchor_info_policy_set_require_explicit_policy() {
+    let der = include_bytes!("trust_anchor_info/tai.policy_two.require_explicit_policy.der");
+    assert_eq!(check(&tai(der), EE), Err(Error::RequiredPolicyNotFound));
+    assert_eq!(
+        check(&tai(der), EE_NO_POLICY),
+        Err(Error::RequiredPolicyNotFound)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn trust_anchor_info_path_len_constraint() {
+    let der = include_bytes!("trust_anchor_info/tai.path_len_zero.der");
+    assert_eq!(check(&tai(der), EE), Err(Error::PathLenConstraintViolated));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn trust_anchor_info_name_constraints() {
+    let store = tai(include_bytes!("trust_anchor_info/tai.name_constraints.der"));
+    assert_eq!(check(&store, EE), Err(Error::NameConstraintViolation));
+}
@@ -0,0 +1,160 @@
+"""
+Generates a root, intermediate and end-entity certificates asserting certificate policies,
+along with RFC 5914 TrustAnchorInfo encodings of the root carrying various certPath controls.
+"""
+
+import functools
+import hashlib
+import sys
+from pathlib import Path
+from typing import List, Optional
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding, PublicFormat
+
+HERE = Path(__file__).parent
+sys.path.insert(0, str(HERE.parent))
+
+import testcerts  # noqa: E402
+from testcerts import NOT_AFTER, NOT_BEFORE, write_der  # noqa: E402
+
+POLICY_ONE = x509.ObjectIdentifier("1.3.6.1.4.1.55555.1")
+POLICY_TWO = x509.ObjectIdentifier("1.3.6.1.4.1.55555.2")
+
+name = functools.partial(testcerts.name, organization="trust anchor info")
+
+
+def tlv(tag: int, value: bytes) -> bytes:
+    if len(value) < 0x80:
+        length = bytes([len(value)])
+    else:
+        encoded = len(value).to_bytes((len(value).bit_length() + 7) // 8, "big")
+        length = bytes([0x80 | len(encoded)]) + encoded
+    return bytes([tag]) + length + value
+
+
+def retag(tag: int, der: bytes) -> bytes:
+    # Re-tag the outer element of `der`, as needed for IMPLICIT tagging.
+    return bytes([tag]) + der[1:]
+
+
+def policies(oids: List[x509.ObjectIdentifier]) -> x509.CertificatePolicies:
+    return x509.CertificatePolicies([x509.PolicyInformation(oid, None) for oid in oids])
+
+
+def cert(
+    subject: x509.Name,
+    key: ec.EllipticCurvePrivateKey,
+    issuer: x509.Name,
+    issuer_key: ec.EllipticCurvePrivateKey,
+    ca: bool,
+    cert_policies: Optional[List[x509.ObjectIdentifier]],
+) -> x509.Certificate:
+    builder = (
+        x509.CertificateBuilder()
+        .subject_name(subject)
+        .issuer_name(issuer)
+        .public_key(key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=ca, path_length=None), critical=True)
+    )
+    if not ca:
+        builder = builder.add_extension(
+            x509.SubjectAlternativeName([x509.DNSName("ee.example.com")]), critical=False
+        ).add_extension(
+            x509.ExtendedKeyUsage([x509.oid.ExtendedKeyUsageOID.SERVER_AUTH]), critical=False
+        )
+    if cert_policies is not None:
+        builder = builder.add_extension(policies(cert_policies), critical=False)
+    return builder.sign(issuer_key, hashes.SHA256())
+
+
+def trust_anchor_info(
+    root: x509.Certificate,
+    policy_set: Optional[List[x509.ObjectIdentifier]] = None,
+    policy_flags: Optional[bytes] = None,
+    name_constraints: Optional[x509.NameConstraints] = None,
+    path_len: Optional[int] = None,
+    version: Optional[int] = None,
+) -> bytes:
+    spki = root.public_key().public_bytes(Encoding.DER, PublicFormat.SubjectPublicKeyInfo)
+    key_id = hashlib.sha1(spki).digest()
+
+    controls = root.subject.public_bytes()
+    if policy_set is not None:
+        controls += retag(0xA1, policies(policy_set).public_bytes())
+    if policy_flags is not None:
+        controls += tlv(0x82, policy_flags)
+    if name_constraints is not None:
+        controls += retag(0xA3, name_constraints.public_bytes())
+    if path_len is not None:
+        controls += tlv(0x84, bytes([path_len]))
+
+    # The DEFAULT version is only encoded to produce invalid DER.
+    encoded_version = tlv(0x02, bytes([version])) if version is not None else b""
+    return tlv(
+        0x30,
+        encoded_version + spki + tlv(0x04, key_id) + tlv(0x0C, b"test root") + tlv(0x30, controls),
+    )
+
+
+def generate(force: bool) -> None:
+    root_key = ec.generate_private_key(ec.SECP256R1())
+    inter_key = ec.generate_private_key(ec.SECP256R1())
+    ee_key = ec.generate_private_key(ec.SECP256R1())
+
+    root_name, inter_name = name("root"), name("intermediate")
+    root = cert(root_name, root_key, root_name, root_key, True, None)
+    inter = cert(inter_name, inter_key, root_name, root_key, True, [POLICY_ONE])
+    ee = cert(name("ee"), ee_key, inter_name, inter_key, False, [POLICY_ONE])
+    ee_no_policy = cert(name("ee"), ee_key, inter_name, inter_key, False, None)
+
+    write_der(HERE / "root.der", root.public_bytes(Encoding.DER), force)
+    write_der(HERE / "root.name.der", root.subject.public_bytes(), force)
+    write_der(
+        HERE / "root.spki.der",
+        root.public_key().public_bytes(Encoding.DER, PublicFormat.SubjectPublicKeyInfo),
+        force,
+    )
+    write_der(HERE / "inter.der", inter.public_bytes(Encoding.DER), force)
+    write_der(HERE / "ee.der", ee.public_bytes(Encoding.DER), force)
+    write_der(HERE / "ee.no_policy.der", ee_no_policy.public_bytes(Encoding.DER), force)
+
+    write_der(HERE / "tai.der", trust_anchor_info(root), force)
+    write_der(HERE / "tai.explicit_version.der", trust_anchor_info(root, version=1), force)
+    write_der(
+        HERE / "tai.policy_one.der", trust_anchor_info(root, policy_set=[POLICY_ONE]), force
+    )
+    write_der(
+        HERE / "tai.policy_two.der", trust_anchor_info(root, policy_set=[POLICY_TWO]), force
+    )
+    # requireExplicitPolicy (1): two bits used, six unused.
+    write_der(
+        HERE / "tai.require_explicit_policy.der",
+        trust_anchor_info(root, policy_flags=bytes([0x06, 0x40])),
+        force,
+    )
+    write_der(
+        HERE / "tai.policy_two.require_explicit_policy.der",
+        trust_anchor_info(root, policy_set=[POLICY_TWO], policy_flags=bytes([0x06, 0x40])),
+        force,
+    )
+    write_der(HERE / "tai.path_len_zero.der", trust_anchor_info(root, path_len=0), force)
+    write_der(
+        HERE / "tai.name_constraints.der",
+        trust_anchor_info(
+            root,
+            name_constraints=x509.NameConstraints(
+                permitted_subtrees=[x509.DNSName("example.org")], excluded_subtrees=None
+            ),
+        ),
+        force,
+    )
+
+
+if __name__ == "__main__":
+    testcerts.main(generate)
@@ -0,0 +1,67 @@
+#![cfg(all(feature = "alloc", feature = "ring"))]
+
+use webpki::{KeyUsage, TrustAnchor, TrustAnchorStore, TrustAnchors};
+
+fn check_cloudflare<A: TrustAnchors + ?Sized>(anchors: &A) -> Result<(), webpki::Error> {
+    let ee: &[u8] = include_bytes!("cloudflare_dns/ee.der");
+    let inter: &[u8] = include_bytes!("cloudflare_dns/inter.der");
+    let time = webpki::Time::from_seconds_since_unix_epoch(1_663_495_771);
+
+    let cert = webpki::EndEntityCert::try_from(ee).unwrap();
+    cert.verify_for_usage(
+        &[
+            webpki::ECDSA_P256_SHA256,
+            webpki::ECDSA_P384_SHA384,
+            webpki::RSA_PKCS1_2048_8192_SHA384,
+        ],
+        anchors,
+        &[inter],
+        time,
+        KeyUsage::server_auth(),
+        None,
+    )
+}
+
+#[test]
+fn trust_anchor_store_finds_issuer() {
+    let ca = include_bytes!("cloudflare_dns/ca.der");
+    let unrelated = include_bytes!("netflix/ca.der");
+
+    let mut store = TrustAnchorStore::new();
+    store.add_cert_der(unrelated).unwrap();
+    assert_eq!(check_cloudflare(&store), Err(webpki::Error::UnknownIssuer));
+
+    store.add_cert_der(ca).unwrap();
+    assert_eq!(store.len(), 2);
+    assert_eq!(check_cloudflare(&store), Ok(()));
+}
+
+#[test]
+fn trust_anchor_store_tries_every_anchor_with_subject() {
+    // An anchor with the right subject but the wrong key mustn't hide the right one.
+    let ca = include_bytes!("cloudflare_dns/ca.der");
+    let unrelated = include_bytes!("netflix/ca.der");
+    let decoy = || TrustAnchor {
+        spki: TrustAnchor::try_from_cert_der(unrelated).unwrap().spki,
+        ..TrustAnchor::try_from_cert_der(ca).unwrap()
+    };
+
+    let store = [decoy()].into_iter().collect::<TrustAnchorStore>();
+    assert_eq!(
+        check_cloudflare(&store),
+        Err(webpki::Error::InvalidSignatureForPublicKey)
+    );
+
+    let store = [decoy(), TrustAnchor::try_from_cert_der(ca).unwrap()]
+        .into_iter()
+        .collect::<TrustAnchorStore>();
+    assert_eq!(check_cloudflare(&store), Ok(()));
+}
+
+#[test]
+fn trust_anchors_from_vec() {
+    let ca = include_bytes!("cloudflare_dns/ca.der");
+    let anchors = vec![TrustAnchor::try_from_cert_der(ca).unwrap()];
+    assert_eq!(check_cloudflare(&anchors), Ok(()));
+    assert_eq!(check_cloudflare(anchors.as_slice()), Ok(()));
+}
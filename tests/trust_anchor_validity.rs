@@ -0,0 +1,118 @@
+#![cfg(all(feature = "alloc", feature = "ring"))]
+
+use webpki::{Error, KeyUsage, TrustAnchor, TrustAnchorStore, TrustAnchors, VerifyOptions};
+
+fn check<A: TrustAnchors + ?Sized>(
+    anchors: &A,
+    secs: u64,
+    options: &VerifyOptions,
+) -> Result<(), Error> {
+    let ee: &[u8] = include_bytes!("trust_anchor_validity/ee.der");
+    webpki::EndEntityCert::try_from(ee)
+        .unwrap()
+        .verify_for_usage_with_options(
+            &[webpki::ECDSA_P256_SHA256],
+            anchors,
+            &[],
+            webpki::Time::from_seconds_since_unix_epoch(secs),
+            KeyUsage::client_auth(),
+            None,
+            options,
+        )
+        .map(|_| ())
+        .map_err(Error::from)
+}
+
+/// Valid for 30 seconds either side of 0x1fed_f00d, unlike `ee.der`, which is valid for a year.
+const CA: &[u8] = include_bytes!("trust_anchor_validity/ca.der");
+
+#[test]
+fn trust_anchor_validity_enforced() {
+    let mut store = TrustAnchorStore::new();
+    store.add_cert_der(CA).unwrap();
+
+    let with_validity = VerifyOptions::new().with_trust_anchor_validity();
+    assert_eq!(check(&store, 0x1fed_f00d, &with_validity), Ok(()));
+    assert_eq!(
+        check(&store, 0x1fed_f00d + 31, &with_validity),
+        Err(Error::CertExpired)
+    );
+    assert_eq!(
+        check(&store, 0x1fed_f00d - 31, &with_validity),
+        Err(Error::CertNotValidYet)
+    );
+    assert_eq!(
+        check(&store, 0x1fed_f00d + 31, &VerifyOptions::new()),
+        Ok(())
+    );
+
+    // Anchors without a certificate have no validity period to enforce.
+    let anchor = TrustAnchor::try_from_cert_der(CA).unwrap();
+    assert_eq!(check(&[anchor], 0x1fed_f00d + 31, &with_validity), Ok(()));
+}
+
+#[test]
+fn trust_anchor_validity_from_v1_cert() {
+    let ee: &[u8] = include_bytes!("netflix/ee.der");
+    let inter: &[u8] = include_bytes!("netflix/inter.der");
+    let mut store = TrustAnchorStore::new();
+    store
+        .add_cert_der(include_bytes!("netflix/ca.der"))
+        .unwrap();
+
+    let cert = webpki::EndEntityCert::try_from(ee).unwrap();
+    let verify = |secs| {
+        cert.verify_for_usage_with_options(
+            &[webpki::RSA_PKCS1_2048_8192_SHA256],
+            &store,
+            &[inter],
+            webpki::Time::from_seconds_since_unix_epoch(secs),
+            KeyUsage::server_auth(),
+            None,
+            &VerifyOptions::new().with_trust_anchor_validity(),
+        )
+        .map(|_| ())
+        .map_err(Error::from)
+    };
+    assert_eq!(verify(1_492_441_716), Ok(()));
+}
+
+#[test]
+fn self_signed_trust_anchor() {
+    let algs = &[webpki::ECDSA_P256_SHA256];
+
+    let root = include_bytes!("trust_anchor_info/root.der");
+    let anchor = TrustAnchor::try_from_self_signed_cert_der(root, algs).unwrap();
+    assert_eq!(
+        anchor.subject,
+        TrustAnchor::try_from_cert_der(root).unwrap().subject
+    );
+
+    // The signature algorithm must be supported.
+    assert_eq!(
+        TrustAnchor::try_from_self_signed_cert_der(root, &[webpki::ED25519]).unwrap_err(),
+        Error::UnsupportedSignatureAlgorithm
+    );
+
+    assert_eq!(
+        TrustAnchor::try_from_self_signed_cert_der(
+            include_bytes!("trust_anchor_info/inter.der"),
+            algs
+        )
+        .unwrap_err(),
+        Error::TrustAnchorNotSelfSigned
+    );
+    assert_eq!(
+        TrustAnchor::try_from_self_signed_cert_der(
+            include_bytes!("partial_chain/ee.self_signed.der"),
+            algs
+        )
+        .unwrap_err(),
+        Error::EndEntityUsedAsCa
+    );
+    assert_eq!(
+        TrustAnchor::try_from_self_signed_cert_der(include_bytes!("netflix/ca.der"), algs)
+            .unwrap_err(),
+        Error::UnsupportedCertVersion
+    );
+}
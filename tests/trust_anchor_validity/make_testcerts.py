@@ -0,0 +1,69 @@
+"""
+Generates a CA and an end-entity certificate it issued, where the validity period of the CA is
+much shorter than that of the end-entity certificate, to test trust anchor validity checks.
+"""
+
+import datetime
+import functools
+import sys
+from pathlib import Path
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.x509.oid import ExtendedKeyUsageOID
+
+HERE = Path(__file__).parent
+sys.path.insert(0, str(HERE.parent))
+
+import testcerts  # noqa: E402
+from testcerts import write_der  # noqa: E402
+
+# The CA is valid for 30 seconds either side of this time.
+CA_TIME: int = 0x1FEDF00D
+CA_NOT_BEFORE: datetime.datetime = datetime.datetime.utcfromtimestamp(CA_TIME - 30)
+CA_NOT_AFTER: datetime.datetime = datetime.datetime.utcfromtimestamp(CA_TIME + 30)
+
+# The end-entity certificate is valid for a year either side.
+NOT_BEFORE: datetime.datetime = datetime.datetime.utcfromtimestamp(CA_TIME - 365 * 86400)
+NOT_AFTER: datetime.datetime = datetime.datetime.utcfromtimestamp(CA_TIME + 365 * 86400)
+
+name = functools.partial(testcerts.name, organization="trust anchor validity")
+
+
+def generate(force: bool) -> None:
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+    ca = (
+        x509.CertificateBuilder()
+        .subject_name(name("CA"))
+        .issuer_name(name("CA"))
+        .public_key(ca_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(CA_NOT_BEFORE)
+        .not_valid_after(CA_NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .sign(ca_key, hashes.SHA256())
+    )
+    write_der(HERE / "ca.der", ca.public_bytes(Encoding.DER), force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1())
+    ee = (
+        x509.CertificateBuilder()
+        .subject_name(name("ee.example.com"))
+        .issuer_name(ca.subject)
+        .public_key(ee_key.public_key())
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=False, path_length=None), critical=True)
+        .add_extension(
+            x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]), critical=False
+        )
+        .sign(ca_key, hashes.SHA256())
+    )
+    write_der(HERE / "ee.der", ee.public_bytes(Encoding.DER), force)
+
+
+if __name__ == "__main__":
+    testcerts.main(generate)
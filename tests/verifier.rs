@@ -0,0 +1,315 @@
+#![cfg(all(feature = "alloc", feature = "ring"))]
+
+use std::sync::Arc;
+use std::thread;
+
+use webpki::{
+    BorrowedCertRevocationList, EndEntityCert, Error, IntermediateStore, KeyUsage,
+    OwnedCertRevocationList, TrustAnchorStore, Verifier,
+};
+
+static ALGS: &[&dyn webpki::SignatureVerificationAlgorithm] = &[webpki::ECDSA_P256_SHA256];
+
+fn time() -> webpki::Time {
+    webpki::Time::from_seconds_since_unix_epoch(0x1fed_f00d)
+}
+
+fn crl(der: &[u8]) -> OwnedCertRevocationList {
+    BorrowedCertRevocationList::from_der(der)
+        .unwrap()
+        .to_owned()
+        .unwrap()
+}
+
+fn build_verifier(
+    root: &'static [u8],
+    intermediates: &[&[u8]],
+    crls: &[&[u8]],
+) -> Result<Verifier<'static>, Error> {
+    let mut anchors = TrustAnchorStore::new();
+    anchors.add_cert_der(root).unwrap();
+    let mut store = IntermediateStore::new();
+    for der in intermediates {
+        store.add(der).unwrap();
+    }
+
+    let mut verifier = Verifier::new(ALGS, anchors).with_intermediate_store(store)?;
+    for der in crls {
+        verifier.add_crl(crl(der))?;
+    }
+    Ok(verifier)
+}
+
+fn check(verifier: &Verifier, ee: &[u8], intermediates: &[&[u8]]) -> Result<(), Error> {
+    verifier
+        .verify(
+            &EndEntityCert::try_from(ee).unwrap(),
+            intermediates,
+            time(),
+            KeyUsage::client_auth(),
+        )
+        .map(|_| ())
+        .map_err(Error::from)
+}
+
+const NO_KU_EE: &[u8] = include_bytes!("client_auth_revocation/no_ku_chain.ee.der");
+const NO_KU_INT_A: &[u8] =
+    include_bytes!("client_auth_revocation/no_ku_chain.int.a.ca.der").as_slice();
+const NO_KU_INT_B: &[u8] =
+    include_bytes!("client_auth_revocation/no_ku_chain.int.b.ca.der").as_slice();
+const NO_KU_ROOT: &[u8] =
+    include_bytes!("client_auth_revocation/no_ku_chain.root.ca.der").as_slice();
+
+#[test]
+fn verifier_uses_stored_intermediates() {
+    let verifier = build_verifier(NO_KU_ROOT, &[NO_KU_INT_A, NO_KU_INT_B], &[]).unwrap();
+    assert_eq!(check(&verifier, NO_KU_EE, &[]), Ok(()));
+
+    let verifier = build_verifier(NO_KU_ROOT, &[], &[]).unwrap();
+    assert_eq!(check(&verifier, NO_KU_EE, &[]), Err(Error::UnknownIssuer));
+    assert_eq!(
+        check(&verifier, NO_KU_EE, &[NO_KU_INT_A, NO_KU_INT_B]),
+        Ok(())
+    );
+}
+
+#[test]
+fn verifier_checks_crls() {
+    let verifier = build_verifier(
+        NO_KU_ROOT,
+        &[NO_KU_INT_A, NO_KU_INT_B],
+        &[include_bytes!("client_auth_revocation/ee_not_revoked_ee_depth.crl.der").as_slice()],
+    )
+    .unwrap();
+    assert_eq!(check(&verifier, NO_KU_EE, &[]), Ok(()));
+
+    let verifier = build_verifier(
+        include_bytes!("client_auth_revocation/ku_chain.root.ca.der").as_slice(),
+        &[
+            include_bytes!("client_auth_revocation/ku_chain.int.a.ca.der").as_slice(),
+            include_bytes!("client_auth_revocation/ku_chain.int.b.ca.der").as_slice(),
+        ],
+        &[include_bytes!("client_auth_revocation/ee_revoked_crl_ku_ee_depth.crl.der").as_slice()],
+    )
+    .unwrap();
+    assert_eq!(
+        check(
+            &verifier,
+            include_bytes!("client_auth_revocation/ku_chain.ee.der"),
+            &[]
+        ),
+        Err(Error::CertRevoked)
+    );
+}
+
+#[test]
+fn verifier_rejects_crl_with_bad_signature_up_front() {
+    assert_eq!(
+        build_verifier(
+            NO_KU_ROOT,
+            &[NO_KU_INT_A, NO_KU_INT_B],
+            &[
+                include_bytes!("client_auth_revocation/ee_revoked_badsig_ee_depth.crl.der")
+                    .as_slice()
+            ],
+        )
+        .err(),
+        Some(Error::InvalidCrlSignatureForPublicKey)
+    );
+}
+
+#[test]
+fn verifier_validates_crls_regardless_of_store_order() {
+    let build = |crl_der: &[u8]| {
+        let mut anchors = TrustAnchorStore::new();
+        anchors.add_cert_der(NO_KU_ROOT).unwrap();
+        let mut store = IntermediateStore::new();
+        store.add(NO_KU_INT_A).unwrap();
+        store.add(NO_KU_INT_B).unwrap();
+
+        // The CRL is added before the store holding its issuer.
+        let mut verifier = Verifier::new(ALGS, anchors);
+        verifier.add_crl(crl(crl_der)).unwrap();
+        verifier.with_intermediate_store(store)
+    };
+
+    let verifier = build(include_bytes!(
+        "client_auth_revocation/ee_not_revoked_ee_depth.crl.der"
+    ))
+    .unwrap();
+    assert_eq!(check(&verifier, NO_KU_EE, &[]), Ok(()));
+
+    assert_eq!(
+        build(include_bytes!(
+            "client_auth_revocation/ee_revoked_badsig_ee_depth.crl.der"
+        ))
+        .err(),
+        Some(Error::InvalidCrlSignatureForPublicKey)
+    );
+}
+
+#[test]
+fn verifier_checks_crl_with_unknown_issuer_per_lookup() {
+    // The CRL's issuer is only supplied with each verification, so the signature can't be
+    // checked until then.
+    let verifier = build_verifier(
+        NO_KU_ROOT,
+        &[],
+        &[include_bytes!("client_auth_revocation/ee_revoked_badsig_ee_depth.crl.der").as_slice()],
+    )
+    .unwrap();
+    assert_eq!(
+        check(&verifier, NO_KU_EE, &[NO_KU_INT_A, NO_KU_INT_B]),
+        Err(Error::InvalidCrlSignatureForPublicKey)
+    );
+}
+
+#[test]
+fn verifier_is_shareable_across_threads() {
+    let verifier = Arc::new(
+        build_verifier(
+            NO_KU_ROOT,
+            &[NO_KU_INT_A, NO_KU_INT_B],
+            &[include_bytes!("client_auth_revocation/ee_not_revoked_ee_depth.crl.der").as_slice()],
+        )
+        .unwrap(),
+    );
+
+    let handles = (0..4)
+        .map(|_| {
+            let verifier = verifier.clone();
+            thread::spawn(move || check(&verifier, NO_KU_EE, &[]))
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Ok(()));
+    }
+}
+
+#[test]
+fn batch_verifier_returns_result_per_leaf() {
+    let verifier = build_verifier(NO_KU_ROOT, &[NO_KU_INT_A, NO_KU_INT_B], &[]).unwrap();
+    let batch = verifier.batch();
+
+    let results = batch.verify_all(
+        [
+            (NO_KU_EE, [].as_slice()),
+            (
+                include_bytes!("client_auth_revocation/no_ku_chain.topbit.ee.der").as_slice(),
+                &[],
+            ),
+            (
+                include_bytes!("client_auth_revocation/ku_chain.ee.der").as_slice(),
+                &[],
+            ),
+            (b"not a certificate".as_slice(), &[]),
+        ],
+        time(),
+        KeyUsage::client_auth(),
+    );
+    let results = results
+        .into_iter()
+        .map(|result| result.map(|_| ()).map_err(Error::from))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        [
+            Ok(()),
+            Ok(()),
+            Err(Error::UnknownIssuer),
+            Err(Error::BadDer)
+        ]
+    );
+}
+
+#[test]
+fn batch_verifier_uses_intermediates_per_leaf() {
+    // Only the intermediate nearest the root is stored; the other is supplied with each leaf.
+    let verifier = build_verifier(NO_KU_ROOT, &[NO_KU_INT_B], &[]).unwrap();
+    let batch = verifier.batch();
+
+    let results = batch.verify_all(
+        [(NO_KU_EE, [NO_KU_INT_A].as_slice()), (NO_KU_EE, &[])],
+        time(),
+        KeyUsage::client_auth(),
+    );
+    let results = results
+        .into_iter()
+        .map(|result| result.map(|_| ()).map_err(Error::from))
+        .collect::<Vec<_>>();
+    assert_eq!(results, [Ok(()), Err(Error::UnknownIssuer)]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn batch_verifier_verifies_in_parallel() {
+    let verifier = build_verifier(NO_KU_ROOT, &[NO_KU_INT_B], &[]).unwrap();
+    let batch = verifier.batch();
+
+    let end_entities = (0..64)
+        .map(|i| match i % 3 {
+            0 => (NO_KU_EE, [NO_KU_INT_A].as_slice()),
+            1 => (NO_KU_EE, [].as_slice()),
+            _ => (b"not a certificate".as_slice(), [].as_slice()),
+        })
+        .collect::<Vec<_>>();
+    let expected = batch
+        .verify_all(
+            end_entities.iter().copied(),
+            time(),
+            KeyUsage::client_auth(),
+        )
+        .into_iter()
+        .map(|result| result.map(|_| ()).map_err(Error::from))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        expected[..3],
+        [Ok(()), Err(Error::UnknownIssuer), Err(Error::BadDer)]
+    );
+
+    // The results are in the same order as the sequential ones.
+    let results = batch
+        .verify_all_parallel(&end_entities, time(), KeyUsage::client_auth())
+        .into_iter()
+        .map(|result| result.map(|_| ()).map_err(Error::from))
+        .collect::<Vec<_>>();
+    assert_eq!(results, expected);
+
+    assert!(batch
+        .verify_all_parallel(&[], time(), KeyUsage::client_auth())
+        .is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn batch_verifier_only_verifies_end_entity_signatures() {
+    let cache = webpki::BoundedSignatureCache::new(16);
+    let mut anchors = TrustAnchorStore::new();
+    anchors.add_cert_der(NO_KU_ROOT).unwrap();
+    let mut store = IntermediateStore::new();
+    store.add(NO_KU_INT_A).unwrap();
+    store.add(NO_KU_INT_B).unwrap();
+    let verifier = Verifier::new(ALGS, anchors)
+        .with_intermediate_store(store)
+        .unwrap()
+        .with_signature_cache(&cache);
+
+    let batch = verifier.batch();
+    assert_eq!(
+        batch
+            .verify(
+                &EndEntityCert::try_from(NO_KU_EE).unwrap(),
+                &[],
+                time(),
+                KeyUsage::client_auth()
+            )
+            .map(|_| ())
+            .map_err(Error::from),
+        Ok(())
+    );
+
+    // The intermediates' signatures were verified when the batch was constructed, so only the
+    // end-entity certificate's signature was verified, and recorded in the cache.
+    assert_eq!(cache.len(), 1);
+}